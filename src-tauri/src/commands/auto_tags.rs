@@ -0,0 +1,126 @@
+// Offline tagging: keywords ranked against the whole transcript corpus, stored as transcript tags
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tauri::State;
+use crate::commands::transcripts::{load_transcript_segments, store_transcript_tags, ExtractedTag};
+use crate::db::Database;
+use crate::services::keywords::{extract_keywords, CorpusStats};
+
+// Category given to tags created by offline extraction
+const KEYWORD_TAG_CATEGORY: &str = "keyword";
+const DEFAULT_MAX_TAGS: usize = 10;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AutoTagRequest {
+    pub transcript_ids: Option<Vec<String>>, // All transcripts when omitted
+    pub max_tags: Option<usize>, // Per transcript, default 10
+    pub replace_existing: Option<bool>, // Remove the transcript's current tags first; otherwise tags are merged
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AutoTagResult {
+    pub transcript_id: String,
+    pub tags: Vec<ExtractedTag>,
+}
+
+// Extract and store tags without any network calls. Relevance scores combine TF-IDF against all
+// imported transcripts with RAKE phrase scores, so adding transcripts sharpens later runs.
+#[tauri::command]
+pub async fn extract_tags_offline(
+    database: State<'_, Arc<Database>>,
+    request: AutoTagRequest,
+) -> Result<Vec<AutoTagResult>, String> {
+    let max_tags = request.max_tags.unwrap_or(DEFAULT_MAX_TAGS).clamp(1, 50);
+    let replace_existing = request.replace_existing.unwrap_or(false);
+
+    let transcripts = sqlx::query_as::<_, (String, String, String)>(
+        "SELECT TranscriptID, Language, Content FROM Transcript"
+    )
+    .fetch_all(&database.pool)
+    .await
+    .map_err(|e| format!("Failed to fetch transcripts: {}", e))?;
+
+    let mut corpus = CorpusStats::default();
+    for (_, language, content) in &transcripts {
+        corpus.add_document(language, content);
+    }
+
+    let targets: Vec<(String, String)> = match &request.transcript_ids {
+        Some(ids) => {
+            let mut targets = Vec::new();
+            for id in ids {
+                let (_, language, _) = transcripts
+                    .iter()
+                    .find(|(transcript_id, _, _)| transcript_id == id)
+                    .ok_or_else(|| format!("Transcript not found: {}", id))?;
+                targets.push((id.clone(), language.clone()));
+            }
+            targets
+        }
+        None => transcripts.iter().map(|(id, language, _)| (id.clone(), language.clone())).collect(),
+    };
+
+    let mut results = Vec::new();
+
+    for (transcript_id, language) in targets {
+        // Segment text leaves out speaker labels, which would otherwise rank as keywords
+        let segments = load_transcript_segments(&database.pool, &transcript_id)
+            .await
+            .map_err(|e| format!("Failed to fetch transcript segments: {}", e))?;
+        let text = segments.iter().map(|s| s.text.as_str()).collect::<Vec<_>>().join("\n");
+
+        let tags: Vec<ExtractedTag> = extract_keywords(&text, &language, &corpus, max_tags)
+            .into_iter()
+            .map(|keyword| ExtractedTag {
+                tag: keyword.term,
+                relevance: keyword.relevance,
+            })
+            .collect();
+
+        let mut tx = database.pool.begin()
+            .await
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+        if replace_existing {
+            sqlx::query("DELETE FROM TranscriptTags WHERE TranscriptID = ?1")
+                .bind(&transcript_id)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| format!("Failed to remove existing tags: {}", e))?;
+        }
+
+        store_transcript_tags(&mut tx, &transcript_id, &tags, Some(KEYWORD_TAG_CATEGORY)).await?;
+
+        // Keyword tags only exist because extraction produced them; drop the ones nothing uses anymore,
+        // unless they have been organized into the taxonomy
+        if replace_existing {
+            sqlx::query(
+                r#"
+                DELETE FROM Tag
+                WHERE Category = ?1
+                  AND NOT EXISTS(SELECT 1 FROM TranscriptTags tt WHERE tt.TagID = Tag.TagID)
+                  AND NOT EXISTS(SELECT 1 FROM Tag child WHERE child.ParentTagID = Tag.TagID)
+                  AND NOT EXISTS(SELECT 1 FROM TagAlias ta WHERE ta.TagID = Tag.TagID)
+                "#
+            )
+            .bind(KEYWORD_TAG_CATEGORY)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| format!("Failed to remove unused tags: {}", e))?;
+        }
+
+        sqlx::query("UPDATE Transcript SET AnalyzedAt = datetime('now') WHERE TranscriptID = ?1")
+            .bind(&transcript_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| format!("Failed to update transcript: {}", e))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| format!("Failed to commit transaction: {}", e))?;
+
+        results.push(AutoTagResult { transcript_id, tags });
+    }
+
+    Ok(results)
+}
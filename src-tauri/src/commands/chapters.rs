@@ -0,0 +1,250 @@
+// Chapter generation for timed transcripts and export as YouTube or WebVTT chapters
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+use tauri::State;
+use uuid::Uuid;
+use crate::commands::transcripts::load_transcript_segments;
+use crate::db::Database;
+use crate::services::chapters::{generate_chapters, render_vtt_chapters, render_youtube_chapters, Chapter, ChapterOptions};
+
+const DEFAULT_MIN_CHAPTER_SECONDS: f64 = 60.0;
+const DEFAULT_MAX_CHAPTERS: usize = 20;
+// YouTube only shows chapters when the description lists at least this many
+const YOUTUBE_MIN_CHAPTERS: usize = 3;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GenerateChaptersRequest {
+    pub transcript_id: String,
+    pub min_chapter_seconds: Option<f64>, // Default 60, never below 10
+    pub max_chapters: Option<usize>, // Default 20
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TranscriptChapterInfo {
+    pub chapter_id: String,
+    pub chapter_index: i64,
+    pub start_time: f64,
+    pub end_time: f64,
+    pub start_segment: i64,
+    pub end_segment: i64,
+    pub title: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ChapterExportFormat {
+    Youtube, // "00:00 Intro" lines for a video description
+    Vtt, // WebVTT chapters track
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ChapterExportRequest {
+    pub transcript_id: String,
+    pub format: ChapterExportFormat,
+    pub output_path: Option<String>, // Only returned as text when omitted
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ChapterExportResult {
+    pub format: ChapterExportFormat,
+    pub content: String,
+    pub chapter_count: usize,
+    pub output_path: Option<String>,
+    pub warnings: Vec<String>,
+}
+
+async fn load_chapters(pool: &sqlx::SqlitePool, transcript_id: &str) -> Result<Vec<TranscriptChapterInfo>, String> {
+    let rows = sqlx::query_as::<_, (String, i64, f64, f64, i64, i64, String)>(
+        r#"
+        SELECT ChapterID, ChapterIndex, StartTime, EndTime, StartSegment, EndSegment, Title
+        FROM TranscriptChapter
+        WHERE TranscriptID = ?1
+        ORDER BY ChapterIndex
+        "#
+    )
+    .bind(transcript_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Failed to fetch chapters: {}", e))?;
+
+    Ok(rows
+        .into_iter()
+        .map(|(chapter_id, chapter_index, start_time, end_time, start_segment, end_segment, title)| TranscriptChapterInfo {
+            chapter_id,
+            chapter_index,
+            start_time,
+            end_time,
+            start_segment,
+            end_segment,
+            title,
+        })
+        .collect())
+}
+
+// Segment the transcript into chapters and replace any stored ones
+async fn generate_and_store(
+    pool: &sqlx::SqlitePool,
+    transcript_id: &str,
+    options: &ChapterOptions,
+) -> Result<Vec<TranscriptChapterInfo>, String> {
+    let (language,) = sqlx::query_as::<_, (String,)>("SELECT Language FROM Transcript WHERE TranscriptID = ?1")
+        .bind(transcript_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| format!("Failed to fetch transcript: {}", e))?
+        .ok_or_else(|| "Transcript not found".to_string())?;
+
+    let segments = load_transcript_segments(pool, transcript_id)
+        .await
+        .map_err(|e| format!("Failed to fetch transcript segments: {}", e))?;
+
+    let chapters = generate_chapters(&segments, &language, options);
+    if chapters.is_empty() {
+        return Err("Transcript has no timing information to build chapters from".to_string());
+    }
+
+    let mut tx = pool.begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    sqlx::query("DELETE FROM TranscriptChapter WHERE TranscriptID = ?1")
+        .bind(transcript_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to clear chapters: {}", e))?;
+
+    for (index, chapter) in chapters.iter().enumerate() {
+        let Chapter { start_time, end_time, first_segment, last_segment, title } = chapter;
+        sqlx::query(
+            r#"
+            INSERT INTO TranscriptChapter (ChapterID, TranscriptID, ChapterIndex, StartTime, EndTime, StartSegment, EndSegment, Title)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+            "#
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(transcript_id)
+        .bind(index as i64)
+        .bind(start_time)
+        .bind(end_time)
+        .bind(*first_segment as i64)
+        .bind(*last_segment as i64)
+        .bind(title)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to store chapter: {}", e))?;
+    }
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit transaction: {}", e))?;
+
+    load_chapters(pool, transcript_id).await
+}
+
+#[tauri::command]
+pub async fn generate_transcript_chapters(
+    database: State<'_, Arc<Database>>,
+    request: GenerateChaptersRequest,
+) -> Result<Vec<TranscriptChapterInfo>, String> {
+    let options = ChapterOptions {
+        min_chapter_seconds: request.min_chapter_seconds.unwrap_or(DEFAULT_MIN_CHAPTER_SECONDS),
+        max_chapters: request.max_chapters.unwrap_or(DEFAULT_MAX_CHAPTERS).clamp(1, 100),
+    };
+
+    generate_and_store(&database.pool, &request.transcript_id, &options).await
+}
+
+#[tauri::command]
+pub async fn get_transcript_chapters(
+    database: State<'_, Arc<Database>>,
+    transcript_id: String,
+) -> Result<Vec<TranscriptChapterInfo>, String> {
+    load_chapters(&database.pool, &transcript_id).await
+}
+
+#[tauri::command]
+pub async fn rename_chapter(
+    database: State<'_, Arc<Database>>,
+    chapter_id: String,
+    title: String,
+) -> Result<(), String> {
+    let title = title.trim();
+    if title.is_empty() {
+        return Err("Chapter title cannot be empty".to_string());
+    }
+
+    let result = sqlx::query("UPDATE TranscriptChapter SET Title = ?1 WHERE ChapterID = ?2")
+        .bind(title)
+        .bind(&chapter_id)
+        .execute(&database.pool)
+        .await
+        .map_err(|e| format!("Failed to rename chapter: {}", e))?;
+
+    if result.rows_affected() == 0 {
+        return Err("Chapter not found".to_string());
+    }
+
+    Ok(())
+}
+
+// Render the stored chapters, generating them with default settings if there are none yet
+#[tauri::command]
+pub async fn export_chapters(
+    database: State<'_, Arc<Database>>,
+    request: ChapterExportRequest,
+) -> Result<ChapterExportResult, String> {
+    let mut stored = load_chapters(&database.pool, &request.transcript_id).await?;
+    if stored.is_empty() {
+        let options = ChapterOptions {
+            min_chapter_seconds: DEFAULT_MIN_CHAPTER_SECONDS,
+            max_chapters: DEFAULT_MAX_CHAPTERS,
+        };
+        stored = generate_and_store(&database.pool, &request.transcript_id, &options).await?;
+    }
+
+    let chapters: Vec<Chapter> = stored
+        .iter()
+        .map(|c| Chapter {
+            start_time: c.start_time,
+            end_time: c.end_time,
+            first_segment: c.start_segment as usize,
+            last_segment: c.end_segment as usize,
+            title: c.title.clone(),
+        })
+        .collect();
+
+    let mut warnings = Vec::new();
+    let content = match request.format {
+        ChapterExportFormat::Youtube => {
+            if chapters.len() < YOUTUBE_MIN_CHAPTERS {
+                warnings.push(format!(
+                    "YouTube only shows chapters when there are at least {}; this transcript has {}",
+                    YOUTUBE_MIN_CHAPTERS,
+                    chapters.len()
+                ));
+            }
+            render_youtube_chapters(&chapters)
+        }
+        ChapterExportFormat::Vtt => render_vtt_chapters(&chapters),
+    };
+
+    if let Some(output_path) = &request.output_path {
+        let path = Path::new(output_path);
+        if let Some(parent) = path.parent() {
+            if !parent.as_os_str().is_empty() && !parent.exists() {
+                return Err(format!("Export folder does not exist: {}", parent.display()));
+            }
+        }
+        fs::write(path, &content).map_err(|e| format!("Failed to write export file: {}", e))?;
+    }
+
+    Ok(ChapterExportResult {
+        format: request.format,
+        content,
+        chapter_count: chapters.len(),
+        output_path: request.output_path,
+        warnings,
+    })
+}
@@ -0,0 +1,104 @@
+// Finding short-form clip candidates in timed transcripts
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tauri::State;
+use crate::commands::transcript_links::is_video_path;
+use crate::commands::transcripts::{load_transcript_segments, LinkedVideo};
+use crate::db::Database;
+use crate::services::clip_finder::{find_clips, ClipCandidate, ClipOptions};
+use crate::services::keywords::{extract_keywords, CorpusStats};
+
+const DEFAULT_MIN_DURATION: f64 = 15.0;
+const DEFAULT_MAX_DURATION: f64 = 90.0;
+const DEFAULT_CLIP_LIMIT: usize = 10;
+// Transcript keywords used for the keyword density signal
+const CLIP_KEYWORDS: usize = 15;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ClipSearchRequest {
+    pub transcript_id: String,
+    pub min_duration: Option<f64>, // Seconds, default 15
+    pub max_duration: Option<f64>, // Seconds, default 90
+    pub limit: Option<usize>, // Default 10
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ClipSearchResponse {
+    pub transcript_id: String,
+    pub video: Option<LinkedVideo>, // The video to cut the clips from, if the transcript is linked to one
+    pub keywords: Vec<String>,
+    pub candidates: Vec<ClipCandidate>,
+}
+
+// Rank non-overlapping clip windows in a transcript, best first
+#[tauri::command]
+pub async fn find_clip_candidates(
+    database: State<'_, Arc<Database>>,
+    request: ClipSearchRequest,
+) -> Result<ClipSearchResponse, String> {
+    let min_duration = request.min_duration.unwrap_or(DEFAULT_MIN_DURATION);
+    let max_duration = request.max_duration.unwrap_or(DEFAULT_MAX_DURATION);
+    if min_duration <= 0.0 || max_duration < min_duration {
+        return Err(format!("Invalid clip duration range {}–{} seconds", min_duration, max_duration));
+    }
+    let options = ClipOptions {
+        min_duration,
+        max_duration,
+        limit: request.limit.unwrap_or(DEFAULT_CLIP_LIMIT).clamp(1, 100),
+    };
+
+    let (language, file_id, file_path, original_name, duration) =
+        sqlx::query_as::<_, (String, String, String, String, Option<f64>)>(
+            r#"
+            SELECT t.Language, f.FileID, f.FilePath, f.OriginalName, f.Duration
+            FROM Transcript t
+            JOIN File f ON t.FileID = f.FileID
+            WHERE t.TranscriptID = ?1
+            "#
+        )
+        .bind(&request.transcript_id)
+        .fetch_optional(&database.pool)
+        .await
+        .map_err(|e| format!("Failed to fetch transcript: {}", e))?
+        .ok_or_else(|| "Transcript not found".to_string())?;
+
+    let segments = load_transcript_segments(&database.pool, &request.transcript_id)
+        .await
+        .map_err(|e| format!("Failed to fetch transcript segments: {}", e))?;
+    if segments.iter().all(|s| s.start_time.is_none() || s.end_time.is_none()) {
+        return Err("Transcript has no timestamps to cut clips from".to_string());
+    }
+
+    // Keywords ranked against the whole corpus, as for offline tagging
+    let corpus_rows = sqlx::query_as::<_, (String, String)>("SELECT Language, Content FROM Transcript")
+        .fetch_all(&database.pool)
+        .await
+        .map_err(|e| format!("Failed to fetch transcripts: {}", e))?;
+    let mut corpus = CorpusStats::default();
+    for (corpus_language, content) in &corpus_rows {
+        corpus.add_document(corpus_language, content);
+    }
+
+    let text = segments.iter().map(|s| s.text.as_str()).collect::<Vec<_>>().join("\n");
+    let keywords: Vec<String> = extract_keywords(&text, &language, &corpus, CLIP_KEYWORDS)
+        .into_iter()
+        .map(|k| k.term)
+        .collect();
+
+    let candidates = find_clips(&segments, &language, &keywords, &options);
+
+    let is_video = is_video_path(&file_path);
+    let video = is_video.then_some(LinkedVideo {
+        file_id,
+        file_path,
+        original_name,
+        duration,
+    });
+
+    Ok(ClipSearchResponse {
+        transcript_id: request.transcript_id,
+        video,
+        keywords,
+        candidates,
+    })
+}
@@ -1,6 +1,9 @@
+use crate::commands::content_versions::{ensure_baseline_version, record_version};
+use crate::commands::redaction::{record_rejected_scan, scan_target, RedactionTarget};
 use crate::db::Database;
 use serde::{Deserialize, Serialize};
-use sqlx::FromRow;
+use sqlx::{FromRow, Sqlite, Transaction};
+use std::sync::Arc;
 use tauri::State;
 use uuid::Uuid;
 
@@ -33,7 +36,7 @@ pub struct SaveContentRequest {
     pub template_id: String,
     pub title: String,
     pub content: serde_json::Value,
-    pub source_transcript_ids: Vec<String>,
+    pub source_transcript_ids: Vec<String>, // The tracks the content was generated from
 }
 
 #[derive(Debug, Deserialize)]
@@ -41,11 +44,12 @@ pub struct UpdateContentRequest {
     pub title: Option<String>,
     pub content_data: Option<String>,
     pub status: Option<String>,
+    pub change_summary: Option<String>, // Recorded on the new version; described from the diff otherwise
 }
 
 #[tauri::command]
 pub async fn save_generated_content(
-    db: State<'_, Database>,
+    db: State<'_, Arc<Database>>,
     request: SaveContentRequest,
 ) -> Result<String, String> {
     let content_id = Uuid::new_v4().to_string();
@@ -104,7 +108,7 @@ pub async fn save_generated_content(
 
 #[tauri::command]
 pub async fn get_all_content(
-    db: State<'_, Database>,
+    db: State<'_, Arc<Database>>,
 ) -> Result<Vec<ContentWithTemplate>, String> {
     let content = sqlx::query_as::<_, ContentWithTemplate>(
         r#"
@@ -132,7 +136,7 @@ pub async fn get_all_content(
 
 #[tauri::command]
 pub async fn get_content_by_id(
-    db: State<'_, Database>,
+    db: State<'_, Arc<Database>>,
     content_id: String,
 ) -> Result<ContentWithTemplate, String> {
     let content = sqlx::query_as::<_, ContentWithTemplate>(
@@ -162,7 +166,7 @@ pub async fn get_content_by_id(
 
 #[tauri::command]
 pub async fn update_content(
-    db: State<'_, Database>,
+    db: State<'_, Arc<Database>>,
     content_id: String,
     request: UpdateContentRequest,
 ) -> Result<(), String> {
@@ -170,6 +174,8 @@ pub async fn update_content(
     let mut updates = Vec::new();
     let mut bind_values: Vec<String> = Vec::new();
     
+    // The title is scanned for redaction hits along with the content data
+    let content_changed = request.title.is_some() || request.content_data.is_some();
     if let Some(title) = request.title {
         updates.push("title = ?");
         bind_values.push(title);
@@ -180,7 +186,8 @@ pub async fn update_content(
         bind_values.push(content_data);
     }
     
-    if let Some(status) = request.status {
+    let status_changed = request.status.is_some();
+    if let Some(status) = request.status.clone() {
         updates.push("status = ?");
         bind_values.push(status);
     }
@@ -192,22 +199,115 @@ pub async fn update_content(
     query.push_str(&updates.join(", "));
     query.push_str(" WHERE content_id = ?");
     
+    let mut tx = db.pool.begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    let (current_status,) = sqlx::query_as::<_, (String,)>("SELECT status FROM GeneratedContent WHERE content_id = ?")
+        .bind(&content_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to fetch content: {}", e))?
+        .ok_or_else(|| "Content not found".to_string())?;
+    let status = request.status.unwrap_or(current_status);
+
+    ensure_baseline_version(&mut tx, &content_id).await?;
+
     let mut q = sqlx::query(&query);
     for value in bind_values {
         q = q.bind(value);
     }
     q = q.bind(&content_id);
     
-    q.execute(&db.pool)
+    q.execute(&mut *tx)
         .await
         .map_err(|e| format!("Failed to update content: {}", e))?;
+
+    record_version(&mut tx, &content_id, request.change_summary.as_deref()).await?;
+
+    // Content can't be Ready or Published while redaction hits are unresolved, so it is rescanned
+    // when it moves there and when it is edited there
+    if matches!(status.as_str(), "Ready" | "Published") && (status_changed || content_changed) {
+        let action = if status_changed {
+            format!("marking this content {}", status)
+        } else {
+            format!("saving changes to content that is {}", status)
+        };
+        return commit_if_publishable(&db, tx, &content_id, &action).await;
+    }
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit transaction: {}", e))?;
     
     Ok(())
 }
 
+// Commit a change to content that is Ready or Published, once a scan of the changed content finds
+// no open redaction hits. Otherwise the change is rolled back and the error names what is left to
+// resolve. The hits are kept either way: on the stored content when only the status changed, or
+// as pending hits of the rejected edit, whose decisions carry over when it is saved again.
+pub(crate) async fn commit_if_publishable(
+    database: &Database,
+    mut tx: Transaction<'_, Sqlite>,
+    content_id: &str,
+    action: &str,
+) -> Result<(), String> {
+    let report = scan_target(&mut tx, RedactionTarget::Content, content_id).await?;
+    if report.open_count == 0 {
+        return tx.commit()
+            .await
+            .map_err(|e| format!("Failed to commit transaction: {}", e));
+    }
+
+    let mut categories: Vec<&str> = report
+        .hits
+        .iter()
+        .filter(|h| h.status == "open")
+        .map(|h| h.category.as_str())
+        .collect();
+    categories.sort_unstable();
+    categories.dedup();
+    let message = format!(
+        "Resolve the {} open redaction hit(s) ({}) before {}",
+        report.open_count,
+        categories.join(", "),
+        action
+    );
+
+    let select = "SELECT title, content_data FROM GeneratedContent WHERE content_id = ?";
+    let (title, content_data) = sqlx::query_as::<_, (Option<String>, String)>(select)
+        .bind(content_id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to fetch content: {}", e))?;
+    tx.rollback()
+        .await
+        .map_err(|e| format!("Failed to roll back transaction: {}", e))?;
+
+    let stored = sqlx::query_as::<_, (Option<String>, String)>(select)
+        .bind(content_id)
+        .fetch_one(&database.pool)
+        .await
+        .map_err(|e| format!("Failed to fetch content: {}", e))?;
+    if stored == (title.clone(), content_data.clone()) {
+        let mut scan_tx = database.pool.begin()
+            .await
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+        scan_target(&mut scan_tx, RedactionTarget::Content, content_id).await?;
+        scan_tx.commit()
+            .await
+            .map_err(|e| format!("Failed to commit transaction: {}", e))?;
+    } else {
+        record_rejected_scan(database, content_id, title.as_deref(), &content_data, &report).await?;
+    }
+
+    Err(message)
+}
+
 #[tauri::command]
 pub async fn delete_content(
-    db: State<'_, Database>,
+    db: State<'_, Arc<Database>>,
     content_id: String,
 ) -> Result<(), String> {
     sqlx::query("DELETE FROM GeneratedContent WHERE content_id = ?")
@@ -215,13 +315,19 @@ pub async fn delete_content(
         .execute(&db.pool)
         .await
         .map_err(|e| format!("Failed to delete content: {}", e))?;
+
+    sqlx::query("DELETE FROM RedactionHit WHERE TargetType = 'content' AND TargetID = ?")
+        .bind(&content_id)
+        .execute(&db.pool)
+        .await
+        .map_err(|e| format!("Failed to delete redaction hits: {}", e))?;
     
     Ok(())
 }
 
 #[tauri::command]
 pub async fn search_content(
-    db: State<'_, Database>,
+    db: State<'_, Arc<Database>>,
     query: String,
 ) -> Result<Vec<ContentWithTemplate>, String> {
     let search_pattern = format!("%{}%", query);
@@ -251,4 +357,83 @@ pub async fn search_content(
     .map_err(|e| format!("Failed to search content: {}", e))?;
 
     Ok(content)
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::redaction::{resolve_hit, HitResolution};
+
+    async fn edit(database: &Database, content_id: &str, content_data: &str) -> Result<(), String> {
+        let mut tx = database.pool.begin().await.unwrap();
+        sqlx::query("UPDATE GeneratedContent SET content_data = ? WHERE content_id = ?")
+            .bind(content_data)
+            .bind(content_id)
+            .execute(&mut *tx)
+            .await
+            .unwrap();
+        commit_if_publishable(database, tx, content_id, "saving changes to content that is Ready").await
+    }
+
+    #[tokio::test]
+    async fn ignored_hits_of_a_rejected_edit_carry_over_to_the_retry() {
+        let database = Database::new_in_memory().await.unwrap();
+        database.migrate().await.unwrap();
+        sqlx::query(
+            "INSERT INTO GeneratedContent (content_id, template_id, title, content_data, status) VALUES ('gate', 'default-thread', 'Launch', '{\"body\": \"Nothing personal\"}', 'Ready')"
+        )
+        .execute(&database.pool)
+        .await
+        .unwrap();
+
+        // The email blocks the edit, which isn't saved, but its hit is kept for the edit
+        let proposed = r#"{"body": "Write to press@example.com"}"#;
+        let error = edit(&database, "gate", proposed).await.unwrap_err();
+        assert!(error.contains("1 open redaction hit(s) (email)"), "{}", error);
+        let (stored,) = sqlx::query_as::<_, (String,)>("SELECT content_data FROM GeneratedContent WHERE content_id = 'gate'")
+            .fetch_one(&database.pool)
+            .await
+            .unwrap();
+        assert_eq!(stored, r#"{"body": "Nothing personal"}"#);
+
+        let (hit_id,) = sqlx::query_as::<_, (String,)>(
+            "SELECT HitID FROM RedactionHit WHERE TargetID = 'gate' AND ProposedHash IS NOT NULL AND MatchedText = 'press@example.com'"
+        )
+        .fetch_one(&database.pool)
+        .await
+        .unwrap();
+        let report = resolve_hit(&database, &hit_id, HitResolution::Ignore).await.unwrap();
+        assert_eq!(report.pending_hits.len(), 1);
+        assert_eq!(report.pending_hits[0].status, "ignored");
+
+        // Saving the same edit again goes through, and its hits replace the pending ones
+        edit(&database, "gate", proposed).await.unwrap();
+        let hits = sqlx::query_as::<_, (String, Option<String>)>(
+            "SELECT Status, ProposedHash FROM RedactionHit WHERE TargetID = 'gate'"
+        )
+        .fetch_all(&database.pool)
+        .await
+        .unwrap();
+        assert_eq!(hits, vec![("ignored".to_string(), None)]);
+    }
+
+    #[tokio::test]
+    async fn a_different_edit_is_still_checked() {
+        let database = Database::new_in_memory().await.unwrap();
+        database.migrate().await.unwrap();
+        sqlx::query(
+            "INSERT INTO GeneratedContent (content_id, template_id, title, content_data, status) VALUES ('gate2', 'default-thread', 'Launch', '{\"body\": \"Nothing personal\"}', 'Ready')"
+        )
+        .execute(&database.pool)
+        .await
+        .unwrap();
+
+        assert!(edit(&database, "gate2", r#"{"body": "Call 212-555-0188"}"#).await.is_err());
+        let error = edit(&database, "gate2", r#"{"body": "Write to press@example.com"}"#).await.unwrap_err();
+        assert!(error.contains("(email)"), "{}", error);
+        let (pending,) = sqlx::query_as::<_, (i64,)>("SELECT COUNT(*) FROM RedactionHit WHERE TargetID = 'gate2' AND ProposedHash IS NOT NULL")
+            .fetch_one(&database.pool)
+            .await
+            .unwrap();
+        assert_eq!(pending, 1);
+    }
+}
@@ -0,0 +1,179 @@
+// Exporting generated content to Markdown, HTML, text or JSON files, with an export history
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tauri::State;
+use uuid::Uuid;
+use crate::commands::redaction::{scan_target, RedactionTarget};
+use crate::db::Database;
+use crate::services::content_render::{parse_content, render, ContentDocument, ContentExportFormat, ContentKind};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ContentExportRequest {
+    pub content_id: String,
+    pub format: ContentExportFormat,
+    pub output_path: String, // The format's extension is added when the path has none
+    pub platform: Option<String>, // Defaults to where the template type publishes, e.g. "twitter" for threads
+    pub redacted: Option<bool>, // Export the redacted copy, rescanning first so it matches the content
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ContentExportResult {
+    pub export_id: String,
+    pub content_id: String,
+    pub output_path: String,
+    pub format: ContentExportFormat,
+    pub platform: String,
+    pub bytes_written: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ContentExportRecord {
+    pub export_id: String,
+    pub content_id: String,
+    pub content_title: Option<String>,
+    pub export_format: String,
+    pub export_path: Option<String>,
+    pub platform: Option<String>,
+    pub exported_at: String,
+}
+
+async fn load_document(database: &Database, content_id: &str, redacted: bool) -> Result<ContentDocument, String> {
+    let mut tx = database.pool.begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    let (title, content_data, template_type) = sqlx::query_as::<_, (Option<String>, String, String)>(
+        r#"
+        SELECT gc.title, gc.content_data, t.template_type
+        FROM GeneratedContent gc
+        JOIN Template t ON gc.template_id = t.template_id
+        WHERE gc.content_id = ?1
+        "#
+    )
+    .bind(content_id)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|e| format!("Failed to fetch content: {}", e))?
+    .ok_or_else(|| "Content not found".to_string())?;
+
+    let (title, content_data) = if redacted {
+        let report = scan_target(&mut tx, RedactionTarget::Content, content_id).await?;
+        tx.commit()
+            .await
+            .map_err(|e| format!("Failed to commit transaction: {}", e))?;
+        (report.redacted_title.or(title), report.redacted_copy.unwrap_or(content_data))
+    } else {
+        (title, content_data)
+    };
+
+    let content = serde_json::from_str(&content_data).unwrap_or(Value::String(content_data));
+    let kind = ContentKind::from_template_type(&template_type);
+    Ok(parse_content(kind, &content, title.as_deref()))
+}
+
+// The rendered export without writing it, for showing before saving
+#[tauri::command]
+pub async fn preview_content_export(
+    database: State<'_, Arc<Database>>,
+    content_id: String,
+    format: ContentExportFormat,
+    redacted: Option<bool>,
+) -> Result<String, String> {
+    let document = load_document(&database, &content_id, redacted.unwrap_or(false)).await?;
+    render(&document, format)
+}
+
+#[tauri::command]
+pub async fn export_content(
+    database: State<'_, Arc<Database>>,
+    request: ContentExportRequest,
+) -> Result<ContentExportResult, String> {
+    let document = load_document(&database, &request.content_id, request.redacted.unwrap_or(false)).await?;
+    let output = render(&document, request.format)?;
+
+    // A path without an extension gets the format's, e.g. "thread" becomes "thread.md"
+    let mut output_path = PathBuf::from(&request.output_path);
+    if output_path.extension().is_none() {
+        output_path.set_extension(request.format.extension());
+    }
+    let output_path = output_path.to_string_lossy().to_string();
+
+    if let Some(parent) = Path::new(&output_path).parent() {
+        if !parent.as_os_str().is_empty() && !parent.exists() {
+            return Err(format!("Export folder does not exist: {}", parent.display()));
+        }
+    }
+    fs::write(&output_path, &output)
+        .map_err(|e| format!("Failed to write export: {}", e))?;
+
+    let platform = request
+        .platform
+        .as_deref()
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .unwrap_or(document.kind.default_platform())
+        .to_lowercase();
+    let export_id = Uuid::new_v4().to_string();
+
+    sqlx::query(
+        r#"
+        INSERT INTO ExportHistory (export_id, content_id, export_format, export_path, platform)
+        VALUES (?1, ?2, ?3, ?4, ?5)
+        "#
+    )
+    .bind(&export_id)
+    .bind(&request.content_id)
+    .bind(request.format.as_str())
+    .bind(&output_path)
+    .bind(&platform)
+    .execute(&database.pool)
+    .await
+    .map_err(|e| format!("Failed to record export: {}", e))?;
+
+    Ok(ContentExportResult {
+        export_id,
+        content_id: request.content_id,
+        output_path,
+        format: request.format,
+        platform,
+        bytes_written: output.len(),
+    })
+}
+
+// Exports of one piece of content, or of all content when no ID is given, newest first
+#[tauri::command]
+pub async fn get_export_history(
+    database: State<'_, Arc<Database>>,
+    content_id: Option<String>,
+) -> Result<Vec<ContentExportRecord>, String> {
+    let rows = sqlx::query_as::<_, (String, String, Option<String>, String, Option<String>, Option<String>, String)>(
+        r#"
+        SELECT eh.export_id, eh.content_id, gc.title, eh.export_format, eh.export_path, eh.platform,
+               CAST(eh.exported_at AS TEXT)
+        FROM ExportHistory eh
+        JOIN GeneratedContent gc ON gc.content_id = eh.content_id
+        WHERE ?1 IS NULL OR eh.content_id = ?1
+        ORDER BY eh.exported_at DESC, eh.rowid DESC
+        "#
+    )
+    .bind(&content_id)
+    .fetch_all(&database.pool)
+    .await
+    .map_err(|e| format!("Failed to fetch export history: {}", e))?;
+
+    Ok(rows
+        .into_iter()
+        .map(|(export_id, content_id, content_title, export_format, export_path, platform, exported_at)| ContentExportRecord {
+            export_id,
+            content_id,
+            content_title,
+            export_format,
+            export_path,
+            platform,
+            exported_at,
+        })
+        .collect())
+}
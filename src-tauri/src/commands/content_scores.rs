@@ -0,0 +1,116 @@
+// Content scores computed from transcript readability and delivery metrics
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tauri::State;
+use crate::commands::transcripts::load_transcript_segments;
+use crate::db::Database;
+use crate::services::content_metrics::{compute_metrics, score, ContentScoreBreakdown, SCORE_VERSION};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ContentScoreResult {
+    pub transcript_id: String,
+    pub breakdown: Option<ContentScoreBreakdown>, // None when the transcript is too short to score
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ScoreBackfillReport {
+    pub scored: Vec<String>,
+    pub skipped: Vec<String>, // Too short to score
+    pub failed: Vec<(String, String)>, // Transcript ID and error
+}
+
+// Compute and store a transcript's score; a transcript too short to score keeps its current one
+async fn score_and_store(pool: &sqlx::SqlitePool, transcript_id: &str) -> Result<ContentScoreResult, String> {
+    let (language,) = sqlx::query_as::<_, (String,)>("SELECT Language FROM Transcript WHERE TranscriptID = ?1")
+        .bind(transcript_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| format!("Failed to fetch transcript: {}", e))?
+        .ok_or_else(|| "Transcript not found".to_string())?;
+
+    let segments = load_transcript_segments(pool, transcript_id)
+        .await
+        .map_err(|e| format!("Failed to fetch transcript segments: {}", e))?;
+
+    let breakdown = score(compute_metrics(&segments, &language));
+
+    if let Some(breakdown) = &breakdown {
+        let breakdown_json = serde_json::to_string(breakdown)
+            .map_err(|e| format!("Failed to serialize score breakdown: {}", e))?;
+
+        sqlx::query(
+            "UPDATE Transcript SET ContentScore = ?1, ScoreBreakdown = ?2, ScoreSource = 'metrics', AnalyzedAt = datetime('now') WHERE TranscriptID = ?3"
+        )
+        .bind(breakdown.score)
+        .bind(breakdown_json)
+        .bind(transcript_id)
+        .execute(pool)
+        .await
+        .map_err(|e| format!("Failed to store content score: {}", e))?;
+    }
+
+    Ok(ContentScoreResult {
+        transcript_id: transcript_id.to_string(),
+        breakdown,
+    })
+}
+
+// Recompute a transcript's content score from its current text, replacing any externally set score
+#[tauri::command]
+pub async fn score_transcript(
+    database: State<'_, Arc<Database>>,
+    transcript_id: String,
+) -> Result<ContentScoreResult, String> {
+    score_and_store(&database.pool, &transcript_id).await
+}
+
+// Score transcripts that have no score yet or were scored by an older version of the formula.
+// Scores set by the frontend are left alone.
+#[tauri::command]
+pub async fn score_unscored_transcripts(
+    database: State<'_, Arc<Database>>,
+) -> Result<ScoreBackfillReport, String> {
+    let transcript_ids = sqlx::query_as::<_, (String,)>(
+        r#"
+        SELECT TranscriptID FROM Transcript
+        WHERE (ContentScore IS NULL AND ScoreSource IS NULL)
+           OR (ScoreSource = 'metrics' AND COALESCE(json_extract(ScoreBreakdown, '$.version'), 0) < ?1)
+        "#
+    )
+    .bind(SCORE_VERSION as i64)
+    .fetch_all(&database.pool)
+    .await
+    .map_err(|e| format!("Failed to fetch transcripts: {}", e))?;
+
+    let mut report = ScoreBackfillReport::default();
+
+    for (transcript_id,) in transcript_ids {
+        match score_and_store(&database.pool, &transcript_id).await {
+            Ok(result) if result.breakdown.is_some() => report.scored.push(transcript_id),
+            Ok(_) => report.skipped.push(transcript_id),
+            Err(e) => report.failed.push((transcript_id, e)),
+        }
+    }
+
+    Ok(report)
+}
+
+// The stored breakdown behind a transcript's score; None if the score wasn't computed from metrics
+#[tauri::command]
+pub async fn get_content_score_breakdown(
+    database: State<'_, Arc<Database>>,
+    transcript_id: String,
+) -> Result<Option<ContentScoreBreakdown>, String> {
+    let (breakdown,) = sqlx::query_as::<_, (Option<String>,)>(
+        "SELECT ScoreBreakdown FROM Transcript WHERE TranscriptID = ?1"
+    )
+    .bind(&transcript_id)
+    .fetch_optional(&database.pool)
+    .await
+    .map_err(|e| format!("Failed to fetch transcript: {}", e))?
+    .ok_or_else(|| "Transcript not found".to_string())?;
+
+    breakdown
+        .map(|json| serde_json::from_str(&json).map_err(|e| format!("Failed to parse score breakdown: {}", e)))
+        .transpose()
+}
@@ -0,0 +1,333 @@
+// Version history for generated content: a snapshot on every update, diffs and restores
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::{Sqlite, Transaction};
+use std::sync::Arc;
+use tauri::State;
+use uuid::Uuid;
+use crate::commands::content::commit_if_publishable;
+use crate::db::Database;
+use crate::services::json_diff::{diff_json, JsonChange};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ContentVersionInfo {
+    pub version_id: String,
+    pub version_number: i64,
+    pub change_summary: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ContentVersionDetail {
+    pub version: ContentVersionInfo,
+    pub title: Option<String>,
+    pub content: Value,
+    pub status: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ContentVersionDiff {
+    pub content_id: String,
+    pub from_version: i64,
+    pub to_version: i64,
+    pub changes: Vec<JsonChange>, // Paths under /title, /status and /content
+}
+
+// What a version records; stored as version_data
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct ContentSnapshot {
+    title: Option<String>,
+    status: String,
+    content: Value,
+}
+
+impl ContentSnapshot {
+    fn to_value(&self) -> Value {
+        serde_json::to_value(self).unwrap_or(Value::Null)
+    }
+}
+
+async fn load_snapshot(tx: &mut Transaction<'_, Sqlite>, content_id: &str) -> Result<ContentSnapshot, String> {
+    let (title, content_data, status) = sqlx::query_as::<_, (Option<String>, String, String)>(
+        "SELECT title, content_data, status FROM GeneratedContent WHERE content_id = ?1"
+    )
+    .bind(content_id)
+    .fetch_optional(&mut **tx)
+    .await
+    .map_err(|e| format!("Failed to fetch content: {}", e))?
+    .ok_or_else(|| "Content not found".to_string())?;
+
+    let content = serde_json::from_str(&content_data).unwrap_or(Value::String(content_data));
+    Ok(ContentSnapshot { title, status, content })
+}
+
+async fn version_snapshot(
+    tx: &mut Transaction<'_, Sqlite>,
+    content_id: &str,
+    version_number: Option<i64>, // The newest version when None
+) -> Result<Option<ContentSnapshot>, String> {
+    let row = sqlx::query_as::<_, (String,)>(
+        r#"
+        SELECT version_data FROM ContentVersion
+        WHERE content_id = ?1 AND (?2 IS NULL OR version_number = ?2)
+        ORDER BY version_number DESC
+        LIMIT 1
+        "#
+    )
+    .bind(content_id)
+    .bind(version_number)
+    .fetch_optional(&mut **tx)
+    .await
+    .map_err(|e| format!("Failed to fetch content version: {}", e))?;
+
+    row.map(|(data,)| {
+        serde_json::from_str(&data).map_err(|e| format!("Failed to read content version: {}", e))
+    })
+    .transpose()
+}
+
+async fn insert_version(
+    tx: &mut Transaction<'_, Sqlite>,
+    content_id: &str,
+    snapshot: &ContentSnapshot,
+    change_summary: Option<&str>,
+) -> Result<ContentVersionInfo, String> {
+    let version_data = serde_json::to_string(snapshot)
+        .map_err(|e| format!("Failed to serialize content version: {}", e))?;
+    let version_id = Uuid::new_v4().to_string();
+
+    sqlx::query(
+        r#"
+        INSERT INTO ContentVersion (version_id, content_id, version_number, version_data, change_summary)
+        SELECT ?1, ?2, COALESCE(MAX(version_number), 0) + 1, ?3, ?4
+        FROM ContentVersion WHERE content_id = ?2
+        "#
+    )
+    .bind(&version_id)
+    .bind(content_id)
+    .bind(&version_data)
+    .bind(change_summary)
+    .execute(&mut **tx)
+    .await
+    .map_err(|e| format!("Failed to save content version: {}", e))?;
+
+    let (version_number, created_at) = sqlx::query_as::<_, (i64, String)>(
+        "SELECT version_number, CAST(created_at AS TEXT) FROM ContentVersion WHERE version_id = ?1"
+    )
+    .bind(&version_id)
+    .fetch_one(&mut **tx)
+    .await
+    .map_err(|e| format!("Failed to fetch content version: {}", e))?;
+
+    Ok(ContentVersionInfo {
+        version_id,
+        version_number,
+        change_summary: change_summary.map(str::to_string),
+        created_at,
+    })
+}
+
+// A one-line description of a diff between two snapshots, e.g. "Title changed; Content: 2 changed, 1 added"
+fn summarize_changes(old: &ContentSnapshot, new: &ContentSnapshot, changes: &[JsonChange]) -> String {
+    let mut parts = Vec::new();
+    if old.title != new.title {
+        parts.push("Title changed".to_string());
+    }
+    if old.status != new.status {
+        parts.push(format!("Status {} → {}", old.status, new.status));
+    }
+
+    let content_changes: Vec<&JsonChange> = changes
+        .iter()
+        .filter(|c| c.path == "/content" || c.path.starts_with("/content/"))
+        .collect();
+    let counts: Vec<String> = ["changed", "added", "removed"]
+        .iter()
+        .filter_map(|kind| {
+            let count = content_changes.iter().filter(|c| c.kind == *kind).count();
+            (count > 0).then(|| format!("{} {}", count, kind))
+        })
+        .collect();
+    if !counts.is_empty() {
+        parts.push(format!("Content: {}", counts.join(", ")));
+    }
+
+    parts.join("; ")
+}
+
+/// Record the content as it was generated as version 1, so the first update can be diffed and undone.
+/// Call before changing the content.
+pub(crate) async fn ensure_baseline_version(tx: &mut Transaction<'_, Sqlite>, content_id: &str) -> Result<(), String> {
+    let current = load_snapshot(tx, content_id).await?;
+    if version_snapshot(tx, content_id, None).await?.is_none() {
+        insert_version(tx, content_id, &current, Some("Generated version")).await?;
+    }
+    Ok(())
+}
+
+/// Snapshot the content after a change as the newest version. Nothing is recorded when it matches
+/// the newest version; without a summary one is written from the diff.
+pub(crate) async fn record_version(
+    tx: &mut Transaction<'_, Sqlite>,
+    content_id: &str,
+    change_summary: Option<&str>,
+) -> Result<Option<ContentVersionInfo>, String> {
+    let current = load_snapshot(tx, content_id).await?;
+    let head = version_snapshot(tx, content_id, None).await?;
+    if head.as_ref() == Some(&current) {
+        return Ok(None);
+    }
+
+    let generated = head.as_ref().map(|head| {
+        let changes = diff_json(&head.to_value(), &current.to_value());
+        summarize_changes(head, &current, &changes)
+    });
+    let summary = change_summary
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .or(generated);
+
+    insert_version(tx, content_id, &current, summary.as_deref()).await.map(Some)
+}
+
+#[tauri::command]
+pub async fn get_content_versions(
+    database: State<'_, Arc<Database>>,
+    content_id: String,
+) -> Result<Vec<ContentVersionInfo>, String> {
+    let rows = sqlx::query_as::<_, (String, i64, Option<String>, String)>(
+        r#"
+        SELECT version_id, version_number, change_summary, CAST(created_at AS TEXT)
+        FROM ContentVersion
+        WHERE content_id = ?1
+        ORDER BY version_number DESC
+        "#
+    )
+    .bind(&content_id)
+    .fetch_all(&database.pool)
+    .await
+    .map_err(|e| format!("Failed to fetch content versions: {}", e))?;
+
+    Ok(rows
+        .into_iter()
+        .map(|(version_id, version_number, change_summary, created_at)| ContentVersionInfo {
+            version_id,
+            version_number,
+            change_summary,
+            created_at,
+        })
+        .collect())
+}
+
+#[tauri::command]
+pub async fn get_content_version(
+    database: State<'_, Arc<Database>>,
+    content_id: String,
+    version_number: i64,
+) -> Result<ContentVersionDetail, String> {
+    let (version_id, change_summary, created_at, version_data) = sqlx::query_as::<_, (String, Option<String>, String, String)>(
+        r#"
+        SELECT version_id, change_summary, CAST(created_at AS TEXT), version_data
+        FROM ContentVersion
+        WHERE content_id = ?1 AND version_number = ?2
+        "#
+    )
+    .bind(&content_id)
+    .bind(version_number)
+    .fetch_optional(&database.pool)
+    .await
+    .map_err(|e| format!("Failed to fetch content version: {}", e))?
+    .ok_or_else(|| format!("Version {} not found", version_number))?;
+
+    let snapshot: ContentSnapshot = serde_json::from_str(&version_data)
+        .map_err(|e| format!("Failed to read content version: {}", e))?;
+
+    Ok(ContentVersionDetail {
+        version: ContentVersionInfo {
+            version_id,
+            version_number,
+            change_summary,
+            created_at,
+        },
+        title: snapshot.title,
+        content: snapshot.content,
+        status: snapshot.status,
+    })
+}
+
+#[tauri::command]
+pub async fn diff_content_versions(
+    database: State<'_, Arc<Database>>,
+    content_id: String,
+    from_version: i64,
+    to_version: i64,
+) -> Result<ContentVersionDiff, String> {
+    let mut tx = database.pool.begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    let old = version_snapshot(&mut tx, &content_id, Some(from_version))
+        .await?
+        .ok_or_else(|| format!("Version {} not found", from_version))?;
+    let new = version_snapshot(&mut tx, &content_id, Some(to_version))
+        .await?
+        .ok_or_else(|| format!("Version {} not found", to_version))?;
+
+    Ok(ContentVersionDiff {
+        content_id,
+        from_version,
+        to_version,
+        changes: diff_json(&old.to_value(), &new.to_value()),
+    })
+}
+
+// Restoring never rewrites history: the old title and content are saved again as the newest
+// version. The status is left as it is, so restoring can't publish or unpublish anything.
+#[tauri::command]
+pub async fn restore_content_version(
+    database: State<'_, Arc<Database>>,
+    content_id: String,
+    version_number: i64,
+) -> Result<ContentVersionInfo, String> {
+    let mut tx = database.pool.begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    ensure_baseline_version(&mut tx, &content_id).await?;
+    let snapshot = version_snapshot(&mut tx, &content_id, Some(version_number))
+        .await?
+        .ok_or_else(|| format!("Version {} not found", version_number))?;
+
+    let content_data = serde_json::to_string(&snapshot.content)
+        .map_err(|e| format!("Failed to serialize content: {}", e))?;
+    sqlx::query("UPDATE GeneratedContent SET title = ?1, content_data = ?2 WHERE content_id = ?3")
+        .bind(&snapshot.title)
+        .bind(&content_data)
+        .bind(&content_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to restore content: {}", e))?;
+
+    let summary = format!("Restored version {}", version_number);
+    let version = record_version(&mut tx, &content_id, Some(&summary))
+        .await?
+        .ok_or_else(|| format!("The content already matches version {}", version_number))?;
+
+    // A restore is an edit, so content that is Ready or Published goes through the redaction gate
+    let (status,) = sqlx::query_as::<_, (String,)>("SELECT status FROM GeneratedContent WHERE content_id = ?1")
+        .bind(&content_id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to fetch content: {}", e))?;
+    if matches!(status.as_str(), "Ready" | "Published") {
+        let action = format!("restoring version {} of content that is {}", version_number, status);
+        commit_if_publishable(&database, tx, &content_id, &action).await?;
+    } else {
+        tx.commit()
+            .await
+            .map_err(|e| format!("Failed to commit transaction: {}", e))?;
+    }
+
+    Ok(version)
+}
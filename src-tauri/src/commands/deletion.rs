@@ -0,0 +1,367 @@
+// Deleting files and transcripts together with everything derived from them
+use serde::{Deserialize, Serialize};
+use sqlx::{Sqlite, Transaction};
+use std::fs;
+use std::sync::Arc;
+use tauri::{AppHandle, Manager, State};
+use crate::commands::transcript_links::is_placeholder_path;
+use crate::db::Database;
+
+// App data folders holding derived artifacts, named after the FileID they were generated from
+const DERIVED_ARTIFACT_DIRS: &[&str] = &["thumbnails", "proxies"];
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct DeletionReport {
+    pub files_removed: Vec<String>,
+    pub transcripts_removed: Vec<String>,
+    pub segments_removed: u64,
+    pub speakers_removed: u64,
+    pub revisions_removed: u64,
+    pub embeddings_removed: u64,
+    pub disfluency_stats_removed: u64,
+    pub chapters_removed: u64,
+    pub recommendations_removed: u64,
+    pub redaction_hits_removed: u64,
+    pub tag_links_removed: u64,
+    pub tags_removed: Vec<String>, // Tags no longer attached to anything
+    pub content_sources_removed: u64,
+    pub detached_content_ids: Vec<String>, // Generated content left without any source transcript
+    pub project_links_removed: u64,
+    pub artifacts_removed: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+pub(crate) async fn table_exists(tx: &mut Transaction<'_, Sqlite>, name: &str) -> Result<bool, sqlx::Error> {
+    let (exists,) = sqlx::query_as::<_, (bool,)>(
+        "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?1)"
+    )
+    .bind(name)
+    .fetch_one(&mut **tx)
+    .await?;
+
+    Ok(exists)
+}
+
+// Delete a transcript and its dependent rows. Rows are removed explicitly rather than left to
+// ON DELETE CASCADE so the report can count them; the FTS delete trigger clears the search index.
+async fn delete_transcript_rows(
+    tx: &mut Transaction<'_, Sqlite>,
+    transcript_id: &str,
+    report: &mut DeletionReport,
+) -> Result<(), sqlx::Error> {
+    let tag_ids: Vec<(String,)> = sqlx::query_as("SELECT TagID FROM TranscriptTags WHERE TranscriptID = ?1")
+        .bind(transcript_id)
+        .fetch_all(&mut **tx)
+        .await?;
+
+    let content_ids: Vec<(String,)> = sqlx::query_as("SELECT content_id FROM ContentSources WHERE transcript_id = ?1")
+        .bind(transcript_id)
+        .fetch_all(&mut **tx)
+        .await?;
+
+    report.tag_links_removed += sqlx::query("DELETE FROM TranscriptTags WHERE TranscriptID = ?1")
+        .bind(transcript_id)
+        .execute(&mut **tx)
+        .await?
+        .rows_affected();
+
+    report.content_sources_removed += sqlx::query("DELETE FROM ContentSources WHERE transcript_id = ?1")
+        .bind(transcript_id)
+        .execute(&mut **tx)
+        .await?
+        .rows_affected();
+
+    report.redaction_hits_removed += sqlx::query("DELETE FROM RedactionHit WHERE TargetType = 'transcript' AND TargetID = ?1")
+        .bind(transcript_id)
+        .execute(&mut **tx)
+        .await?
+        .rows_affected();
+
+    report.segments_removed += sqlx::query("DELETE FROM TranscriptSegment WHERE TranscriptID = ?1")
+        .bind(transcript_id)
+        .execute(&mut **tx)
+        .await?
+        .rows_affected();
+
+    report.disfluency_stats_removed += sqlx::query("DELETE FROM SpeakerDisfluency WHERE TranscriptID = ?1")
+        .bind(transcript_id)
+        .execute(&mut **tx)
+        .await?
+        .rows_affected();
+
+    report.speakers_removed += sqlx::query("DELETE FROM Speaker WHERE TranscriptID = ?1")
+        .bind(transcript_id)
+        .execute(&mut **tx)
+        .await?
+        .rows_affected();
+
+    report.revisions_removed += sqlx::query("DELETE FROM TranscriptRevision WHERE TranscriptID = ?1")
+        .bind(transcript_id)
+        .execute(&mut **tx)
+        .await?
+        .rows_affected();
+
+    report.embeddings_removed += sqlx::query("DELETE FROM TranscriptChunk WHERE TranscriptID = ?1")
+        .bind(transcript_id)
+        .execute(&mut **tx)
+        .await?
+        .rows_affected();
+
+    report.chapters_removed += sqlx::query("DELETE FROM TranscriptChapter WHERE TranscriptID = ?1")
+        .bind(transcript_id)
+        .execute(&mut **tx)
+        .await?
+        .rows_affected();
+
+    report.recommendations_removed += sqlx::query(
+        "DELETE FROM RelatedTranscript WHERE TranscriptID = ?1 OR RelatedTranscriptID = ?1"
+    )
+    .bind(transcript_id)
+    .execute(&mut **tx)
+    .await?
+    .rows_affected();
+
+    sqlx::query("DELETE FROM RelatedTranscriptState WHERE TranscriptID = ?1")
+        .bind(transcript_id)
+        .execute(&mut **tx)
+        .await?;
+
+    sqlx::query("DELETE FROM Transcript WHERE TranscriptID = ?1")
+        .bind(transcript_id)
+        .execute(&mut **tx)
+        .await?;
+    report.transcripts_removed.push(transcript_id.to_string());
+
+    // Drop tags that were only used by this transcript. Tags with child tags or aliases are part
+    // of the user's taxonomy and stay.
+    let mut in_use_sql = String::from(
+        "SELECT EXISTS(SELECT 1 FROM TranscriptTags WHERE TagID = ?1) OR EXISTS(SELECT 1 FROM Tag WHERE ParentTagID = ?1) OR EXISTS(SELECT 1 FROM TagAlias WHERE TagID = ?1)"
+    );
+    if table_exists(tx, "FileTag").await? {
+        in_use_sql.push_str(" OR EXISTS(SELECT 1 FROM FileTag WHERE TagID = ?1)");
+    }
+    for (tag_id,) in tag_ids {
+        let (in_use,) = sqlx::query_as::<_, (bool,)>(&in_use_sql)
+            .bind(&tag_id)
+            .fetch_one(&mut **tx)
+            .await?;
+
+        if !in_use {
+            if let Some((tag_name,)) = sqlx::query_as::<_, (String,)>("DELETE FROM Tag WHERE TagID = ?1 RETURNING TagName")
+                .bind(&tag_id)
+                .fetch_optional(&mut **tx)
+                .await?
+            {
+                report.tags_removed.push(tag_name);
+            }
+        }
+    }
+
+    // Generated content is the user's work, so it is kept and reported instead of deleted
+    for (content_id,) in content_ids {
+        let (has_sources,) = sqlx::query_as::<_, (bool,)>(
+            "SELECT EXISTS(SELECT 1 FROM ContentSources WHERE content_id = ?1)"
+        )
+        .bind(&content_id)
+        .fetch_one(&mut **tx)
+        .await?;
+
+        if !has_sources && !report.detached_content_ids.contains(&content_id) {
+            report.detached_content_ids.push(content_id);
+        }
+    }
+
+    Ok(())
+}
+
+async fn delete_file_rows(
+    tx: &mut Transaction<'_, Sqlite>,
+    file_id: &str,
+    report: &mut DeletionReport,
+) -> Result<(), sqlx::Error> {
+    let transcript_ids: Vec<(String,)> = sqlx::query_as("SELECT TranscriptID FROM Transcript WHERE FileID = ?1")
+        .bind(file_id)
+        .fetch_all(&mut **tx)
+        .await?;
+
+    for (transcript_id,) in transcript_ids {
+        delete_transcript_rows(tx, &transcript_id, report).await?;
+    }
+
+    if table_exists(tx, "FileTag").await? {
+        report.tag_links_removed += sqlx::query("DELETE FROM FileTag WHERE FileID = ?1")
+            .bind(file_id)
+            .execute(&mut **tx)
+            .await?
+            .rows_affected();
+    }
+
+    sqlx::query(
+        "UPDATE DescriptProject SET FileCount = MAX(FileCount - 1, 0) WHERE ProjectID IN (SELECT ProjectID FROM ProjectFiles WHERE FileID = ?1)"
+    )
+    .bind(file_id)
+    .execute(&mut **tx)
+    .await?;
+
+    report.project_links_removed += sqlx::query("DELETE FROM ProjectFiles WHERE FileID = ?1")
+        .bind(file_id)
+        .execute(&mut **tx)
+        .await?
+        .rows_affected();
+
+    sqlx::query("DELETE FROM File WHERE FileID = ?1")
+        .bind(file_id)
+        .execute(&mut **tx)
+        .await?;
+    report.files_removed.push(file_id.to_string());
+
+    Ok(())
+}
+
+// Remove thumbnails, proxies and other files generated for a FileID. This happens after the
+// database commit, so failures are reported as warnings rather than undoing the delete.
+fn remove_derived_artifacts(app: &AppHandle, file_id: &str, report: &mut DeletionReport) {
+    let app_data_dir = match app.path().app_data_dir() {
+        Ok(dir) => dir,
+        Err(e) => {
+            report.warnings.push(format!("Failed to get app data directory: {}", e));
+            return;
+        }
+    };
+
+    for dir in DERIVED_ARTIFACT_DIRS {
+        let Ok(entries) = fs::read_dir(app_data_dir.join(dir)) else { continue };
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let belongs_to_file = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .map_or(false, |stem| stem == file_id || stem.starts_with(&format!("{}_", file_id)));
+            if !belongs_to_file {
+                continue;
+            }
+
+            let result = if path.is_dir() { fs::remove_dir_all(&path) } else { fs::remove_file(&path) };
+            match result {
+                Ok(()) => report.artifacts_removed.push(path.to_string_lossy().to_string()),
+                Err(e) => report.warnings.push(format!("Failed to remove {}: {}", path.display(), e)),
+            }
+        }
+    }
+}
+
+// Removes the File record and everything derived from it. The source video itself is never touched.
+#[tauri::command]
+pub async fn delete_file(
+    app: AppHandle,
+    database: State<'_, Arc<Database>>,
+    file_id: String,
+) -> Result<DeletionReport, String> {
+    let mut report = DeletionReport::default();
+    let mut tx = database.pool.begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    let exists = sqlx::query_as::<_, (String,)>("SELECT FileID FROM File WHERE FileID = ?1")
+        .bind(&file_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to fetch file: {}", e))?
+        .is_some();
+    if !exists {
+        return Err("File not found".to_string());
+    }
+
+    delete_file_rows(&mut tx, &file_id, &mut report)
+        .await
+        .map_err(|e| format!("Failed to delete file: {}", e))?;
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit transaction: {}", e))?;
+
+    remove_derived_artifacts(&app, &file_id, &mut report);
+    Ok(report)
+}
+
+// Removes a transcript and, when it was imported standalone, the placeholder File row created for
+// it. Other tracks of the same file are kept, and the placeholder with them.
+#[tauri::command]
+pub async fn delete_transcript(
+    app: AppHandle,
+    database: State<'_, Arc<Database>>,
+    transcript_id: String,
+) -> Result<DeletionReport, String> {
+    let mut report = DeletionReport::default();
+    let mut tx = database.pool.begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    let (file_id, file_path) = sqlx::query_as::<_, (String, String)>(
+        "SELECT f.FileID, f.FilePath FROM Transcript t JOIN File f ON t.FileID = f.FileID WHERE t.TranscriptID = ?1"
+    )
+    .bind(&transcript_id)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|e| format!("Failed to fetch transcript: {}", e))?
+    .ok_or_else(|| "Transcript not found".to_string())?;
+
+    let (other_tracks,) = sqlx::query_as::<_, (bool,)>(
+        "SELECT EXISTS(SELECT 1 FROM Transcript WHERE FileID = ?1 AND TranscriptID != ?2)"
+    )
+    .bind(&file_id)
+    .bind(&transcript_id)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| format!("Failed to check transcript tracks: {}", e))?;
+
+    let placeholder = is_placeholder_path(&file_path) && !other_tracks;
+    let result = if placeholder {
+        delete_file_rows(&mut tx, &file_id, &mut report).await
+    } else {
+        delete_transcript_rows(&mut tx, &transcript_id, &mut report).await
+    };
+    result.map_err(|e| format!("Failed to delete transcript: {}", e))?;
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit transaction: {}", e))?;
+
+    if placeholder {
+        remove_derived_artifacts(&app, &file_id, &mut report);
+    }
+    Ok(report)
+}
+
+// Placeholder File rows whose transcript is gone; left behind by deletes made before this command existed
+#[tauri::command]
+pub async fn delete_orphaned_placeholder_files(
+    database: State<'_, Arc<Database>>,
+) -> Result<DeletionReport, String> {
+    let mut report = DeletionReport::default();
+    let mut tx = database.pool.begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    let files = sqlx::query_as::<_, (String, String)>(
+        "SELECT f.FileID, f.FilePath FROM File f WHERE NOT EXISTS(SELECT 1 FROM Transcript t WHERE t.FileID = f.FileID)"
+    )
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(|e| format!("Failed to fetch files: {}", e))?;
+
+    for (file_id, file_path) in files {
+        if is_placeholder_path(&file_path) {
+            delete_file_rows(&mut tx, &file_id, &mut report)
+                .await
+                .map_err(|e| format!("Failed to delete file: {}", e))?;
+        }
+    }
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit transaction: {}", e))?;
+
+    Ok(report)
+}
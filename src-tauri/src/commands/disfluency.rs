@@ -0,0 +1,288 @@
+// Filler word and disfluency analysis per transcript, segment and speaker
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tauri::State;
+use uuid::Uuid;
+use crate::db::Database;
+use crate::services::disfluency::{analyze_text, DisfluencyCounts};
+
+// Fillers listed per speaker and for the whole transcript
+const TOP_FILLERS: usize = 5;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DisfluencyStats {
+    pub word_count: usize,
+    pub filler_count: usize,
+    pub repetition_count: usize,
+    pub false_start_count: usize,
+    pub filler_density: f64, // Fillers per 100 words
+    pub top_fillers: Vec<(String, usize)>,
+}
+
+impl From<&DisfluencyCounts> for DisfluencyStats {
+    fn from(counts: &DisfluencyCounts) -> Self {
+        DisfluencyStats {
+            word_count: counts.word_count,
+            filler_count: counts.filler_count,
+            repetition_count: counts.repetition_count,
+            false_start_count: counts.false_start_count,
+            filler_density: counts.filler_density(),
+            top_fillers: counts.top_fillers(TOP_FILLERS),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SpeakerDisfluencyStats {
+    pub speaker_id: Option<String>, // None for speech without a speaker label
+    pub speaker: Option<String>, // Display name
+    pub stats: DisfluencyStats,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SegmentDisfluency {
+    pub segment_index: i64,
+    pub start_time: Option<f64>,
+    pub filler_count: usize,
+    pub repetition_count: usize,
+    pub false_start_count: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DisfluencyReport {
+    pub transcript_id: String,
+    pub totals: DisfluencyStats,
+    pub speakers: Vec<SpeakerDisfluencyStats>,
+    pub segments: Vec<SegmentDisfluency>, // Only segments with at least one disfluency
+    pub cleaned_word_count: usize,
+}
+
+// Analyze a transcript and store the per-segment counts, per-speaker statistics and cleaned text
+async fn analyze_and_store(pool: &sqlx::SqlitePool, transcript_id: &str) -> Result<(DisfluencyReport, String), String> {
+    let (language, content) = sqlx::query_as::<_, (String, String)>(
+        "SELECT Language, Content FROM Transcript WHERE TranscriptID = ?1"
+    )
+    .bind(transcript_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| format!("Failed to fetch transcript: {}", e))?
+    .ok_or_else(|| "Transcript not found".to_string())?;
+
+    let mut rows = sqlx::query_as::<_, (Option<i64>, Option<f64>, Option<String>, Option<String>, String)>(
+        r#"
+        SELECT seg.SegmentIndex, seg.StartTime, seg.SpeakerID, s.DisplayName, seg.Text
+        FROM TranscriptSegment seg
+        LEFT JOIN Speaker s ON seg.SpeakerID = s.SpeakerID
+        WHERE seg.TranscriptID = ?1
+        ORDER BY seg.SegmentIndex
+        "#
+    )
+    .bind(transcript_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Failed to fetch transcript segments: {}", e))?;
+
+    // Transcripts imported before segments were stored are analyzed as one block
+    if rows.is_empty() {
+        rows.push((None, None, None, None, content));
+    }
+
+    let mut totals = DisfluencyCounts::default();
+    let mut speakers: Vec<(Option<String>, Option<String>, DisfluencyCounts)> = Vec::new();
+    let mut segments = Vec::new();
+    let mut segment_updates = Vec::new();
+    let mut cleaned_parts: Vec<String> = Vec::new();
+    let mut current_speaker: Option<String> = None;
+
+    for (segment_index, start_time, speaker_id, speaker_name, text) in rows {
+        let analysis = analyze_text(&text, &language);
+        totals.add(&analysis.counts);
+
+        match speakers.iter_mut().find(|(id, _, _)| *id == speaker_id) {
+            Some((_, _, counts)) => counts.add(&analysis.counts),
+            None => speakers.push((speaker_id.clone(), speaker_name.clone(), analysis.counts.clone())),
+        }
+
+        if let Some(segment_index) = segment_index {
+            let counts = &analysis.counts;
+            if counts.filler_count + counts.repetition_count + counts.false_start_count > 0 {
+                segments.push(SegmentDisfluency {
+                    segment_index,
+                    start_time,
+                    filler_count: counts.filler_count,
+                    repetition_count: counts.repetition_count,
+                    false_start_count: counts.false_start_count,
+                });
+            }
+            segment_updates.push((
+                segment_index,
+                counts.filler_count as i64,
+                (counts.repetition_count + counts.false_start_count) as i64,
+            ));
+        }
+
+        // Same layout as the stored content: "Name:" prefixed whenever the speaker changes
+        if !analysis.cleaned.is_empty() {
+            match speaker_name {
+                Some(name) if Some(&name) != current_speaker.as_ref() => {
+                    cleaned_parts.push(format!("{}: {}", name, analysis.cleaned));
+                    current_speaker = Some(name);
+                }
+                _ => cleaned_parts.push(analysis.cleaned),
+            }
+        }
+    }
+
+    let cleaned = cleaned_parts.join(" ");
+
+    let mut tx = pool.begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    for (segment_index, filler_count, disfluency_count) in segment_updates {
+        sqlx::query(
+            "UPDATE TranscriptSegment SET FillerCount = ?1, DisfluencyCount = ?2 WHERE TranscriptID = ?3 AND SegmentIndex = ?4"
+        )
+        .bind(filler_count)
+        .bind(disfluency_count)
+        .bind(transcript_id)
+        .bind(segment_index)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to update segment: {}", e))?;
+    }
+
+    sqlx::query("UPDATE Transcript SET CleanedContent = ?1, FillerDensity = ?2 WHERE TranscriptID = ?3")
+        .bind(&cleaned)
+        .bind(totals.filler_density())
+        .bind(transcript_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to update transcript: {}", e))?;
+
+    sqlx::query("DELETE FROM SpeakerDisfluency WHERE TranscriptID = ?1")
+        .bind(transcript_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to clear speaker statistics: {}", e))?;
+
+    for (speaker_id, _, counts) in &speakers {
+        let top_fillers = serde_json::to_string(&counts.top_fillers(TOP_FILLERS))
+            .map_err(|e| format!("Failed to serialize fillers: {}", e))?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO SpeakerDisfluency (
+                StatID, TranscriptID, SpeakerID, WordCount, FillerCount, RepetitionCount,
+                FalseStartCount, FillerDensity, TopFillers
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+            "#
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(transcript_id)
+        .bind(speaker_id)
+        .bind(counts.word_count as i64)
+        .bind(counts.filler_count as i64)
+        .bind(counts.repetition_count as i64)
+        .bind(counts.false_start_count as i64)
+        .bind(counts.filler_density())
+        .bind(top_fillers)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to store speaker statistics: {}", e))?;
+    }
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit transaction: {}", e))?;
+
+    let report = DisfluencyReport {
+        transcript_id: transcript_id.to_string(),
+        totals: DisfluencyStats::from(&totals),
+        speakers: speakers
+            .iter()
+            .map(|(speaker_id, speaker, counts)| SpeakerDisfluencyStats {
+                speaker_id: speaker_id.clone(),
+                speaker: speaker.clone(),
+                stats: DisfluencyStats::from(counts),
+            })
+            .collect(),
+        segments,
+        cleaned_word_count: cleaned.split_whitespace().count(),
+    };
+
+    Ok((report, cleaned))
+}
+
+// Count fillers ("um", "you know"), repeated words and false starts, and store a cleaned variant
+#[tauri::command]
+pub async fn analyze_transcript_disfluencies(
+    database: State<'_, Arc<Database>>,
+    transcript_id: String,
+) -> Result<DisfluencyReport, String> {
+    let (report, _) = analyze_and_store(&database.pool, &transcript_id).await?;
+    Ok(report)
+}
+
+// Per-speaker statistics from the last analysis; empty if the transcript hasn't been analyzed
+#[tauri::command]
+pub async fn get_speaker_disfluency_stats(
+    database: State<'_, Arc<Database>>,
+    transcript_id: String,
+) -> Result<Vec<SpeakerDisfluencyStats>, String> {
+    let rows = sqlx::query_as::<_, (Option<String>, Option<String>, i64, i64, i64, i64, f64, Option<String>)>(
+        r#"
+        SELECT d.SpeakerID, s.DisplayName, d.WordCount, d.FillerCount, d.RepetitionCount,
+               d.FalseStartCount, d.FillerDensity, d.TopFillers
+        FROM SpeakerDisfluency d
+        LEFT JOIN Speaker s ON d.SpeakerID = s.SpeakerID
+        WHERE d.TranscriptID = ?1
+        ORDER BY d.FillerDensity DESC
+        "#
+    )
+    .bind(&transcript_id)
+    .fetch_all(&database.pool)
+    .await
+    .map_err(|e| format!("Failed to fetch speaker statistics: {}", e))?;
+
+    Ok(rows
+        .into_iter()
+        .map(|(speaker_id, speaker, word_count, filler_count, repetition_count, false_start_count, filler_density, top_fillers)| {
+            SpeakerDisfluencyStats {
+                speaker_id,
+                speaker,
+                stats: DisfluencyStats {
+                    word_count: word_count as usize,
+                    filler_count: filler_count as usize,
+                    repetition_count: repetition_count as usize,
+                    false_start_count: false_start_count as usize,
+                    filler_density,
+                    top_fillers: top_fillers
+                        .and_then(|json| serde_json::from_str(&json).ok())
+                        .unwrap_or_default(),
+                },
+            }
+        })
+        .collect())
+}
+
+// Transcript text without fillers, repetitions and false starts; analyzes the transcript first if needed
+#[tauri::command]
+pub async fn get_cleaned_transcript(
+    database: State<'_, Arc<Database>>,
+    transcript_id: String,
+) -> Result<String, String> {
+    let (cleaned,) = sqlx::query_as::<_, (Option<String>,)>(
+        "SELECT CleanedContent FROM Transcript WHERE TranscriptID = ?1"
+    )
+    .bind(&transcript_id)
+    .fetch_optional(&database.pool)
+    .await
+    .map_err(|e| format!("Failed to fetch transcript: {}", e))?
+    .ok_or_else(|| "Transcript not found".to_string())?;
+
+    match cleaned {
+        Some(cleaned) => Ok(cleaned),
+        None => Ok(analyze_and_store(&database.pool, &transcript_id).await?.1),
+    }
+}
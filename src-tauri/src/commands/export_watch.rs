@@ -0,0 +1,375 @@
+// Watching the Descript export folder: transcripts saved there are imported, matched to the
+// uploaded files and project they came from, and the project is marked Complete
+use serde::{Deserialize, Serialize};
+use sqlx::{Executor, Sqlite, SqlitePool};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter, State};
+use tokio::sync::Mutex;
+use crate::commands::transcript_links::{is_placeholder_path, normalize_stem};
+use crate::commands::transcript_revisions::{apply_edit, TranscriptSnapshot};
+use crate::commands::transcripts::{import_single_transcript, read_transcript_file, VALID_TRANSCRIPT_EXTENSIONS};
+use crate::commands::upload::export_folder;
+use crate::db::Database;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+// Files changed more recently than this may still be being written
+const SETTLE_TIME: Duration = Duration::from_secs(2);
+// Revision author for transcripts updated from a re-saved export
+const EXPORT_AUTHOR: &str = "descript export";
+
+// Keeps the watcher and a manual scan from importing the same file twice
+static SCAN_LOCK: Mutex<()> = Mutex::const_new(());
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportImportEvent {
+    pub file_path: String,
+    pub filename: String,
+    pub transcript_id: Option<String>,
+    pub file_id: Option<String>, // Media file the transcript was linked to
+    pub project_id: Option<String>,
+    pub project_name: Option<String>,
+    pub project_complete: bool, // Every file in the project now has a transcript
+    pub needs_link: bool, // Imported, but no single media file matched
+    pub skipped: bool, // Not imported: the transcript it was imported as before has been deleted
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExportImportRecord {
+    pub file_path: String,
+    pub transcript_id: Option<String>,
+    pub project_id: Option<String>,
+    pub project_name: Option<String>,
+    pub status: String, // "imported" or "failed"
+    pub error: Option<String>,
+    pub processed_at: String,
+}
+
+/// Poll the export folder in the background for as long as the app runs. A scan that fails, e.g.
+/// because the folder can't be read, is reported once until the error changes or a scan succeeds.
+pub fn start_export_watcher(app: AppHandle, database: Arc<Database>) {
+    tauri::async_runtime::spawn(async move {
+        let mut last_error: Option<String> = None;
+        loop {
+            match scan_folder(&app, &database).await {
+                Ok(_) => last_error = None,
+                Err(error) => {
+                    if last_error.as_ref() != Some(&error) {
+                        let _ = app.emit("transcript-export-scan-failed", &error);
+                        last_error = Some(error);
+                    }
+                }
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    });
+}
+
+fn is_export_file(path: &Path) -> bool {
+    let Some(name) = path.file_name().and_then(|n| n.to_str()) else { return false };
+    // Hidden files and editor or download temporaries
+    if name.starts_with('.') || name.starts_with("~$") {
+        return false;
+    }
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| VALID_TRANSCRIPT_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+}
+
+// Import every new or changed export in the folder, oldest first, emitting an event for each
+async fn scan_folder(app: &AppHandle, database: &Arc<Database>) -> Result<Vec<ExportImportEvent>, String> {
+    let _guard = SCAN_LOCK.lock().await;
+    let folder = export_folder(app)?;
+
+    let entries = std::fs::read_dir(&folder)
+        .map_err(|e| format!("Failed to read export folder: {}", e))?;
+    let settled_before = SystemTime::now() - SETTLE_TIME;
+
+    let mut exports = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(metadata) = entry.metadata() else { continue };
+        if !metadata.is_file() || !is_export_file(&path) {
+            continue;
+        }
+        let Ok(modified) = metadata.modified() else { continue };
+        if modified > settled_before {
+            continue;
+        }
+        let modified_at = modified.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs() as i64);
+        exports.push((modified_at, metadata.len() as i64, path.to_string_lossy().to_string()));
+    }
+    exports.sort();
+
+    let mut events = Vec::new();
+    for (modified_at, file_size, file_path) in exports {
+        let (seen,) = sqlx::query_as::<_, (bool,)>(
+            "SELECT EXISTS(SELECT 1 FROM ExportImport WHERE FilePath = ?1 AND FileSize = ?2 AND ModifiedAt = ?3)"
+        )
+        .bind(&file_path)
+        .bind(file_size)
+        .bind(modified_at)
+        .fetch_one(&database.pool)
+        .await
+        .map_err(|e| format!("Failed to check export: {}", e))?;
+        if seen {
+            continue;
+        }
+
+        // A re-saved export replaces the text of the transcript it was imported as, keeping its
+        // history, tags and speaker names. If that transcript has since been deleted, the user
+        // didn't want it: the file is recorded as seen and skipped instead of imported again.
+        let previous = sqlx::query_as::<_, (String, Option<String>)>(
+            "SELECT e.Status, t.TranscriptID FROM ExportImport e LEFT JOIN Transcript t ON t.TranscriptID = e.TranscriptID WHERE e.FilePath = ?1"
+        )
+        .bind(&file_path)
+        .fetch_optional(&database.pool)
+        .await
+        .map_err(|e| format!("Failed to check export: {}", e))?;
+
+        let event = match previous {
+            Some((_, Some(transcript_id))) => update_export(database, &file_path, &transcript_id).await,
+            Some((status, None)) if status == "imported" => {
+                sqlx::query(
+                    "UPDATE ExportImport SET FileSize = ?2, ModifiedAt = ?3, ProcessedAt = CURRENT_TIMESTAMP WHERE FilePath = ?1"
+                )
+                .bind(&file_path)
+                .bind(file_size)
+                .bind(modified_at)
+                .execute(&database.pool)
+                .await
+                .map_err(|e| format!("Failed to record export: {}", e))?;
+
+                let event = ExportImportEvent { skipped: true, ..new_event(&file_path) };
+                let _ = app.emit("transcript-export-skipped", &event);
+                events.push(event);
+                continue;
+            }
+            _ => import_export(app, database, &file_path).await,
+        };
+        sqlx::query(
+            r#"
+            INSERT OR REPLACE INTO ExportImport (FilePath, FileSize, ModifiedAt, TranscriptID, ProjectID, Status, Error, ProcessedAt)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, CURRENT_TIMESTAMP)
+            "#
+        )
+        .bind(&file_path)
+        .bind(file_size)
+        .bind(modified_at)
+        .bind(&event.transcript_id)
+        .bind(&event.project_id)
+        .bind(if event.error.is_some() { "failed" } else { "imported" })
+        .bind(&event.error)
+        .execute(&database.pool)
+        .await
+        .map_err(|e| format!("Failed to record export: {}", e))?;
+
+        let name = if event.error.is_some() { "transcript-export-failed" } else { "transcript-export-imported" };
+        let _ = app.emit(name, &event);
+        events.push(event);
+    }
+
+    Ok(events)
+}
+
+fn new_event(file_path: &str) -> ExportImportEvent {
+    let filename = Path::new(file_path)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    ExportImportEvent {
+        file_path: file_path.to_string(),
+        filename,
+        transcript_id: None,
+        file_id: None,
+        project_id: None,
+        project_name: None,
+        project_complete: false,
+        needs_link: false,
+        skipped: false,
+        error: None,
+    }
+}
+
+async fn import_export(app: &AppHandle, database: &Arc<Database>, file_path: &str) -> ExportImportEvent {
+    let mut event = new_event(file_path);
+
+    // Matched to a media file by name or by a single-file project named like the export
+    let imported = import_single_transcript(app, database, file_path, None, None)
+        .await
+        .map_err(|e| e.to_string());
+    match imported {
+        Ok(transcript) => event.transcript_id = Some(transcript.id),
+        Err(error) => {
+            event.error = Some(error);
+            return event;
+        }
+    }
+
+    if let Err(error) = match_project(&database.pool, &mut event).await {
+        event.error = Some(error);
+    }
+    event
+}
+
+async fn update_export(database: &Arc<Database>, file_path: &str, transcript_id: &str) -> ExportImportEvent {
+    let mut event = new_event(file_path);
+    event.transcript_id = Some(transcript_id.to_string());
+
+    if let Err(error) = replace_transcript(database, file_path, transcript_id).await {
+        event.error = Some(error);
+        return event;
+    }
+
+    if let Err(error) = match_project(&database.pool, &mut event).await {
+        event.error = Some(error);
+    }
+    event
+}
+
+// Replace the transcript's text and segments with the export's, as a new revision. Nothing is
+// recorded when only the file's timestamp changed.
+async fn replace_transcript(database: &Database, file_path: &str, transcript_id: &str) -> Result<(), String> {
+    let file = read_transcript_file(Path::new(file_path)).map_err(|e| e.to_string())?;
+
+    let mut tx = database.pool.begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    let (current,) = sqlx::query_as::<_, (String,)>("SELECT Content FROM Transcript WHERE TranscriptID = ?1")
+        .bind(transcript_id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to fetch transcript: {}", e))?;
+    if current == file.content {
+        return Ok(());
+    }
+
+    apply_edit(
+        &mut tx,
+        transcript_id,
+        |_| Ok(TranscriptSnapshot { content: file.content, segments: file.parsed.segments }),
+        EXPORT_AUTHOR,
+        Some("Updated from the re-saved Descript export"),
+    )
+    .await?;
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit transaction: {}", e))?;
+
+    Ok(())
+}
+
+/// Mark the projects a file was uploaded in Complete once every file in them has a transcript
+pub(crate) async fn complete_projects<'e, E>(executor: E, file_id: &str) -> Result<(), String>
+where
+    E: Executor<'e, Database = Sqlite>,
+{
+    sqlx::query(
+        r#"
+        UPDATE DescriptProject SET Status = 'Complete'
+        WHERE ProjectID IN (SELECT ProjectID FROM ProjectFiles WHERE FileID = ?1)
+          AND Status != 'Complete'
+          AND NOT EXISTS(
+              SELECT 1 FROM ProjectFiles pf
+              WHERE pf.ProjectID = DescriptProject.ProjectID
+                AND NOT EXISTS(SELECT 1 FROM Transcript t WHERE t.FileID = pf.FileID)
+          )
+        "#
+    )
+    .bind(file_id)
+    .execute(executor)
+    .await
+    .map_err(|e| format!("Failed to update project: {}", e))?;
+
+    Ok(())
+}
+
+// The project an imported export belongs to: the one its media file was uploaded in, otherwise one
+// named like the export, which then still needs the transcript linked to one of its files
+async fn match_project(pool: &SqlitePool, event: &mut ExportImportEvent) -> Result<(), String> {
+    let (file_id, file_path) = sqlx::query_as::<_, (String, String)>(
+        "SELECT t.FileID, f.FilePath FROM Transcript t JOIN File f ON t.FileID = f.FileID WHERE t.TranscriptID = ?1"
+    )
+    .bind(&event.transcript_id)
+    .fetch_one(pool)
+    .await
+    .map_err(|e| format!("Failed to fetch transcript: {}", e))?;
+
+    let project = if is_placeholder_path(&file_path) {
+        event.needs_link = true;
+        let stem = normalize_stem(&event.filename);
+        let projects = sqlx::query_as::<_, (String, String, String)>(
+            "SELECT ProjectID, ProjectName, Status FROM DescriptProject ORDER BY UploadedAt DESC"
+        )
+        .fetch_all(pool)
+        .await
+        .map_err(|e| format!("Failed to fetch projects: {}", e))?;
+        projects.into_iter().find(|(_, name, _)| normalize_stem(name) == stem)
+    } else {
+        event.file_id = Some(file_id.clone());
+        complete_projects(pool, &file_id).await?;
+        sqlx::query_as::<_, (String, String, String)>(
+            r#"
+            SELECT dp.ProjectID, dp.ProjectName, dp.Status
+            FROM ProjectFiles pf JOIN DescriptProject dp ON dp.ProjectID = pf.ProjectID
+            WHERE pf.FileID = ?1
+            ORDER BY dp.UploadedAt DESC
+            LIMIT 1
+            "#
+        )
+        .bind(&file_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| format!("Failed to fetch project: {}", e))?
+    };
+
+    if let Some((project_id, project_name, status)) = project {
+        event.project_id = Some(project_id);
+        event.project_name = Some(project_name);
+        event.project_complete = status == "Complete";
+    }
+    Ok(())
+}
+
+// Check the export folder now rather than waiting for the next poll
+#[tauri::command]
+pub async fn scan_export_folder(
+    app: AppHandle,
+    database: State<'_, Arc<Database>>,
+) -> Result<Vec<ExportImportEvent>, String> {
+    scan_folder(&app, &database).await
+}
+
+#[tauri::command]
+pub async fn get_export_imports(
+    database: State<'_, Arc<Database>>,
+) -> Result<Vec<ExportImportRecord>, String> {
+    let rows = sqlx::query_as::<_, (String, Option<String>, Option<String>, Option<String>, String, Option<String>, String)>(
+        r#"
+        SELECT e.FilePath, e.TranscriptID, e.ProjectID, dp.ProjectName, e.Status, e.Error, CAST(e.ProcessedAt AS TEXT)
+        FROM ExportImport e
+        LEFT JOIN DescriptProject dp ON dp.ProjectID = e.ProjectID
+        ORDER BY e.ProcessedAt DESC
+        "#
+    )
+    .fetch_all(&database.pool)
+    .await
+    .map_err(|e| format!("Failed to fetch export imports: {}", e))?;
+
+    Ok(rows
+        .into_iter()
+        .map(|(file_path, transcript_id, project_id, project_name, status, error, processed_at)| ExportImportRecord {
+            file_path,
+            transcript_id,
+            project_id,
+            project_name,
+            status,
+            error,
+            processed_at,
+        })
+        .collect())
+}
@@ -4,6 +4,7 @@ use tauri::{AppHandle, State};
 use uuid::Uuid;
 use std::fs;
 use std::sync::Arc;
+use crate::commands::tags::resolve_tag_id;
 use crate::db::Database;
 use chrono::{Utc, DateTime, Datelike};
 use std::process::Command;
@@ -48,7 +49,7 @@ pub struct FileImportError {
 }
 
 // Valid video file extensions
-const VALID_VIDEO_EXTENSIONS: &[&str] = &["mp4", "mov", "avi", "mkv", "webm", "flv", "wmv"];
+pub(crate) const VALID_VIDEO_EXTENSIONS: &[&str] = &["mp4", "mov", "avi", "mkv", "webm", "flv", "wmv"];
 
 #[tauri::command]
 pub async fn validate_video_files(file_paths: Vec<String>) -> Result<Vec<String>, String> {
@@ -399,15 +400,10 @@ async fn import_and_organize_single_file(
     
     // Insert tags
     for tag in &tags {
-        // Check if tag exists
-        let existing_tag = sqlx::query_as::<_, (String,)>(
-            "SELECT TagID FROM Tag WHERE TagName = ?"
-        )
-        .bind(tag)
-        .fetch_optional(&database.pool)
-        .await?;
+        // Check if tag exists, under its own name or an alias
+        let existing_tag = resolve_tag_id(&database.pool, tag).await?;
 
-        let tag_id = if let Some((id,)) = existing_tag {
+        let tag_id = if let Some(id) = existing_tag {
             id
         } else {
             // Create new tag
@@ -1,8 +1,28 @@
 pub mod auth;
+pub mod auto_tags;
+pub mod chapters;
+pub mod clips;
 pub mod content;
+pub mod content_export;
+pub mod content_scores;
+pub mod content_versions;
 pub mod database;
+pub mod deletion;
+pub mod disfluency;
+pub mod export_watch;
 pub mod files;
+pub mod redaction;
+pub mod related;
+pub mod semantic_search;
 pub mod settings;
+pub mod speakers;
+pub mod tags;
 pub mod templates;
+pub mod transcript_export;
+pub mod transcript_links;
+pub mod transcript_merge;
+pub mod transcript_revisions;
+pub mod transcript_summaries;
+pub mod transcript_tracks;
 pub mod transcripts;
 pub mod upload;
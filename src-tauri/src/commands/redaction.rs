@@ -0,0 +1,569 @@
+// Redacting personal data, profanity and blocklisted terms in transcripts and generated content
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use sqlx::{Sqlite, Transaction};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tauri::State;
+use uuid::Uuid;
+use crate::db::Database;
+use crate::services::redaction::{apply_redactions, detect, BlocklistTerm, RedactionHit};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum RedactionTarget {
+    Transcript,
+    Content, // GeneratedContent
+}
+
+impl RedactionTarget {
+    fn as_str(&self) -> &'static str {
+        match self {
+            RedactionTarget::Transcript => "transcript",
+            RedactionTarget::Content => "content",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum HitResolution {
+    Redact, // Confirm the replacement
+    Ignore, // Keep the original text in the redacted copy
+    Reopen,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RedactionHitInfo {
+    pub hit_id: String,
+    pub location: Option<String>, // JSON pointer to the string within generated content, or "title"
+    pub category: String, // "email", "phone", "url", "card_number", "profanity" or "blocklist"
+    pub matched_text: String,
+    pub replacement: String,
+    pub start: usize, // UTF-16 offsets into the transcript text or content string
+    pub end: usize,
+    pub status: String, // "open", "redacted" or "ignored"
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RedactionReport {
+    pub target_type: RedactionTarget,
+    pub target_id: String,
+    pub hits: Vec<RedactionHitInfo>,
+    pub open_count: usize,
+    pub pending_hits: Vec<RedactionHitInfo>, // Content only: hits in the last edit the Ready gate rejected
+    pub redacted_copy: Option<String>, // Transcript text, or content JSON; None until scanned
+    pub redacted_title: Option<String>, // Content only
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RedactionTermInfo {
+    pub term_id: String,
+    pub term: String,
+    pub replacement: Option<String>,
+}
+
+// Location of a generated content title's hits; JSON pointers are empty or start with '/'
+const TITLE_LOCATION: &str = "title";
+
+// One scanned string: where it sits in the target, its text and what was found in it
+struct ScannedText {
+    location: Option<String>,
+    text: String,
+    hits: Vec<RedactionHit>,
+}
+
+// Identifies a version of generated content's title and data, so hits found in an edit that was
+// rejected carry over when the same edit is saved again
+fn proposal_hash(title: Option<&str>, content_data: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(title.unwrap_or("").as_bytes());
+    hasher.update([0]);
+    hasher.update(content_data.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+fn utf16_offset(text: &str, byte_offset: usize) -> usize {
+    text[..byte_offset].encode_utf16().count()
+}
+
+fn escape_pointer(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+// Every string in a JSON document with its JSON pointer
+fn collect_strings(value: &Value, pointer: String, strings: &mut Vec<(String, String)>) {
+    match value {
+        Value::String(text) => strings.push((pointer, text.clone())),
+        Value::Array(items) => {
+            for (index, item) in items.iter().enumerate() {
+                collect_strings(item, format!("{}/{}", pointer, index), strings);
+            }
+        }
+        Value::Object(fields) => {
+            for (key, item) in fields {
+                collect_strings(item, format!("{}/{}", pointer, escape_pointer(key)), strings);
+            }
+        }
+        _ => {}
+    }
+}
+
+async fn load_blocklist(tx: &mut Transaction<'_, Sqlite>) -> Result<Vec<BlocklistTerm>, String> {
+    let rows = sqlx::query_as::<_, (String, Option<String>)>("SELECT Term, Replacement FROM RedactionTerm")
+        .fetch_all(&mut **tx)
+        .await
+        .map_err(|e| format!("Failed to fetch blocklist: {}", e))?;
+
+    Ok(rows
+        .into_iter()
+        .map(|(term, replacement)| BlocklistTerm { term, replacement })
+        .collect())
+}
+
+async fn load_report(
+    tx: &mut Transaction<'_, Sqlite>,
+    target: RedactionTarget,
+    target_id: &str,
+) -> Result<RedactionReport, String> {
+    let copy_sql = match target {
+        RedactionTarget::Transcript => "SELECT RedactedContent, NULL FROM Transcript WHERE TranscriptID = ?1",
+        RedactionTarget::Content => "SELECT redacted_data, redacted_title FROM GeneratedContent WHERE content_id = ?1",
+    };
+    let (redacted_copy, redacted_title) = sqlx::query_as::<_, (Option<String>, Option<String>)>(copy_sql)
+        .bind(target_id)
+        .fetch_optional(&mut **tx)
+        .await
+        .map_err(|e| format!("Failed to fetch redacted copy: {}", e))?
+        .ok_or_else(|| match target {
+            RedactionTarget::Transcript => "Transcript not found".to_string(),
+            RedactionTarget::Content => "Content not found".to_string(),
+        })?;
+
+    let rows = sqlx::query_as::<_, (String, Option<String>, String, String, String, i64, i64, String, bool)>(
+        r#"
+        SELECT HitID, Location, Category, MatchedText, Replacement, StartOffset, EndOffset, Status, ProposedHash IS NOT NULL
+        FROM RedactionHit
+        WHERE TargetType = ?1 AND TargetID = ?2
+        ORDER BY Location, StartOffset
+        "#
+    )
+    .bind(target.as_str())
+    .bind(target_id)
+    .fetch_all(&mut **tx)
+    .await
+    .map_err(|e| format!("Failed to fetch redaction hits: {}", e))?;
+
+    let mut hits = Vec::new();
+    let mut pending_hits = Vec::new();
+    for (hit_id, location, category, matched_text, replacement, start, end, status, pending) in rows {
+        let hit = RedactionHitInfo {
+            hit_id,
+            location,
+            category,
+            matched_text,
+            replacement,
+            start: start as usize,
+            end: end as usize,
+            status,
+        };
+        if pending { pending_hits.push(hit) } else { hits.push(hit) }
+    }
+
+    Ok(RedactionReport {
+        target_type: target,
+        target_id: target_id.to_string(),
+        open_count: hits.iter().filter(|h| h.status == "open").count(),
+        hits,
+        pending_hits,
+        redacted_copy,
+        redacted_title,
+    })
+}
+
+/// Scan a transcript's text, or a piece of generated content's title and every string in its data,
+/// replace the stored hits and write a redacted copy. Hits that weren't ignored are replaced in the
+/// copy, open ones included. Decisions on earlier hits carry over to the same text in the same
+/// category, including decisions on the hits of a rejected edit of the content.
+pub(crate) async fn scan_target(
+    tx: &mut Transaction<'_, Sqlite>,
+    target: RedactionTarget,
+    target_id: &str,
+) -> Result<RedactionReport, String> {
+    let blocklist = load_blocklist(tx).await?;
+
+    let (scanned, content_json, proposal) = match target {
+        RedactionTarget::Transcript => {
+            let (content,) = sqlx::query_as::<_, (String,)>("SELECT Content FROM Transcript WHERE TranscriptID = ?1")
+                .bind(target_id)
+                .fetch_optional(&mut **tx)
+                .await
+                .map_err(|e| format!("Failed to fetch transcript: {}", e))?
+                .ok_or_else(|| "Transcript not found".to_string())?;
+            let hits = detect(&content, &blocklist);
+            (vec![ScannedText { location: None, text: content, hits }], None, None)
+        }
+        RedactionTarget::Content => {
+            let (title, content_data) = sqlx::query_as::<_, (Option<String>, String)>(
+                "SELECT title, content_data FROM GeneratedContent WHERE content_id = ?1"
+            )
+            .bind(target_id)
+            .fetch_optional(&mut **tx)
+            .await
+            .map_err(|e| format!("Failed to fetch content: {}", e))?
+            .ok_or_else(|| "Content not found".to_string())?;
+            let proposal = proposal_hash(title.as_deref(), &content_data);
+            let value = serde_json::from_str(&content_data).unwrap_or(Value::String(content_data));
+            // The title is published too, e.g. as the heading of an export
+            let mut strings: Vec<(String, String)> = title.map(|t| (TITLE_LOCATION.to_string(), t)).into_iter().collect();
+            collect_strings(&value, String::new(), &mut strings);
+            let scanned = strings
+                .into_iter()
+                .map(|(pointer, text)| {
+                    let hits = detect(&text, &blocklist);
+                    ScannedText { location: Some(pointer), text, hits }
+                })
+                .collect();
+            (scanned, Some(value), Some(proposal))
+        }
+    };
+
+    // Earlier hits keep their IDs when found again at the same place; decisions carry over to
+    // the same text in the same category
+    let previous = sqlx::query_as::<_, (String, Option<String>, String, String, i64, String, Option<String>, Option<String>)>(
+        "SELECT HitID, Location, Category, MatchedText, StartOffset, Status, CAST(ResolvedAt AS TEXT), ProposedHash FROM RedactionHit WHERE TargetType = ?1 AND TargetID = ?2"
+    )
+    .bind(target.as_str())
+    .bind(target_id)
+    .fetch_all(&mut **tx)
+    .await
+    .map_err(|e| format!("Failed to fetch redaction hits: {}", e))?;
+    let mut hit_ids: HashMap<(Option<String>, String, String, i64), String> = HashMap::new();
+    let mut decisions: HashMap<(String, String), (String, Option<String>)> = HashMap::new();
+    for (hit_id, location, category, text, start, status, resolved_at, proposed) in previous {
+        if status != "open" {
+            decisions.insert((category.clone(), text.to_lowercase()), (status, resolved_at));
+        }
+        // Only the hits replaced below give up their IDs
+        if proposed.is_none() || proposed == proposal {
+            hit_ids.insert((location, category, text, start), hit_id);
+        }
+    }
+
+    // Hits of a rejected edit are kept until that edit is saved, when they are found again here
+    sqlx::query("DELETE FROM RedactionHit WHERE TargetType = ?1 AND TargetID = ?2 AND (ProposedHash IS NULL OR ProposedHash = ?3)")
+        .bind(target.as_str())
+        .bind(target_id)
+        .bind(&proposal)
+        .execute(&mut **tx)
+        .await
+        .map_err(|e| format!("Failed to clear redaction hits: {}", e))?;
+
+    let mut redacted_strings = Vec::new();
+    for scanned_text in &scanned {
+        let mut applied = Vec::new();
+        for hit in &scanned_text.hits {
+            let decision = decisions.get(&(hit.category.as_str().to_string(), hit.text.to_lowercase()));
+            let (status, resolved_at) = decision.map_or(("open", None), |(s, r)| (s.as_str(), r.clone()));
+            let start = utf16_offset(&scanned_text.text, hit.start) as i64;
+            let hit_id = hit_ids
+                .remove(&(scanned_text.location.clone(), hit.category.as_str().to_string(), hit.text.clone(), start))
+                .unwrap_or_else(|| Uuid::new_v4().to_string());
+
+            sqlx::query(
+                r#"
+                INSERT INTO RedactionHit (HitID, TargetType, TargetID, Location, Category, MatchedText, Replacement, StartOffset, EndOffset, Status, ResolvedAt)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
+                "#
+            )
+            .bind(&hit_id)
+            .bind(target.as_str())
+            .bind(target_id)
+            .bind(&scanned_text.location)
+            .bind(hit.category.as_str())
+            .bind(&hit.text)
+            .bind(&hit.replacement)
+            .bind(start)
+            .bind(utf16_offset(&scanned_text.text, hit.end) as i64)
+            .bind(status)
+            .bind(resolved_at)
+            .execute(&mut **tx)
+            .await
+            .map_err(|e| format!("Failed to store redaction hit: {}", e))?;
+
+            if status != "ignored" {
+                applied.push(hit);
+            }
+        }
+        redacted_strings.push(apply_redactions(&scanned_text.text, applied));
+    }
+
+    match content_json {
+        None => {
+            let redacted = redacted_strings.pop().unwrap_or_default();
+            sqlx::query("UPDATE Transcript SET RedactedContent = ?1 WHERE TranscriptID = ?2")
+                .bind(&redacted)
+                .bind(target_id)
+                .execute(&mut **tx)
+                .await
+                .map_err(|e| format!("Failed to store redacted transcript: {}", e))?;
+        }
+        Some(mut value) => {
+            let mut redacted_title = None;
+            for (scanned_text, redacted) in scanned.iter().zip(redacted_strings) {
+                let pointer = scanned_text.location.as_deref().unwrap_or("");
+                if pointer == TITLE_LOCATION {
+                    redacted_title = Some(redacted);
+                } else if let Some(slot) = value.pointer_mut(pointer) {
+                    *slot = Value::String(redacted);
+                }
+            }
+            let redacted = serde_json::to_string(&value)
+                .map_err(|e| format!("Failed to serialize redacted content: {}", e))?;
+            sqlx::query("UPDATE GeneratedContent SET redacted_data = ?1, redacted_title = ?2 WHERE content_id = ?3")
+                .bind(&redacted)
+                .bind(&redacted_title)
+                .bind(target_id)
+                .execute(&mut **tx)
+                .await
+                .map_err(|e| format!("Failed to store redacted content: {}", e))?;
+        }
+    }
+
+    load_report(tx, target, target_id).await
+}
+
+/// Keep the hits of a content edit the Ready gate rejected, so they can be redacted or ignored
+/// before the edit is retried. `report` is the scan of the edit, made in the transaction that was
+/// rolled back; hits of an earlier rejected edit are replaced.
+pub(crate) async fn record_rejected_scan(
+    database: &Database,
+    content_id: &str,
+    title: Option<&str>,
+    content_data: &str,
+    report: &RedactionReport,
+) -> Result<(), String> {
+    let proposal = proposal_hash(title, content_data);
+    let mut tx = database.pool.begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    sqlx::query("DELETE FROM RedactionHit WHERE TargetType = 'content' AND TargetID = ?1 AND ProposedHash IS NOT NULL")
+        .bind(content_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to clear redaction hits: {}", e))?;
+
+    for hit in &report.hits {
+        // The rejected scan may have reused the ID of a hit in the stored text, which is still there
+        let (taken,) = sqlx::query_as::<_, (bool,)>("SELECT EXISTS(SELECT 1 FROM RedactionHit WHERE HitID = ?1)")
+            .bind(&hit.hit_id)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(|e| format!("Failed to check redaction hit: {}", e))?;
+        let hit_id = if taken { Uuid::new_v4().to_string() } else { hit.hit_id.clone() };
+
+        sqlx::query(
+            r#"
+            INSERT INTO RedactionHit (HitID, TargetType, TargetID, Location, Category, MatchedText, Replacement, StartOffset, EndOffset, Status, ResolvedAt, ProposedHash)
+            VALUES (?1, 'content', ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, CASE WHEN ?9 = 'open' THEN NULL ELSE CURRENT_TIMESTAMP END, ?10)
+            "#
+        )
+        .bind(&hit_id)
+        .bind(content_id)
+        .bind(&hit.location)
+        .bind(&hit.category)
+        .bind(&hit.matched_text)
+        .bind(&hit.replacement)
+        .bind(hit.start as i64)
+        .bind(hit.end as i64)
+        .bind(&hit.status)
+        .bind(&proposal)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to store redaction hit: {}", e))?;
+    }
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit transaction: {}", e))?;
+
+    Ok(())
+}
+
+async fn scan(database: &Database, target: RedactionTarget, target_id: &str) -> Result<RedactionReport, String> {
+    let mut tx = database.pool.begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    let report = scan_target(&mut tx, target, target_id).await?;
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit transaction: {}", e))?;
+
+    Ok(report)
+}
+
+#[tauri::command]
+pub async fn scan_transcript_redactions(
+    database: State<'_, Arc<Database>>,
+    transcript_id: String,
+) -> Result<RedactionReport, String> {
+    scan(&database, RedactionTarget::Transcript, &transcript_id).await
+}
+
+#[tauri::command]
+pub async fn scan_content_redactions(
+    database: State<'_, Arc<Database>>,
+    content_id: String,
+) -> Result<RedactionReport, String> {
+    scan(&database, RedactionTarget::Content, &content_id).await
+}
+
+// The hits and redacted copy from the last scan, without scanning again
+#[tauri::command]
+pub async fn get_redaction_report(
+    database: State<'_, Arc<Database>>,
+    target_type: RedactionTarget,
+    target_id: String,
+) -> Result<RedactionReport, String> {
+    let mut tx = database.pool.begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    load_report(&mut tx, target_type, &target_id).await
+}
+
+// Redact, ignore or reopen a hit. Every occurrence of the same text in the same category within
+// that transcript or content gets the same decision, and the redacted copy is regenerated.
+#[tauri::command]
+pub async fn resolve_redaction_hit(
+    database: State<'_, Arc<Database>>,
+    hit_id: String,
+    resolution: HitResolution,
+) -> Result<RedactionReport, String> {
+    resolve_hit(&database, &hit_id, resolution).await
+}
+
+pub(crate) async fn resolve_hit(database: &Database, hit_id: &str, resolution: HitResolution) -> Result<RedactionReport, String> {
+    let mut tx = database.pool.begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    let (target_type, target_id, category, matched_text) = sqlx::query_as::<_, (String, String, String, String)>(
+        "SELECT TargetType, TargetID, Category, MatchedText FROM RedactionHit WHERE HitID = ?1"
+    )
+    .bind(hit_id)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|e| format!("Failed to fetch redaction hit: {}", e))?
+    .ok_or_else(|| "Redaction hit not found".to_string())?;
+    let target = if target_type == "content" { RedactionTarget::Content } else { RedactionTarget::Transcript };
+
+    let occurrences = sqlx::query_as::<_, (String, String)>(
+        "SELECT HitID, MatchedText FROM RedactionHit WHERE TargetType = ?1 AND TargetID = ?2 AND Category = ?3"
+    )
+    .bind(&target_type)
+    .bind(&target_id)
+    .bind(&category)
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(|e| format!("Failed to fetch redaction hits: {}", e))?;
+
+    let status = match resolution {
+        HitResolution::Redact => "redacted",
+        HitResolution::Ignore => "ignored",
+        HitResolution::Reopen => "open",
+    };
+    for (occurrence_id, text) in occurrences {
+        if text.to_lowercase() != matched_text.to_lowercase() {
+            continue;
+        }
+        sqlx::query(
+            "UPDATE RedactionHit SET Status = ?1, ResolvedAt = CASE WHEN ?1 = 'open' THEN NULL ELSE CURRENT_TIMESTAMP END WHERE HitID = ?2"
+        )
+        .bind(status)
+        .bind(&occurrence_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to update redaction hit: {}", e))?;
+    }
+
+    let report = scan_target(&mut tx, target, &target_id).await?;
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit transaction: {}", e))?;
+
+    Ok(report)
+}
+
+#[tauri::command]
+pub async fn get_redaction_terms(
+    database: State<'_, Arc<Database>>,
+) -> Result<Vec<RedactionTermInfo>, String> {
+    let rows = sqlx::query_as::<_, (String, String, Option<String>)>(
+        "SELECT TermID, Term, Replacement FROM RedactionTerm ORDER BY Term"
+    )
+    .fetch_all(&database.pool)
+    .await
+    .map_err(|e| format!("Failed to fetch blocklist: {}", e))?;
+
+    Ok(rows
+        .into_iter()
+        .map(|(term_id, term, replacement)| RedactionTermInfo { term_id, term, replacement })
+        .collect())
+}
+
+// Add a name or term to the blocklist; it is found on the next scan
+#[tauri::command]
+pub async fn add_redaction_term(
+    database: State<'_, Arc<Database>>,
+    term: String,
+    replacement: Option<String>,
+) -> Result<RedactionTermInfo, String> {
+    let term = term.split_whitespace().collect::<Vec<_>>().join(" ");
+    if !term.chars().any(char::is_alphanumeric) {
+        return Err("Blocklist term cannot be empty".to_string());
+    }
+    let replacement = replacement.map(|r| r.trim().to_string()).filter(|r| !r.is_empty());
+
+    let (exists,) = sqlx::query_as::<_, (bool,)>("SELECT EXISTS(SELECT 1 FROM RedactionTerm WHERE Term = ?1)")
+        .bind(&term)
+        .fetch_one(&database.pool)
+        .await
+        .map_err(|e| format!("Failed to check blocklist: {}", e))?;
+    if exists {
+        return Err(format!("\"{}\" is already on the blocklist", term));
+    }
+
+    let term_id = Uuid::new_v4().to_string();
+    sqlx::query("INSERT INTO RedactionTerm (TermID, Term, Replacement) VALUES (?1, ?2, ?3)")
+        .bind(&term_id)
+        .bind(&term)
+        .bind(&replacement)
+        .execute(&database.pool)
+        .await
+        .map_err(|e| format!("Failed to add blocklist term: {}", e))?;
+
+    Ok(RedactionTermInfo { term_id, term, replacement })
+}
+
+#[tauri::command]
+pub async fn remove_redaction_term(
+    database: State<'_, Arc<Database>>,
+    term_id: String,
+) -> Result<(), String> {
+    let result = sqlx::query("DELETE FROM RedactionTerm WHERE TermID = ?1")
+        .bind(&term_id)
+        .execute(&database.pool)
+        .await
+        .map_err(|e| format!("Failed to remove blocklist term: {}", e))?;
+
+    if result.rows_affected() == 0 {
+        return Err("Blocklist term not found".to_string());
+    }
+    Ok(())
+}
@@ -0,0 +1,257 @@
+// Related-transcript recommendations, cached until the corpus changes
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tauri::State;
+use crate::db::Database;
+use crate::services::keywords::CorpusStats;
+use crate::services::related::{rank_related, RelatedDocument, RelatedMatch};
+
+const DEFAULT_RELATED_LIMIT: usize = 10;
+// Rankings cached per transcript; requests for more are capped at this
+const CACHED_RELATED: usize = 25;
+// Weight a tag passes on to each parent, so "AI tools" also counts a little as "AI"
+const PARENT_TAG_FACTOR: f64 = 0.5;
+// Tag relevance assumed when none was recorded
+const DEFAULT_TAG_RELEVANCE: f64 = 0.5;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RelatedTranscriptsRequest {
+    pub transcript_id: String,
+    pub limit: Option<usize>, // Default 10, at most 25
+    pub refresh: Option<bool>, // Recompute even if the cached ranking is current
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RelatedTranscript {
+    pub transcript_id: String,
+    pub filename: String,
+    pub language: String,
+    pub imported_at: String,
+    pub score: f64,
+    pub tag_score: f64,
+    pub text_score: f64,
+    pub shared_tags: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RelatedTranscriptsResponse {
+    pub transcript_id: String,
+    pub related: Vec<RelatedTranscript>,
+    pub cached: bool, // Served from the cache rather than recomputed
+}
+
+// Changes whenever transcripts are imported, removed or edited, or tags or the tag hierarchy change
+async fn corpus_stamp(pool: &sqlx::SqlitePool) -> Result<String, String> {
+    let (transcripts, latest_import, revisions, tag_version) = sqlx::query_as::<_, (i64, String, i64, i64)>(
+        r#"
+        SELECT COUNT(*), CAST(COALESCE(MAX(ImportedAt), '') AS TEXT),
+               (SELECT COUNT(*) FROM TranscriptRevision),
+               (SELECT COALESCE(MAX(Version), 0) FROM TagTaxonomyVersion)
+        FROM Transcript
+        "#
+    )
+    .fetch_one(pool)
+    .await
+    .map_err(|e| format!("Failed to check transcript corpus: {}", e))?;
+
+    Ok(format!("{}|{}|{}|{}", transcripts, latest_import, revisions, tag_version))
+}
+
+// Rank every other recording's transcripts against this one; other tracks of the same file are
+// the same recording and left out
+async fn compute_related(pool: &sqlx::SqlitePool, transcript_id: &str) -> Result<Vec<RelatedMatch>, String> {
+    let rows = sqlx::query_as::<_, (String, String, String)>(
+        r#"
+        SELECT TranscriptID, Language, Content FROM Transcript
+        WHERE TranscriptID = ?1 OR FileID != (SELECT FileID FROM Transcript WHERE TranscriptID = ?1)
+        "#
+    )
+    .bind(transcript_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Failed to fetch transcripts: {}", e))?;
+
+    if !rows.iter().any(|(id, _, _)| id == transcript_id) {
+        return Err("Transcript not found".to_string());
+    }
+
+    // Tags with their ancestors, each level up at a reduced weight
+    let tag_rows = sqlx::query_as::<_, (String, String, f64)>(
+        r#"
+        WITH RECURSIVE Expanded(TranscriptID, TagID, Weight, Depth) AS (
+            SELECT TranscriptID, TagID, COALESCE(Relevance, ?1), 0 FROM TranscriptTags
+            UNION
+            SELECT e.TranscriptID, t.ParentTagID, e.Weight * ?2, e.Depth + 1
+            FROM Expanded e JOIN Tag t ON t.TagID = e.TagID
+            WHERE t.ParentTagID IS NOT NULL AND e.Depth < 10
+        )
+        SELECT e.TranscriptID, tg.TagName, MAX(e.Weight)
+        FROM Expanded e JOIN Tag tg ON tg.TagID = e.TagID
+        GROUP BY e.TranscriptID, e.TagID
+        "#
+    )
+    .bind(DEFAULT_TAG_RELEVANCE)
+    .bind(PARENT_TAG_FACTOR)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Failed to fetch tags: {}", e))?;
+
+    let mut tags: HashMap<String, Vec<(String, f64)>> = HashMap::new();
+    for (id, name, weight) in tag_rows {
+        tags.entry(id).or_default().push((name, weight));
+    }
+
+    let mut corpus = CorpusStats::default();
+    for (_, language, content) in &rows {
+        corpus.add_document(language, content);
+    }
+
+    let documents: Vec<RelatedDocument> = rows
+        .iter()
+        .map(|(id, language, content)| RelatedDocument {
+            transcript_id: id.clone(),
+            terms: corpus.tfidf_vector(language, content),
+            tags: tags.remove(id).unwrap_or_default(),
+        })
+        .collect();
+
+    let target = documents
+        .iter()
+        .find(|d| d.transcript_id == transcript_id)
+        .ok_or_else(|| "Transcript not found".to_string())?;
+
+    Ok(rank_related(target, &documents, CACHED_RELATED))
+}
+
+async fn store_related(
+    pool: &sqlx::SqlitePool,
+    transcript_id: &str,
+    matches: &[RelatedMatch],
+    stamp: &str,
+) -> Result<(), String> {
+    let mut tx = pool.begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    sqlx::query("DELETE FROM RelatedTranscript WHERE TranscriptID = ?1")
+        .bind(transcript_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to clear related transcripts: {}", e))?;
+
+    for (rank, m) in matches.iter().enumerate() {
+        let shared_tags = serde_json::to_string(&m.shared_tags)
+            .map_err(|e| format!("Failed to serialize shared tags: {}", e))?;
+        sqlx::query(
+            r#"
+            INSERT INTO RelatedTranscript (TranscriptID, RelatedTranscriptID, Rank, Score, TagScore, TextScore, SharedTags, CorpusStamp)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+            "#
+        )
+        .bind(transcript_id)
+        .bind(&m.transcript_id)
+        .bind(rank as i64)
+        .bind(m.score)
+        .bind(m.tag_score)
+        .bind(m.text_score)
+        .bind(&shared_tags)
+        .bind(stamp)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to store related transcripts: {}", e))?;
+    }
+
+    // Recorded even when there are no matches, so an empty ranking is cached too
+    sqlx::query(
+        r#"
+        INSERT INTO RelatedTranscriptState (TranscriptID, CorpusStamp, MatchCount)
+        VALUES (?1, ?2, ?3)
+        ON CONFLICT(TranscriptID) DO UPDATE
+        SET CorpusStamp = excluded.CorpusStamp, MatchCount = excluded.MatchCount, ComputedAt = CURRENT_TIMESTAMP
+        "#
+    )
+    .bind(transcript_id)
+    .bind(stamp)
+    .bind(matches.len() as i64)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| format!("Failed to store related transcripts: {}", e))?;
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit transaction: {}", e))?;
+
+    Ok(())
+}
+
+// Other recordings to reference or combine with this one, best match first. The ranking is
+// cached and recomputed once transcripts or tags have changed since it was computed.
+#[tauri::command]
+pub async fn get_related_transcripts(
+    database: State<'_, Arc<Database>>,
+    request: RelatedTranscriptsRequest,
+) -> Result<RelatedTranscriptsResponse, String> {
+    let limit = request.limit.unwrap_or(DEFAULT_RELATED_LIMIT).clamp(1, CACHED_RELATED);
+    let stamp = corpus_stamp(&database.pool).await?;
+
+    let stale = if request.refresh.unwrap_or(false) {
+        true
+    } else {
+        // Stale when never computed or computed against a different corpus
+        let (current,) = sqlx::query_as::<_, (bool,)>(
+            "SELECT EXISTS(SELECT 1 FROM RelatedTranscriptState WHERE TranscriptID = ?1 AND CorpusStamp = ?2)"
+        )
+        .bind(&request.transcript_id)
+        .bind(&stamp)
+        .fetch_one(&database.pool)
+        .await
+        .map_err(|e| format!("Failed to check related transcripts: {}", e))?;
+        !current
+    };
+
+    if stale {
+        let matches = compute_related(&database.pool, &request.transcript_id).await?;
+        store_related(&database.pool, &request.transcript_id, &matches, &stamp).await?;
+    }
+
+    let rows = sqlx::query_as::<_, (String, String, String, String, f64, f64, f64, Option<String>)>(
+        r#"
+        SELECT r.RelatedTranscriptID, f.OriginalName, t.Language, CAST(t.ImportedAt AS TEXT),
+               r.Score, r.TagScore, r.TextScore, r.SharedTags
+        FROM RelatedTranscript r
+        JOIN Transcript t ON r.RelatedTranscriptID = t.TranscriptID
+        JOIN File f ON t.FileID = f.FileID
+        WHERE r.TranscriptID = ?1
+        ORDER BY r.Rank
+        LIMIT ?2
+        "#
+    )
+    .bind(&request.transcript_id)
+    .bind(limit as i64)
+    .fetch_all(&database.pool)
+    .await
+    .map_err(|e| format!("Failed to fetch related transcripts: {}", e))?;
+
+    let related = rows
+        .into_iter()
+        .map(|(transcript_id, filename, language, imported_at, score, tag_score, text_score, shared_tags)| RelatedTranscript {
+            transcript_id,
+            filename,
+            language,
+            imported_at,
+            score,
+            tag_score,
+            text_score,
+            shared_tags: shared_tags
+                .and_then(|json| serde_json::from_str(&json).ok())
+                .unwrap_or_default(),
+        })
+        .collect();
+
+    Ok(RelatedTranscriptsResponse {
+        transcript_id: request.transcript_id,
+        related,
+        cached: !stale,
+    })
+}
@@ -0,0 +1,466 @@
+// Semantic transcript search: chunk embeddings stored in SQLite, fused with FTS5 keyword ranks
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tauri::State;
+use crate::commands::settings::SettingsState;
+use crate::db::Database;
+use crate::services::embeddings::{
+    blob_to_vector, chunk_plain_text, chunk_segments, cosine_similarity, vector_to_blob, EmbeddingProvider,
+};
+use crate::services::language_detect::normalize_language_filter;
+use crate::services::search_query::build_fts_query;
+use crate::services::settings::{EmbeddingSettings, SettingsService};
+use crate::services::transcript_parser::ParsedSegment;
+
+// Reciprocal rank fusion constant; dampens the advantage of the very top ranks
+const RRF_K: f64 = 60.0;
+// Candidates taken from each ranking before fusion
+const CANDIDATES_PER_RANKING: usize = 200;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IndexEmbeddingsRequest {
+    pub transcript_ids: Option<Vec<String>>, // All transcripts when omitted
+    pub force: Option<bool>, // Re-embed transcripts whose text hasn't changed
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct IndexEmbeddingsReport {
+    pub model: String,
+    pub indexed: Vec<String>,
+    pub unchanged: usize,
+    pub chunks_written: usize,
+    pub failed: Vec<(String, String)>, // Transcript ID and error
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SemanticSearchRequest {
+    pub query: String,
+    pub limit: Option<i32>,
+    pub semantic_weight: Option<f64>, // 0.0 = keyword only, 1.0 = semantic only, default 0.5
+    pub language: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SemanticSearchResult {
+    pub transcript_id: String,
+    pub file_id: String,
+    pub filename: String,
+    pub language: String,
+    pub summary: Option<String>,
+    pub score: f64, // Fused score, higher is better
+    pub similarity: Option<f64>, // Cosine similarity of the best matching chunk
+    pub semantic_rank: Option<usize>, // 1-based
+    pub keyword_rank: Option<usize>, // 1-based
+    pub passage: Option<String>, // Best matching chunk
+    pub passage_start_time: Option<f64>,
+    pub passage_end_time: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SemanticSearchResponse {
+    pub results: Vec<SemanticSearchResult>,
+    pub model: String,
+    pub unindexed_count: i64, // Transcripts not yet indexed with this model; only keyword matches can find them
+    pub keyword_error: Option<String>, // Set when the query isn't valid keyword syntax and only semantic ranking was used
+    pub search_time_ms: f64,
+}
+
+// Build the configured provider. The settings lock is released before any request is made.
+fn embedding_provider(
+    settings_state: &SettingsState,
+    app_handle: &tauri::AppHandle,
+) -> Result<EmbeddingProvider, String> {
+    let (settings, api_key) = embedding_settings(settings_state, app_handle, true)?;
+    EmbeddingProvider::from_settings(&settings, api_key)
+}
+
+// The configured model's ID, for lookups that don't need to call the provider
+fn embedding_model_id(settings_state: &SettingsState, app_handle: &tauri::AppHandle) -> Result<String, String> {
+    let (settings, _) = embedding_settings(settings_state, app_handle, false)?;
+    EmbeddingProvider::model_id_for_settings(&settings)
+}
+
+// The embedding settings, plus the endpoint's API key when asked for and an HTTP provider is set
+fn embedding_settings(
+    settings_state: &SettingsState,
+    app_handle: &tauri::AppHandle,
+    with_api_key: bool,
+) -> Result<(EmbeddingSettings, Option<String>), String> {
+    let mut state_guard = settings_state.0.lock().unwrap();
+
+    if state_guard.is_none() {
+        let service = SettingsService::new(app_handle)
+            .map_err(|e| format!("Failed to initialize settings service: {}", e))?;
+        *state_guard = Some(service);
+    }
+
+    let service = state_guard.as_ref().unwrap();
+    let settings = service.load_settings()
+        .map_err(|e| format!("Failed to load settings: {}", e))?;
+
+    let api_key = if with_api_key && settings.embeddings.provider == "http" {
+        service.get_decrypted_api_key(&settings.embeddings.api_key_service)
+            .map_err(|e| format!("Failed to get embedding API key: {}", e))?
+    } else {
+        None
+    };
+
+    Ok((settings.embeddings, api_key))
+}
+
+fn content_hash(content: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(content.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+async fn load_segments(pool: &sqlx::SqlitePool, transcript_id: &str) -> Result<Vec<ParsedSegment>, sqlx::Error> {
+    let rows = sqlx::query_as::<_, (Option<f64>, Option<f64>, String)>(
+        "SELECT StartTime, EndTime, Text FROM TranscriptSegment WHERE TranscriptID = ?1 ORDER BY SegmentIndex"
+    )
+    .bind(transcript_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|(start_time, end_time, text)| ParsedSegment {
+            start_time,
+            end_time,
+            speaker: None,
+            text,
+            words: Vec::new(),
+        })
+        .collect())
+}
+
+async fn index_transcript(
+    pool: &sqlx::SqlitePool,
+    provider: &EmbeddingProvider,
+    model: &str,
+    transcript_id: &str,
+    content: &str,
+) -> Result<usize, String> {
+    let segments = load_segments(pool, transcript_id)
+        .await
+        .map_err(|e| format!("Failed to fetch transcript segments: {}", e))?;
+
+    let chunks = if segments.is_empty() {
+        chunk_plain_text(content)
+    } else {
+        chunk_segments(&segments)
+    };
+
+    let texts: Vec<String> = chunks.iter().map(|c| c.text.clone()).collect();
+    let vectors = provider.embed(&texts).await?;
+    let hash = content_hash(content);
+
+    let mut tx = pool.begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    sqlx::query("DELETE FROM TranscriptChunk WHERE TranscriptID = ?1 AND Model = ?2")
+        .bind(transcript_id)
+        .bind(model)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to clear old embeddings: {}", e))?;
+
+    for (index, (chunk, vector)) in chunks.iter().zip(&vectors).enumerate() {
+        sqlx::query(
+            r#"
+            INSERT INTO TranscriptChunk (
+                ChunkID, TranscriptID, ChunkIndex, StartSegment, EndSegment, StartTime, EndTime,
+                Text, Model, Dimensions, Vector, SourceHash
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)
+            "#
+        )
+        .bind(uuid::Uuid::new_v4().to_string())
+        .bind(transcript_id)
+        .bind(index as i64)
+        .bind(chunk.segments.map(|(first, _)| first as i64))
+        .bind(chunk.segments.map(|(_, last)| last as i64))
+        .bind(chunk.start_time)
+        .bind(chunk.end_time)
+        .bind(&chunk.text)
+        .bind(model)
+        .bind(vector.len() as i64)
+        .bind(vector_to_blob(vector))
+        .bind(&hash)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to store embedding: {}", e))?;
+    }
+
+    sqlx::query(
+        r#"
+        INSERT OR REPLACE INTO TranscriptEmbeddingIndex (TranscriptID, Model, SourceHash, ChunkCount, IndexedAt)
+        VALUES (?1, ?2, ?3, ?4, CURRENT_TIMESTAMP)
+        "#
+    )
+    .bind(transcript_id)
+    .bind(model)
+    .bind(&hash)
+    .bind(chunks.len() as i64)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| format!("Failed to record embedding index: {}", e))?;
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit transaction: {}", e))?;
+
+    Ok(chunks.len())
+}
+
+// Embed transcripts chunk by chunk. Transcripts whose text is unchanged since they were last
+// embedded with the current model are skipped unless `force` is set.
+#[tauri::command]
+pub async fn index_transcript_embeddings(
+    database: State<'_, Arc<Database>>,
+    settings_state: State<'_, SettingsState>,
+    app_handle: tauri::AppHandle,
+    request: IndexEmbeddingsRequest,
+) -> Result<IndexEmbeddingsReport, String> {
+    let provider = embedding_provider(&settings_state, &app_handle)?;
+    let model = provider.model_id();
+    let force = request.force.unwrap_or(false);
+
+    let transcripts = match &request.transcript_ids {
+        Some(ids) => {
+            let ids = serde_json::to_string(ids).map_err(|e| format!("Invalid transcript IDs: {}", e))?;
+            sqlx::query_as::<_, (String, String)>(
+                "SELECT TranscriptID, Content FROM Transcript WHERE TranscriptID IN (SELECT value FROM json_each(?1))"
+            )
+            .bind(ids)
+            .fetch_all(&database.pool)
+            .await
+        }
+        None => {
+            sqlx::query_as::<_, (String, String)>("SELECT TranscriptID, Content FROM Transcript")
+                .fetch_all(&database.pool)
+                .await
+        }
+    }
+    .map_err(|e| format!("Failed to fetch transcripts: {}", e))?;
+
+    let mut report = IndexEmbeddingsReport {
+        model: model.clone(),
+        ..Default::default()
+    };
+
+    for (transcript_id, content) in transcripts {
+        if !force {
+            let (current,) = sqlx::query_as::<_, (bool,)>(
+                "SELECT EXISTS(SELECT 1 FROM TranscriptEmbeddingIndex WHERE TranscriptID = ?1 AND Model = ?2 AND SourceHash = ?3)"
+            )
+            .bind(&transcript_id)
+            .bind(&model)
+            .bind(content_hash(&content))
+            .fetch_one(&database.pool)
+            .await
+            .map_err(|e| format!("Failed to check existing embeddings: {}", e))?;
+
+            if current {
+                report.unchanged += 1;
+                continue;
+            }
+        }
+
+        // One failing transcript (e.g. a provider error on a huge input) doesn't stop the batch
+        match index_transcript(&database.pool, &provider, &model, &transcript_id, &content).await {
+            Ok(chunks) => {
+                report.chunks_written += chunks;
+                report.indexed.push(transcript_id);
+            }
+            Err(e) => report.failed.push((transcript_id, e)),
+        }
+    }
+
+    Ok(report)
+}
+
+// Best matching chunk of a transcript
+struct ChunkMatch {
+    similarity: f32,
+    text: String,
+    start_time: Option<f64>,
+    end_time: Option<f64>,
+}
+
+// A transcript's place in the fused ranking
+#[derive(Default)]
+struct FusedRank {
+    score: f64,
+    semantic_rank: Option<usize>,
+    keyword_rank: Option<usize>,
+}
+
+// Hybrid search: transcripts are ranked by their best chunk's cosine similarity to the query and
+// by FTS5 keyword rank, and the two rankings are combined with weighted reciprocal rank fusion.
+#[tauri::command]
+pub async fn semantic_search_transcripts(
+    database: State<'_, Arc<Database>>,
+    settings_state: State<'_, SettingsState>,
+    app_handle: tauri::AppHandle,
+    request: SemanticSearchRequest,
+) -> Result<SemanticSearchResponse, String> {
+    let start_time = std::time::Instant::now();
+
+    let query = request.query.trim();
+    if query.is_empty() {
+        return Err("Search query is empty".to_string());
+    }
+
+    let limit = request.limit.unwrap_or(20).clamp(1, 100) as usize;
+    let semantic_weight = request.semantic_weight.unwrap_or(0.5).clamp(0.0, 1.0);
+    let language = normalize_language_filter(request.language.as_deref())?;
+    let language = language.as_deref();
+
+    // Read from the settings alone, so keyword-only searches never build the provider or read its key
+    let model = embedding_model_id(&settings_state, &app_handle)?;
+
+    // Semantic ranking
+    let mut semantic: Vec<(String, ChunkMatch)> = Vec::new();
+    if semantic_weight > 0.0 {
+        let provider = embedding_provider(&settings_state, &app_handle)?;
+        let query_vector = provider
+            .embed(&[query.to_string()])
+            .await?
+            .pop()
+            .ok_or_else(|| "Embedding provider returned no vector for the query".to_string())?;
+
+        let chunks = sqlx::query_as::<_, (String, String, Option<f64>, Option<f64>, Vec<u8>)>(
+            r#"
+            SELECT c.TranscriptID, c.Text, c.StartTime, c.EndTime, c.Vector
+            FROM TranscriptChunk c
+            JOIN Transcript t ON c.TranscriptID = t.TranscriptID
+            WHERE c.Model = ?1 AND (?2 IS NULL OR t.Language = ?2)
+            "#
+        )
+        .bind(&model)
+        .bind(language)
+        .fetch_all(&database.pool)
+        .await
+        .map_err(|e| format!("Failed to fetch embeddings: {}", e))?;
+
+        let mut best: HashMap<String, ChunkMatch> = HashMap::new();
+        for (transcript_id, text, start_time, end_time, vector) in chunks {
+            let similarity = cosine_similarity(&query_vector, &blob_to_vector(&vector));
+            if best.get(&transcript_id).is_none_or(|b| similarity > b.similarity) {
+                best.insert(transcript_id, ChunkMatch { similarity, text, start_time, end_time });
+            }
+        }
+
+        semantic = best.into_iter().filter(|(_, m)| m.similarity > 0.0).collect();
+        semantic.sort_by(|a, b| b.1.similarity.total_cmp(&a.1.similarity));
+        semantic.truncate(CANDIDATES_PER_RANKING);
+    }
+
+    // Keyword ranking; natural-language queries that aren't valid search syntax fall back to semantic only
+    let mut keyword: Vec<String> = Vec::new();
+    let mut keyword_error = None;
+    if semantic_weight < 1.0 {
+        match build_fts_query(query) {
+            Ok(fts_query) => {
+                keyword = sqlx::query_as::<_, (String,)>(
+                    r#"
+                    SELECT t.TranscriptID
+                    FROM TranscriptFTS fts
+                    JOIN Transcript t ON fts.rowid = t.rowid
+                    WHERE TranscriptFTS MATCH ?1 AND (?2 IS NULL OR t.Language = ?2)
+                    ORDER BY rank
+                    LIMIT ?3
+                    "#
+                )
+                .bind(&fts_query)
+                .bind(language)
+                .bind(CANDIDATES_PER_RANKING as i64)
+                .fetch_all(&database.pool)
+                .await
+                .map_err(|e| format!("Search query failed: {}", e))?
+                .into_iter()
+                .map(|(id,)| id)
+                .collect();
+            }
+            Err(e) => keyword_error = Some(e),
+        }
+    }
+
+    // Weighted reciprocal rank fusion
+    let mut fused: HashMap<String, FusedRank> = HashMap::new();
+    for (index, (transcript_id, _)) in semantic.iter().enumerate() {
+        let entry = fused.entry(transcript_id.clone()).or_default();
+        entry.score += semantic_weight / (RRF_K + index as f64 + 1.0);
+        entry.semantic_rank = Some(index + 1);
+    }
+    for (index, transcript_id) in keyword.iter().enumerate() {
+        let entry = fused.entry(transcript_id.clone()).or_default();
+        entry.score += (1.0 - semantic_weight) / (RRF_K + index as f64 + 1.0);
+        entry.keyword_rank = Some(index + 1);
+    }
+
+    let mut ranked: Vec<(String, FusedRank)> = fused.into_iter().collect();
+    ranked.sort_by(|a, b| b.1.score.total_cmp(&a.1.score).then_with(|| a.0.cmp(&b.0)));
+    ranked.truncate(limit);
+
+    let mut best_chunks: HashMap<String, ChunkMatch> = semantic.into_iter().collect();
+    let mut results = Vec::new();
+
+    for (transcript_id, rank) in ranked {
+        let (file_id, filename, language, summary) = sqlx::query_as::<_, (String, String, String, Option<String>)>(
+            r#"
+            SELECT t.FileID, f.OriginalName, t.Language, t.Summary
+            FROM Transcript t
+            JOIN File f ON t.FileID = f.FileID
+            WHERE t.TranscriptID = ?1
+            "#
+        )
+        .bind(&transcript_id)
+        .fetch_one(&database.pool)
+        .await
+        .map_err(|e| format!("Failed to fetch transcript: {}", e))?;
+
+        let chunk = best_chunks.remove(&transcript_id);
+
+        results.push(SemanticSearchResult {
+            transcript_id,
+            file_id,
+            filename,
+            language,
+            summary,
+            score: rank.score,
+            similarity: chunk.as_ref().map(|c| c.similarity as f64),
+            semantic_rank: rank.semantic_rank,
+            keyword_rank: rank.keyword_rank,
+            passage_start_time: chunk.as_ref().and_then(|c| c.start_time),
+            passage_end_time: chunk.as_ref().and_then(|c| c.end_time),
+            passage: chunk.map(|c| c.text),
+        });
+    }
+
+    let (unindexed_count,) = sqlx::query_as::<_, (i64,)>(
+        r#"
+        SELECT COUNT(*) FROM Transcript t
+        WHERE (?2 IS NULL OR t.Language = ?2)
+          AND NOT EXISTS(SELECT 1 FROM TranscriptEmbeddingIndex i WHERE i.TranscriptID = t.TranscriptID AND i.Model = ?1)
+        "#
+    )
+    .bind(&model)
+    .bind(language)
+    .fetch_one(&database.pool)
+    .await
+    .map_err(|e| format!("Failed to count unindexed transcripts: {}", e))?;
+
+    let search_time_ms = start_time.elapsed().as_secs_f64() * 1000.0;
+
+    Ok(SemanticSearchResponse {
+        results,
+        model,
+        unindexed_count,
+        keyword_error,
+        search_time_ms,
+    })
+}
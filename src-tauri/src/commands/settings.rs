@@ -1,5 +1,5 @@
 use crate::services::settings::{
-    BrandSettings, FileOrganization, Preferences, SettingsService, UsageStats,
+    BrandSettings, EmbeddingSettings, FileOrganization, Preferences, SettingsService, UsageStats,
 };
 use serde::{Deserialize, Serialize};
 use std::sync::Mutex;
@@ -20,6 +20,7 @@ pub struct SettingsResponse {
     pub file_organization: FileOrganization,
     pub brand_settings: BrandSettings,
     pub usage_stats: UsageStats,
+    pub embeddings: EmbeddingSettings,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -65,6 +66,7 @@ pub async fn get_settings(
         file_organization: settings.file_organization,
         brand_settings: settings.brand_settings,
         usage_stats: settings.usage_stats,
+        embeddings: settings.embeddings,
     })
 }
 
@@ -173,6 +175,31 @@ pub async fn update_brand_settings(
     Ok(())
 }
 
+#[tauri::command]
+pub async fn update_embedding_settings(
+    state: State<'_, SettingsState>,
+    app_handle: tauri::AppHandle,
+    embeddings: EmbeddingSettings,
+) -> Result<(), String> {
+    if embeddings.provider != "hashing" && embeddings.provider != "http" {
+        return Err(format!("Unknown embedding provider: {}", embeddings.provider));
+    }
+    
+    let mut state_guard = state.0.lock().unwrap();
+    
+    if state_guard.is_none() {
+        let service = SettingsService::new(&app_handle)
+            .map_err(|e| format!("Failed to initialize settings service: {}", e))?;
+        *state_guard = Some(service);
+    }
+    
+    let service = state_guard.as_ref().unwrap();
+    service.update_embedding_settings(embeddings)
+        .map_err(|e| format!("Failed to update embedding settings: {}", e))?;
+    
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn update_usage_stats(
     state: State<'_, SettingsState>,
@@ -0,0 +1,131 @@
+// Speaker diarization: per-speaker statistics, renaming and speaker-filtered text
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tauri::State;
+use crate::db::Database;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SpeakerStats {
+    pub speaker_id: String,
+    pub label: String,
+    pub display_name: String,
+    pub segment_count: i64,
+    pub word_count: i64,
+    pub talk_time_seconds: f64,
+    pub word_share: f64,
+    pub talk_time_share: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RenameSpeakerRequest {
+    pub speaker_id: String,
+    pub display_name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SpeakerTextRequest {
+    pub transcript_id: String,
+    pub speaker_ids: Vec<String>,
+}
+
+#[tauri::command]
+pub async fn get_transcript_speakers(
+    database: State<'_, Arc<Database>>,
+    transcript_id: String,
+) -> Result<Vec<SpeakerStats>, String> {
+    // Talk time only counts timed segments; untimed formats contribute words only
+    let rows = sqlx::query_as::<_, (String, String, String, i64, i64, f64)>(
+        r#"
+        SELECT
+            s.SpeakerID,
+            s.Label,
+            s.DisplayName,
+            COUNT(seg.SegmentID),
+            COALESCE(SUM(seg.WordCount), 0),
+            COALESCE(SUM(MAX(seg.EndTime - seg.StartTime, 0)), 0.0)
+        FROM Speaker s
+        LEFT JOIN TranscriptSegment seg ON seg.SpeakerID = s.SpeakerID
+        WHERE s.TranscriptID = ?1
+        GROUP BY s.SpeakerID
+        ORDER BY MIN(seg.SegmentIndex)
+        "#
+    )
+    .bind(&transcript_id)
+    .fetch_all(&database.pool)
+    .await
+    .map_err(|e| format!("Failed to fetch speakers: {}", e))?;
+
+    let total_words: i64 = rows.iter().map(|r| r.4).sum();
+    let total_time: f64 = rows.iter().map(|r| r.5).sum();
+
+    let speakers = rows
+        .into_iter()
+        .map(|(speaker_id, label, display_name, segment_count, word_count, talk_time_seconds)| {
+            SpeakerStats {
+                speaker_id,
+                label,
+                display_name,
+                segment_count,
+                word_count,
+                talk_time_seconds,
+                word_share: if total_words > 0 { word_count as f64 / total_words as f64 } else { 0.0 },
+                talk_time_share: if total_time > 0.0 { talk_time_seconds / total_time } else { 0.0 },
+            }
+        })
+        .collect();
+
+    Ok(speakers)
+}
+
+#[tauri::command]
+pub async fn rename_speaker(
+    database: State<'_, Arc<Database>>,
+    request: RenameSpeakerRequest,
+) -> Result<(), String> {
+    let display_name = request.display_name.trim();
+    if display_name.is_empty() {
+        return Err("Speaker name cannot be empty".to_string());
+    }
+
+    let result = sqlx::query("UPDATE Speaker SET DisplayName = ?1 WHERE SpeakerID = ?2")
+        .bind(display_name)
+        .bind(&request.speaker_id)
+        .execute(&database.pool)
+        .await
+        .map_err(|e| format!("Failed to rename speaker: {}", e))?;
+
+    if result.rows_affected() == 0 {
+        return Err("Speaker not found".to_string());
+    }
+
+    Ok(())
+}
+
+// Text spoken by the selected speakers only, e.g. a guest's answers for content generation
+#[tauri::command]
+pub async fn get_speaker_text(
+    database: State<'_, Arc<Database>>,
+    request: SpeakerTextRequest,
+) -> Result<String, String> {
+    if request.speaker_ids.is_empty() {
+        return Err("At least one speaker must be selected".to_string());
+    }
+
+    let placeholders = vec!["?"; request.speaker_ids.len()].join(", ");
+    let query = format!(
+        "SELECT Text FROM TranscriptSegment WHERE TranscriptID = ? AND SpeakerID IN ({}) ORDER BY SegmentIndex",
+        placeholders
+    );
+
+    let mut q = sqlx::query_as::<_, (String,)>(&query).bind(&request.transcript_id);
+    for speaker_id in &request.speaker_ids {
+        q = q.bind(speaker_id);
+    }
+
+    let rows = q
+        .fetch_all(&database.pool)
+        .await
+        .map_err(|e| format!("Failed to fetch speaker text: {}", e))?;
+
+    Ok(rows.into_iter().map(|(text,)| text).collect::<Vec<_>>().join("\n\n"))
+}
@@ -0,0 +1,392 @@
+// Tag taxonomy: parent/child tags, aliases and merging duplicate tags
+use serde::{Deserialize, Serialize};
+use sqlx::{Executor, Sqlite, Transaction};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tauri::State;
+use uuid::Uuid;
+use crate::commands::deletion::table_exists;
+use crate::db::Database;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TagNode {
+    pub tag_id: String,
+    pub name: String,
+    pub category: Option<String>,
+    pub parent_tag_id: Option<String>,
+    pub aliases: Vec<String>,
+    pub transcript_count: i64, // Transcripts tagged with this tag itself
+    pub total_transcript_count: i64, // Transcripts tagged with this tag or any tag under it
+    pub children: Vec<TagNode>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MergeTagsRequest {
+    pub source_tag_id: String, // Removed; becomes an alias of the target
+    pub target_tag_id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TagMergeResult {
+    pub tag_id: String,
+    pub transcript_links_moved: u64,
+    pub file_links_moved: u64,
+    pub aliases: Vec<String>,
+}
+
+/// Tag a name refers to: the tag with that name, otherwise the tag it is an alias of.
+/// Matching ignores case.
+pub(crate) async fn resolve_tag_id<'e, E>(executor: E, name: &str) -> Result<Option<String>, sqlx::Error>
+where
+    E: Executor<'e, Database = Sqlite>,
+{
+    let row = sqlx::query_as::<_, (String,)>(
+        r#"
+        SELECT TagID FROM (
+            SELECT TagID, 0 AS Priority FROM Tag WHERE LOWER(TagName) = LOWER(?1)
+            UNION ALL
+            SELECT TagID, 1 AS Priority FROM TagAlias WHERE Alias = ?1
+        )
+        ORDER BY Priority
+        LIMIT 1
+        "#
+    )
+    .bind(name.trim())
+    .fetch_optional(executor)
+    .await?;
+
+    Ok(row.map(|(tag_id,)| tag_id))
+}
+
+async fn fetch_tag(
+    tx: &mut Transaction<'_, Sqlite>,
+    tag_id: &str,
+) -> Result<(String, Option<String>, Option<String>), String> {
+    sqlx::query_as::<_, (String, Option<String>, Option<String>)>(
+        "SELECT TagName, Category, ParentTagID FROM Tag WHERE TagID = ?1"
+    )
+    .bind(tag_id)
+    .fetch_optional(&mut **tx)
+    .await
+    .map_err(|e| format!("Failed to fetch tag: {}", e))?
+    .ok_or_else(|| format!("Tag not found: {}", tag_id))
+}
+
+// Whether `tag_id` sits anywhere under `ancestor_id`
+async fn is_descendant(tx: &mut Transaction<'_, Sqlite>, tag_id: &str, ancestor_id: &str) -> Result<bool, String> {
+    let (descendant,) = sqlx::query_as::<_, (bool,)>(
+        r#"
+        WITH RECURSIVE Ancestor(TagID) AS (
+            SELECT ParentTagID FROM Tag WHERE TagID = ?1
+            UNION
+            SELECT t.ParentTagID FROM Tag t JOIN Ancestor a ON t.TagID = a.TagID
+        )
+        SELECT EXISTS(SELECT 1 FROM Ancestor WHERE TagID = ?2)
+        "#
+    )
+    .bind(tag_id)
+    .bind(ancestor_id)
+    .fetch_one(&mut **tx)
+    .await
+    .map_err(|e| format!("Failed to check tag hierarchy: {}", e))?;
+
+    Ok(descendant)
+}
+
+async fn load_aliases<'e, E>(executor: E, tag_id: &str) -> Result<Vec<String>, String>
+where
+    E: Executor<'e, Database = Sqlite>,
+{
+    let rows = sqlx::query_as::<_, (String,)>("SELECT Alias FROM TagAlias WHERE TagID = ?1 ORDER BY Alias")
+        .bind(tag_id)
+        .fetch_all(executor)
+        .await
+        .map_err(|e| format!("Failed to fetch tag aliases: {}", e))?;
+
+    Ok(rows.into_iter().map(|(alias,)| alias).collect())
+}
+
+// All tags as a forest, with direct and rolled-up transcript counts
+#[tauri::command]
+pub async fn get_tag_tree(
+    database: State<'_, Arc<Database>>,
+) -> Result<Vec<TagNode>, String> {
+    let rows = sqlx::query_as::<_, (String, String, Option<String>, Option<String>, i64, i64)>(
+        r#"
+        WITH RECURSIVE Descendant(RootID, TagID) AS (
+            SELECT TagID, TagID FROM Tag
+            UNION
+            SELECT d.RootID, t.TagID FROM Tag t JOIN Descendant d ON t.ParentTagID = d.TagID
+        )
+        SELECT t.TagID, t.TagName, t.Category, t.ParentTagID,
+               (SELECT COUNT(*) FROM TranscriptTags tt WHERE tt.TagID = t.TagID),
+               COUNT(DISTINCT tt.TranscriptID)
+        FROM Tag t
+        JOIN Descendant d ON d.RootID = t.TagID
+        LEFT JOIN TranscriptTags tt ON tt.TagID = d.TagID
+        GROUP BY t.TagID
+        ORDER BY t.TagName COLLATE NOCASE
+        "#
+    )
+    .fetch_all(&database.pool)
+    .await
+    .map_err(|e| format!("Failed to fetch tags: {}", e))?;
+
+    let alias_rows = sqlx::query_as::<_, (String, String)>("SELECT TagID, Alias FROM TagAlias ORDER BY Alias")
+        .fetch_all(&database.pool)
+        .await
+        .map_err(|e| format!("Failed to fetch tag aliases: {}", e))?;
+    let mut aliases: HashMap<String, Vec<String>> = HashMap::new();
+    for (tag_id, alias) in alias_rows {
+        aliases.entry(tag_id).or_default().push(alias);
+    }
+
+    let mut children: HashMap<Option<String>, Vec<TagNode>> = HashMap::new();
+    for (tag_id, name, category, parent_tag_id, transcript_count, total_transcript_count) in rows {
+        children.entry(parent_tag_id.clone()).or_default().push(TagNode {
+            aliases: aliases.remove(&tag_id).unwrap_or_default(),
+            tag_id,
+            name,
+            category,
+            parent_tag_id,
+            transcript_count,
+            total_transcript_count,
+            children: Vec::new(),
+        });
+    }
+
+    fn attach(node: &mut TagNode, children: &mut HashMap<Option<String>, Vec<TagNode>>) {
+        node.children = children.remove(&Some(node.tag_id.clone())).unwrap_or_default();
+        for child in &mut node.children {
+            attach(child, children);
+        }
+    }
+
+    let mut roots = children.remove(&None).unwrap_or_default();
+    for root in &mut roots {
+        attach(root, &mut children);
+    }
+
+    Ok(roots)
+}
+
+// Move a tag under another one, or to the top level when `parent_tag_id` is None
+#[tauri::command]
+pub async fn set_tag_parent(
+    database: State<'_, Arc<Database>>,
+    tag_id: String,
+    parent_tag_id: Option<String>,
+) -> Result<(), String> {
+    let mut tx = database.pool.begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    fetch_tag(&mut tx, &tag_id).await?;
+    if let Some(parent_id) = &parent_tag_id {
+        fetch_tag(&mut tx, parent_id).await?;
+        if parent_id == &tag_id || is_descendant(&mut tx, parent_id, &tag_id).await? {
+            return Err("A tag cannot be placed under itself or one of its own children".to_string());
+        }
+    }
+
+    sqlx::query("UPDATE Tag SET ParentTagID = ?1 WHERE TagID = ?2")
+        .bind(&parent_tag_id)
+        .bind(&tag_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to update tag: {}", e))?;
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit transaction: {}", e))?;
+
+    Ok(())
+}
+
+// Add an alternative name; returns the tag's aliases
+#[tauri::command]
+pub async fn add_tag_alias(
+    database: State<'_, Arc<Database>>,
+    tag_id: String,
+    alias: String,
+) -> Result<Vec<String>, String> {
+    let alias = alias.trim();
+    if alias.is_empty() {
+        return Err("Alias cannot be empty".to_string());
+    }
+
+    let mut tx = database.pool.begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    let (tag_name, _, _) = fetch_tag(&mut tx, &tag_id).await?;
+
+    match resolve_tag_id(&mut *tx, alias).await.map_err(|e| format!("Failed to check tag names: {}", e))? {
+        Some(existing) if existing == tag_id => {
+            if alias.eq_ignore_ascii_case(&tag_name) {
+                return Err(format!("'{}' is already the tag's name", alias));
+            }
+        }
+        Some(existing) => {
+            let (existing_name, _, _) = fetch_tag(&mut tx, &existing).await?;
+            return Err(format!("'{}' already refers to the tag '{}'; merge the tags instead", alias, existing_name));
+        }
+        None => {
+            sqlx::query("INSERT INTO TagAlias (AliasID, TagID, Alias) VALUES (?1, ?2, ?3)")
+                .bind(Uuid::new_v4().to_string())
+                .bind(&tag_id)
+                .bind(alias)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| format!("Failed to add alias: {}", e))?;
+        }
+    }
+
+    let aliases = load_aliases(&mut *tx, &tag_id).await?;
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit transaction: {}", e))?;
+
+    Ok(aliases)
+}
+
+#[tauri::command]
+pub async fn remove_tag_alias(
+    database: State<'_, Arc<Database>>,
+    alias: String,
+) -> Result<(), String> {
+    let result = sqlx::query("DELETE FROM TagAlias WHERE Alias = ?1")
+        .bind(alias.trim())
+        .execute(&database.pool)
+        .await
+        .map_err(|e| format!("Failed to remove alias: {}", e))?;
+
+    if result.rows_affected() == 0 {
+        return Err("Alias not found".to_string());
+    }
+
+    Ok(())
+}
+
+// Fold one tag into another. Transcript and file links move to the target (keeping the higher
+// relevance where both were linked), child tags and aliases follow, and the source's name becomes
+// an alias so future imports resolve to the target.
+#[tauri::command]
+pub async fn merge_tags(
+    database: State<'_, Arc<Database>>,
+    request: MergeTagsRequest,
+) -> Result<TagMergeResult, String> {
+    let MergeTagsRequest { source_tag_id, target_tag_id } = request;
+    if source_tag_id == target_tag_id {
+        return Err("Cannot merge a tag into itself".to_string());
+    }
+
+    let mut tx = database.pool.begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    let (source_name, source_category, source_parent) = fetch_tag(&mut tx, &source_tag_id).await?;
+    let (target_name, _, _) = fetch_tag(&mut tx, &target_tag_id).await?;
+
+    // A target nested under the source takes the source's place, so the children moved below don't form a cycle
+    if is_descendant(&mut tx, &target_tag_id, &source_tag_id).await? {
+        sqlx::query("UPDATE Tag SET ParentTagID = ?1 WHERE TagID = ?2")
+            .bind(&source_parent)
+            .bind(&target_tag_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| format!("Failed to update tag: {}", e))?;
+    }
+
+    sqlx::query("UPDATE Tag SET ParentTagID = ?1 WHERE ParentTagID = ?2")
+        .bind(&target_tag_id)
+        .bind(&source_tag_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to move child tags: {}", e))?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO TranscriptTags (TranscriptID, TagID, Relevance)
+        SELECT TranscriptID, ?2, Relevance FROM TranscriptTags WHERE TagID = ?1 AND true
+        ON CONFLICT(TranscriptID, TagID) DO UPDATE
+        SET Relevance = COALESCE(MAX(Relevance, excluded.Relevance), Relevance, excluded.Relevance)
+        "#
+    )
+    .bind(&source_tag_id)
+    .bind(&target_tag_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| format!("Failed to move transcript tags: {}", e))?;
+
+    let transcript_links_moved = sqlx::query("DELETE FROM TranscriptTags WHERE TagID = ?1")
+        .bind(&source_tag_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to move transcript tags: {}", e))?
+        .rows_affected();
+
+    let mut file_links_moved = 0;
+    if table_exists(&mut tx, "FileTag").await.map_err(|e| format!("Failed to check file tags: {}", e))? {
+        file_links_moved = sqlx::query(
+            "UPDATE FileTag SET TagID = ?2 WHERE TagID = ?1 AND FileID NOT IN (SELECT FileID FROM FileTag WHERE TagID = ?2)"
+        )
+        .bind(&source_tag_id)
+        .bind(&target_tag_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to move file tags: {}", e))?
+        .rows_affected();
+
+        // Files that already had the target tag
+        sqlx::query("DELETE FROM FileTag WHERE TagID = ?1")
+            .bind(&source_tag_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| format!("Failed to move file tags: {}", e))?;
+    }
+
+    sqlx::query("UPDATE TagAlias SET TagID = ?1 WHERE TagID = ?2")
+        .bind(&target_tag_id)
+        .bind(&source_tag_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to move tag aliases: {}", e))?;
+
+    sqlx::query("UPDATE Tag SET Category = COALESCE(Category, ?1) WHERE TagID = ?2")
+        .bind(&source_category)
+        .bind(&target_tag_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to update tag: {}", e))?;
+
+    sqlx::query("DELETE FROM Tag WHERE TagID = ?1")
+        .bind(&source_tag_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to remove merged tag: {}", e))?;
+
+    if !source_name.eq_ignore_ascii_case(&target_name) {
+        sqlx::query("INSERT OR IGNORE INTO TagAlias (AliasID, TagID, Alias) VALUES (?1, ?2, ?3)")
+            .bind(Uuid::new_v4().to_string())
+            .bind(&target_tag_id)
+            .bind(&source_name)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| format!("Failed to add alias: {}", e))?;
+    }
+
+    let aliases = load_aliases(&mut *tx, &target_tag_id).await?;
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit transaction: {}", e))?;
+
+    Ok(TagMergeResult {
+        tag_id: target_tag_id,
+        transcript_links_moved,
+        file_links_moved,
+        aliases,
+    })
+}
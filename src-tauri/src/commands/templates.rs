@@ -1,6 +1,7 @@
 use crate::db::Database;
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
+use std::sync::Arc;
 use tauri::State;
 use uuid::Uuid;
 
@@ -36,7 +37,7 @@ pub struct UpdateTemplateRequest {
 }
 
 #[tauri::command]
-pub async fn get_all_templates(db: State<'_, Database>) -> Result<Vec<Template>, String> {
+pub async fn get_all_templates(db: State<'_, Arc<Database>>) -> Result<Vec<Template>, String> {
     let templates = sqlx::query_as::<_, Template>(
         r#"
         SELECT 
@@ -62,7 +63,7 @@ pub async fn get_all_templates(db: State<'_, Database>) -> Result<Vec<Template>,
 
 #[tauri::command]
 pub async fn get_template(
-    db: State<'_, Database>,
+    db: State<'_, Arc<Database>>,
     template_id: String,
 ) -> Result<Template, String> {
     let template = sqlx::query_as::<_, Template>(
@@ -91,7 +92,7 @@ pub async fn get_template(
 
 #[tauri::command]
 pub async fn create_template(
-    db: State<'_, Database>,
+    db: State<'_, Arc<Database>>,
     template_data: CreateTemplateRequest,
 ) -> Result<Template, String> {
     println!("[create_template] Received request: {:?}", template_data);
@@ -134,7 +135,7 @@ pub async fn create_template(
 
 #[tauri::command]
 pub async fn update_template(
-    db: State<'_, Database>,
+    db: State<'_, Arc<Database>>,
     template_id: String,
     template_data: UpdateTemplateRequest,
 ) -> Result<Template, String> {
@@ -177,7 +178,7 @@ pub async fn update_template(
 
 #[tauri::command]
 pub async fn delete_template(
-    db: State<'_, Database>,
+    db: State<'_, Arc<Database>>,
     template_id: String,
 ) -> Result<(), String> {
     // Check if template is default
@@ -0,0 +1,118 @@
+// Transcript export to caption and text formats
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+use tauri::State;
+use crate::commands::transcript_tracks::select_track;
+use crate::commands::transcripts::load_transcript_segments;
+use crate::db::Database;
+use crate::services::caption_export::{render_srt, render_txt, render_vtt, resegment, CaptionRules};
+use crate::services::transcript_parser::ParsedSegment;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum TranscriptExportFormat {
+    Srt,
+    Vtt,
+    Txt,
+    Json,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TranscriptExportRequest {
+    pub transcript_id: String,
+    pub format: TranscriptExportFormat,
+    pub output_path: String,
+    pub rules: Option<CaptionRules>, // Caption line rules, SRT/VTT only
+    pub include_speakers: Option<bool>,
+    pub language: Option<String>, // Export the recording's track in this language instead
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TranscriptExportResult {
+    pub transcript_id: String, // The track that was exported
+    pub output_path: String,
+    pub format: TranscriptExportFormat,
+    pub cue_count: usize,
+    pub bytes_written: usize,
+}
+
+#[derive(Debug, Serialize)]
+struct TranscriptJsonExport<'a> {
+    transcript_id: &'a str,
+    language: String,
+    word_count: i64,
+    segments: &'a [ParsedSegment],
+}
+
+#[tauri::command]
+pub async fn export_transcript(
+    database: State<'_, Arc<Database>>,
+    request: TranscriptExportRequest,
+) -> Result<TranscriptExportResult, String> {
+    let rules = request.rules.clone().unwrap_or_default();
+    rules.validate()?;
+    let include_speakers = request.include_speakers.unwrap_or(true);
+
+    let transcript_id = match &request.language {
+        Some(language) => select_track(&database.pool, &request.transcript_id, language)
+            .await?
+            .map(|track| track.transcript_id)
+            .ok_or_else(|| format!("This recording has no transcript in {}", language))?,
+        None => request.transcript_id.clone(),
+    };
+
+    let (language, word_count) = sqlx::query_as::<_, (String, Option<i64>)>(
+        "SELECT Language, WordCount FROM Transcript WHERE TranscriptID = ?1"
+    )
+    .bind(&transcript_id)
+    .fetch_optional(&database.pool)
+    .await
+    .map_err(|e| format!("Failed to fetch transcript: {}", e))?
+    .ok_or_else(|| "Transcript not found".to_string())?;
+
+    let segments = load_transcript_segments(&database.pool, &transcript_id)
+        .await
+        .map_err(|e| format!("Failed to fetch transcript segments: {}", e))?;
+
+    let (output, cue_count) = match request.format {
+        TranscriptExportFormat::Srt => {
+            let cues = resegment(&segments, &rules)?;
+            (render_srt(&cues, include_speakers), cues.len())
+        }
+        TranscriptExportFormat::Vtt => {
+            let cues = resegment(&segments, &rules)?;
+            (render_vtt(&cues, include_speakers), cues.len())
+        }
+        TranscriptExportFormat::Txt => (render_txt(&segments, include_speakers), segments.len()),
+        TranscriptExportFormat::Json => {
+            let export = TranscriptJsonExport {
+                transcript_id: &transcript_id,
+                language,
+                word_count: word_count.unwrap_or(0),
+                segments: &segments,
+            };
+            let json = serde_json::to_string_pretty(&export)
+                .map_err(|e| format!("Failed to serialize transcript: {}", e))?;
+            (json, segments.len())
+        }
+    };
+
+    let output_path = Path::new(&request.output_path);
+    if let Some(parent) = output_path.parent() {
+        if !parent.as_os_str().is_empty() && !parent.exists() {
+            return Err(format!("Export folder does not exist: {}", parent.display()));
+        }
+    }
+
+    fs::write(output_path, &output).map_err(|e| format!("Failed to write export file: {}", e))?;
+
+    Ok(TranscriptExportResult {
+        transcript_id,
+        output_path: request.output_path,
+        format: request.format,
+        cue_count,
+        bytes_written: output.len(),
+    })
+}
@@ -0,0 +1,340 @@
+// Linking transcripts to the video File records they describe
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::path::Path;
+use std::sync::Arc;
+use tauri::State;
+use crate::commands::export_watch::complete_projects;
+use crate::commands::files::VALID_VIDEO_EXTENSIONS;
+use crate::commands::transcript_tracks::{file_track, TrackKind};
+use crate::commands::transcripts::VALID_TRANSCRIPT_EXTENSIONS;
+use crate::db::Database;
+
+// Candidates at or above this score are linked automatically when they are the only one
+const AUTO_LINK_MIN_SCORE: f64 = 0.9;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinkCandidate {
+    pub file_id: String,
+    pub file_path: String,
+    pub original_name: String,
+    pub match_reason: String, // "filename_stem", "partial_stem" or "descript_project"
+    pub score: f64,
+    pub has_transcript: bool, // Already has a transcript in the same language
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TranscriptLinkResult {
+    pub transcript_id: String,
+    pub file_id: String,
+    pub match_reason: String,
+    pub placeholder_removed: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AutoLinkReport {
+    pub linked: Vec<TranscriptLinkResult>,
+    pub ambiguous: Vec<String>, // Transcript IDs with several equally good candidates
+    pub unmatched: Vec<String>,
+}
+
+/// Normalize a file name for matching: "Episode_12.en.srt" and "episode 12.mp4" both become "episode 12"
+pub(crate) fn normalize_stem(file_name: &str) -> String {
+    let mut stem = file_name.to_lowercase();
+
+    // Strip known extensions and language suffixes, possibly stacked ("clip.mp4.en.srt")
+    loop {
+        let Some((rest, ext)) = stem.rsplit_once('.') else { break };
+        let is_extension = VALID_TRANSCRIPT_EXTENSIONS.contains(&ext) || VALID_VIDEO_EXTENSIONS.contains(&ext);
+        let is_language = (ext.len() == 2 || (ext.len() == 5 && ext.as_bytes()[2] == b'-'))
+            && ext.chars().all(|c| c.is_ascii_alphabetic() || c == '-');
+        if !is_extension && !is_language {
+            break;
+        }
+        stem = rest.to_string();
+    }
+
+    let words: Vec<&str> = stem
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect();
+
+    // Drop trailing export suffixes such as "transcript" or "captions"
+    let end = words
+        .iter()
+        .rposition(|w| !matches!(*w, "transcript" | "captions" | "subtitles" | "subs" | "export"))
+        .map_or(0, |i| i + 1);
+
+    words[..end].join(" ")
+}
+
+// A File row created by transcript import rather than a real media file
+pub(crate) fn is_placeholder_path(file_path: &str) -> bool {
+    Path::new(file_path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map_or(false, |ext| VALID_TRANSCRIPT_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+}
+
+pub(crate) fn is_video_path(file_path: &str) -> bool {
+    Path::new(file_path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map_or(false, |ext| VALID_VIDEO_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+}
+
+/// Video files that could belong to a transcript with the given file name and language, best first
+pub(crate) async fn find_video_candidates(
+    pool: &SqlitePool,
+    transcript_name: &str,
+    language: &str,
+) -> Result<Vec<LinkCandidate>, sqlx::Error> {
+    let stem = normalize_stem(transcript_name);
+    if stem.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let files = sqlx::query_as::<_, (String, String, String, bool)>(
+        r#"
+        SELECT f.FileID, f.FilePath, f.OriginalName,
+               EXISTS(SELECT 1 FROM Transcript t WHERE t.FileID = f.FileID AND t.Language = ?1)
+        FROM File f
+        "#
+    )
+    .bind(language)
+    .fetch_all(pool)
+    .await?;
+
+    let mut candidates: Vec<LinkCandidate> = files
+        .into_iter()
+        .filter(|(_, file_path, _, _)| is_video_path(file_path))
+        .filter_map(|(file_id, file_path, original_name, has_transcript)| {
+            let video_stem = normalize_stem(&original_name);
+            let (match_reason, score) = if video_stem == stem {
+                ("filename_stem", 1.0)
+            } else if !video_stem.is_empty() && (video_stem.contains(&stem) || stem.contains(&video_stem)) {
+                ("partial_stem", 0.6)
+            } else {
+                return None;
+            };
+
+            Some(LinkCandidate {
+                file_id,
+                file_path,
+                original_name,
+                match_reason: match_reason.to_string(),
+                score,
+                has_transcript,
+            })
+        })
+        .collect();
+
+    // Descript exports are named after the project, so match project names against the stem
+    let project_files = sqlx::query_as::<_, (String, String, String, String, bool)>(
+        r#"
+        SELECT dp.ProjectName, f.FileID, f.FilePath, f.OriginalName,
+               EXISTS(SELECT 1 FROM Transcript t WHERE t.FileID = f.FileID AND t.Language = ?1)
+        FROM DescriptProject dp
+        JOIN ProjectFiles pf ON pf.ProjectID = dp.ProjectID
+        JOIN File f ON f.FileID = pf.FileID
+        "#
+    )
+    .bind(language)
+    .fetch_all(pool)
+    .await?;
+
+    let project_matches: Vec<_> = project_files
+        .into_iter()
+        .filter(|(project_name, _, file_path, _, _)| normalize_stem(project_name) == stem && is_video_path(file_path))
+        .collect();
+    let project_score = if project_matches.len() == 1 { 0.95 } else { 0.5 };
+
+    for (_, file_id, file_path, original_name, has_transcript) in project_matches {
+        match candidates.iter_mut().find(|c| c.file_id == file_id) {
+            Some(existing) if existing.score >= project_score => {}
+            Some(existing) => {
+                existing.match_reason = "descript_project".to_string();
+                existing.score = project_score;
+            }
+            None => candidates.push(LinkCandidate {
+                file_id,
+                file_path,
+                original_name,
+                match_reason: "descript_project".to_string(),
+                score: project_score,
+                has_transcript,
+            }),
+        }
+    }
+
+    candidates.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+    Ok(candidates)
+}
+
+/// The single confident, still-untranscribed candidate, if there is exactly one
+pub(crate) fn pick_auto_link(candidates: &[LinkCandidate]) -> Option<&LinkCandidate> {
+    let mut confident = candidates
+        .iter()
+        .filter(|c| c.score >= AUTO_LINK_MIN_SCORE && !c.has_transcript);
+    let first = confident.next()?;
+    if confident.next().is_some() {
+        return None;
+    }
+    Some(first)
+}
+
+/// Re-point a transcript at a video file and delete the placeholder File row it used before, unless
+/// other tracks still use it. An original linked to a video that already has one in another
+/// language becomes a translation of it.
+pub(crate) async fn link_transcript(
+    pool: &SqlitePool,
+    transcript_id: &str,
+    file_id: &str,
+) -> Result<bool, String> {
+    let mut tx = pool.begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    let (old_file_id, old_file_path, language, track_kind) = sqlx::query_as::<_, (String, String, String, String)>(
+        "SELECT t.FileID, f.FilePath, t.Language, t.TrackKind FROM Transcript t JOIN File f ON t.FileID = f.FileID WHERE t.TranscriptID = ?1"
+    )
+    .bind(transcript_id)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|e| format!("Failed to fetch transcript: {}", e))?
+    .ok_or_else(|| "Transcript not found".to_string())?;
+
+    if old_file_id == file_id {
+        return Ok(false);
+    }
+
+    let (target_path,) = sqlx::query_as::<_, (String,)>("SELECT FilePath FROM File WHERE FileID = ?1")
+        .bind(file_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to fetch file: {}", e))?
+        .ok_or_else(|| "File not found".to_string())?;
+
+    if is_placeholder_path(&target_path) {
+        return Err("Transcripts can only be linked to media files".to_string());
+    }
+
+    // Translations and cleaned tracks keep their kind; originals may become translations
+    let requested = TrackKind::parse(&track_kind).filter(|kind| *kind != TrackKind::Original);
+    let (kind, source) = file_track(&mut tx, file_id, &language, requested).await?;
+
+    sqlx::query(
+        "UPDATE Transcript SET FileID = ?1, TrackKind = ?2, SourceTranscriptID = COALESCE(?3, SourceTranscriptID) WHERE TranscriptID = ?4"
+    )
+    .bind(file_id)
+    .bind(kind.as_str())
+    .bind(&source)
+    .bind(transcript_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| format!("Failed to link transcript: {}", e))?;
+
+    let (other_tracks,) = sqlx::query_as::<_, (bool,)>("SELECT EXISTS(SELECT 1 FROM Transcript WHERE FileID = ?1)")
+        .bind(&old_file_id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to check placeholder file: {}", e))?;
+
+    let placeholder_removed = is_placeholder_path(&old_file_path) && !other_tracks;
+    if placeholder_removed {
+        sqlx::query("DELETE FROM File WHERE FileID = ?1")
+            .bind(&old_file_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| format!("Failed to remove placeholder file: {}", e))?;
+    }
+
+    // Linking the last untranscribed file of a Descript project completes it
+    complete_projects(&mut *tx, file_id).await?;
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit transaction: {}", e))?;
+
+    Ok(placeholder_removed)
+}
+
+#[tauri::command]
+pub async fn suggest_transcript_links(
+    database: State<'_, Arc<Database>>,
+    transcript_id: String,
+) -> Result<Vec<LinkCandidate>, String> {
+    let (original_name, language) = sqlx::query_as::<_, (String, String)>(
+        "SELECT f.OriginalName, t.Language FROM Transcript t JOIN File f ON t.FileID = f.FileID WHERE t.TranscriptID = ?1"
+    )
+    .bind(&transcript_id)
+    .fetch_optional(&database.pool)
+    .await
+    .map_err(|e| format!("Failed to fetch transcript: {}", e))?
+    .ok_or_else(|| "Transcript not found".to_string())?;
+
+    find_video_candidates(&database.pool, &original_name, &language)
+        .await
+        .map_err(|e| format!("Failed to find matching files: {}", e))
+}
+
+#[tauri::command]
+pub async fn link_transcript_to_file(
+    database: State<'_, Arc<Database>>,
+    transcript_id: String,
+    file_id: String,
+) -> Result<TranscriptLinkResult, String> {
+    let placeholder_removed = link_transcript(&database.pool, &transcript_id, &file_id).await?;
+
+    Ok(TranscriptLinkResult {
+        transcript_id,
+        file_id,
+        match_reason: "user_choice".to_string(),
+        placeholder_removed,
+    })
+}
+
+// Link every transcript still attached to a placeholder file, where the match is unambiguous
+#[tauri::command]
+pub async fn auto_link_transcripts(
+    database: State<'_, Arc<Database>>,
+) -> Result<AutoLinkReport, String> {
+    let transcripts = sqlx::query_as::<_, (String, String, String, String)>(
+        "SELECT t.TranscriptID, f.FilePath, f.OriginalName, t.Language FROM Transcript t JOIN File f ON t.FileID = f.FileID"
+    )
+    .fetch_all(&database.pool)
+    .await
+    .map_err(|e| format!("Failed to fetch transcripts: {}", e))?;
+
+    let mut report = AutoLinkReport {
+        linked: Vec::new(),
+        ambiguous: Vec::new(),
+        unmatched: Vec::new(),
+    };
+
+    for (transcript_id, file_path, original_name, language) in transcripts {
+        if !is_placeholder_path(&file_path) {
+            continue;
+        }
+
+        let candidates = find_video_candidates(&database.pool, &original_name, &language)
+            .await
+            .map_err(|e| format!("Failed to find matching files: {}", e))?;
+
+        match pick_auto_link(&candidates) {
+            Some(candidate) => {
+                let placeholder_removed = link_transcript(&database.pool, &transcript_id, &candidate.file_id).await?;
+                report.linked.push(TranscriptLinkResult {
+                    transcript_id,
+                    file_id: candidate.file_id.clone(),
+                    match_reason: candidate.match_reason.clone(),
+                    placeholder_removed,
+                });
+            }
+            None if candidates.iter().any(|c| !c.has_transcript) => report.ambiguous.push(transcript_id),
+            None => report.unmatched.push(transcript_id),
+        }
+    }
+
+    Ok(report)
+}
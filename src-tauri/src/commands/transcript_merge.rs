@@ -0,0 +1,581 @@
+// Merging multi-part recordings into one transcript and splitting a transcript in two
+use serde::{Deserialize, Serialize};
+use sqlx::{Sqlite, Transaction};
+use std::path::Path;
+use std::sync::Arc;
+use tauri::State;
+use uuid::Uuid;
+use crate::commands::transcript_links::is_video_path;
+use crate::commands::transcript_revisions::{apply_edit, TranscriptRevisionInfo, TranscriptSnapshot};
+use crate::commands::transcript_tracks::{file_track, TrackKind};
+use crate::commands::transcripts::{
+    store_transcript_segments, DEFAULT_KEY_POINTS, DEFAULT_SUMMARY_SENTENCES, VALID_TRANSCRIPT_EXTENSIONS,
+};
+use crate::db::Database;
+use crate::services::content_metrics::{compute_metrics, score};
+use crate::services::summarizer::summarize;
+use crate::services::transcript_parser::{is_generic_speaker_label, ParsedSegment, ParsedTranscript};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MergeTranscriptsRequest {
+    pub transcript_ids: Vec<String>, // In playback order
+    pub file_id: Option<String>, // Video of the full recording to link to; a placeholder file otherwise
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MergedPart {
+    pub transcript_id: String,
+    pub offset: f64, // Seconds added to the part's timestamps
+    pub segment_count: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MergeTranscriptsResult {
+    pub transcript_id: String,
+    pub file_id: String,
+    pub parts: Vec<MergedPart>,
+    pub word_count: usize,
+    pub segment_count: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SplitTranscriptRequest {
+    pub transcript_id: String,
+    pub at_time: Option<f64>, // Seconds; the second part starts at the first segment starting at or after it
+    pub at_segment: Option<usize>, // Position of the first segment of the second part
+    pub rebase_times: Option<bool>, // Shift the second part to start at 0, default true
+    pub author: String, // Recorded on the revision that shortens the original
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SplitTranscriptResult {
+    pub first_transcript_id: String, // The original transcript, now holding the first part
+    pub second_transcript_id: String,
+    pub second_file_id: String,
+    pub split_time: Option<f64>, // Start of the second part in the original timeline
+    pub first_segment_count: usize,
+    pub second_segment_count: usize,
+    pub revision: TranscriptRevisionInfo,
+}
+
+// What a derived transcript inherits from its source
+pub(crate) struct SourceTranscript {
+    pub(crate) transcript_id: String,
+    pub(crate) file_id: String,
+    file_path: String,
+    original_name: String,
+    duration: Option<f64>,
+    pub(crate) language: String,
+    language_confidence: Option<f64>,
+    language_source: Option<String>,
+    source_encoding: Option<String>,
+    pub(crate) segments: Vec<ParsedSegment>, // Speakers identified by label
+    pub(crate) display_names: Vec<(String, String)>, // Speaker label and display name
+}
+
+pub(crate) async fn load_source(tx: &mut Transaction<'_, Sqlite>, transcript_id: &str) -> Result<SourceTranscript, String> {
+    let (file_id, file_path, original_name, duration, language, language_confidence, language_source, source_encoding, content) =
+        sqlx::query_as::<_, (String, String, String, Option<f64>, String, Option<f64>, Option<String>, Option<String>, String)>(
+            r#"
+            SELECT f.FileID, f.FilePath, f.OriginalName, f.Duration, t.Language, t.LanguageConfidence, t.LanguageSource,
+                   t.SourceEncoding, t.Content
+            FROM Transcript t
+            JOIN File f ON t.FileID = f.FileID
+            WHERE t.TranscriptID = ?1
+            "#
+        )
+        .bind(transcript_id)
+        .fetch_optional(&mut **tx)
+        .await
+        .map_err(|e| format!("Failed to fetch transcript: {}", e))?
+        .ok_or_else(|| format!("Transcript not found: {}", transcript_id))?;
+
+    let rows = sqlx::query_as::<_, (Option<f64>, Option<f64>, Option<String>, String, Option<String>)>(
+        r#"
+        SELECT seg.StartTime, seg.EndTime, s.Label, seg.Text, seg.Words
+        FROM TranscriptSegment seg
+        LEFT JOIN Speaker s ON seg.SpeakerID = s.SpeakerID
+        WHERE seg.TranscriptID = ?1
+        ORDER BY seg.SegmentIndex
+        "#
+    )
+    .bind(transcript_id)
+    .fetch_all(&mut **tx)
+    .await
+    .map_err(|e| format!("Failed to fetch transcript segments: {}", e))?;
+
+    // Transcripts imported before segments were stored are treated as one untimed segment
+    let segments = if rows.is_empty() {
+        vec![ParsedSegment {
+            start_time: None,
+            end_time: None,
+            speaker: None,
+            text: content.split_whitespace().collect::<Vec<_>>().join(" "),
+            words: Vec::new(),
+        }]
+    } else {
+        rows.into_iter()
+            .map(|(start_time, end_time, speaker, text, words)| ParsedSegment {
+                start_time,
+                end_time,
+                speaker,
+                text,
+                words: words
+                    .and_then(|json| serde_json::from_str(&json).ok())
+                    .unwrap_or_default(),
+            })
+            .collect()
+    };
+
+    let display_names = sqlx::query_as::<_, (String, String)>(
+        "SELECT Label, DisplayName FROM Speaker WHERE TranscriptID = ?1"
+    )
+    .bind(transcript_id)
+    .fetch_all(&mut **tx)
+    .await
+    .map_err(|e| format!("Failed to fetch speakers: {}", e))?;
+
+    Ok(SourceTranscript {
+        transcript_id: transcript_id.to_string(),
+        file_id,
+        file_path,
+        original_name,
+        duration,
+        language,
+        language_confidence,
+        language_source,
+        source_encoding,
+        segments,
+        display_names,
+    })
+}
+
+// Length of a part's timeline: the video's duration, or the latest time any segment reaches.
+// None when the part has neither, so its length is unknown.
+fn part_duration(source: &SourceTranscript) -> Option<f64> {
+    let video_duration = source.duration.filter(|_| is_video_path(&source.file_path));
+    video_duration.or_else(|| {
+        source
+            .segments
+            .iter()
+            .filter_map(|s| s.end_time.or(s.start_time))
+            .reduce(f64::max)
+    })
+}
+
+// Offset of each part on the merged timeline, and the timeline's total length. Parts must share
+// a language, and a part of unknown length can't come before a timed part, since everything
+// after it would be placed too early.
+fn merge_timeline(sources: &[SourceTranscript]) -> Result<(Vec<f64>, f64), String> {
+    if let Some(other) = sources.iter().find(|s| s.language != sources[0].language) {
+        return Err(format!(
+            "Transcripts in different languages can't be merged: {} is '{}' but {} is '{}'",
+            sources[0].original_name, sources[0].language, other.original_name, other.language
+        ));
+    }
+
+    let mut offsets = Vec::new();
+    let mut offset = 0.0;
+    for (part, source) in sources.iter().enumerate() {
+        offsets.push(offset);
+        match part_duration(source) {
+            Some(duration) => offset += duration,
+            None => {
+                let later_timed = sources[part + 1..]
+                    .iter()
+                    .any(|s| s.segments.iter().any(|seg| seg.start_time.is_some() || seg.end_time.is_some()));
+                if later_timed {
+                    return Err(format!(
+                        "Part {} ({}) has no timestamps and no video duration, so the parts after it can't be placed on the merged timeline",
+                        part + 1,
+                        source.original_name
+                    ));
+                }
+            }
+        }
+    }
+
+    Ok((offsets, offset))
+}
+
+fn shift_segment(segment: &ParsedSegment, offset: f64) -> ParsedSegment {
+    let mut shifted = segment.clone();
+    shifted.start_time = segment.start_time.map(|t| (t + offset).max(0.0));
+    shifted.end_time = segment.end_time.map(|t| (t + offset).max(0.0));
+    for word in &mut shifted.words {
+        word.start_time = word.start_time.map(|t| (t + offset).max(0.0));
+        word.end_time = word.end_time.map(|t| (t + offset).max(0.0));
+    }
+    shifted
+}
+
+// Placeholder File row for a derived transcript, named after the source ("talk (part 2).srt")
+async fn insert_placeholder_file(
+    tx: &mut Transaction<'_, Sqlite>,
+    source: &SourceTranscript,
+    suffix: &str,
+    duration: Option<f64>,
+    size: usize,
+) -> Result<String, String> {
+    let path = Path::new(&source.file_path);
+    let stem = Path::new(&source.original_name)
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("transcript");
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+        .filter(|e| VALID_TRANSCRIPT_EXTENSIONS.contains(&e.as_str()))
+        .unwrap_or_else(|| "txt".to_string());
+    let name = format!("{} ({}).{}", stem, suffix, extension);
+    let file_path = path.with_file_name(&name).to_string_lossy().to_string();
+
+    let file_id = Uuid::new_v4().to_string();
+    sqlx::query(
+        "INSERT INTO File (FileID, FilePath, OriginalName, FileSize, Duration, Status, ImportedAt) VALUES (?1, ?2, ?3, ?4, ?5, 'Imported', datetime('now'))"
+    )
+    .bind(&file_id)
+    .bind(&file_path)
+    .bind(&name)
+    .bind(size as i64)
+    .bind(duration)
+    .execute(&mut **tx)
+    .await
+    .map_err(|e| format!("Failed to create file record: {}", e))?;
+
+    Ok(file_id)
+}
+
+// Create a transcript from segments, with the summary and content score an import would get,
+// speaker display names carried over and the source transcripts' tags linked. `track` is the
+// new transcript's kind and the track it derives from.
+pub(crate) async fn insert_derived_transcript(
+    tx: &mut Transaction<'_, Sqlite>,
+    file_id: &str,
+    source: &SourceTranscript,
+    parsed: &ParsedTranscript,
+    display_names: &[(String, String)],
+    tag_sources: &[String],
+    track: (TrackKind, Option<&str>),
+) -> Result<String, String> {
+    let (track_kind, source_transcript_id) = track;
+    let transcript_id = Uuid::new_v4().to_string();
+    let content = parsed.plain_text();
+
+    let extractive = summarize(&parsed.segments, &source.language, DEFAULT_SUMMARY_SENTENCES, DEFAULT_KEY_POINTS);
+    let (summary, key_points_json) = match extractive {
+        Some(extractive) => {
+            let json = serde_json::to_string(&extractive.key_points)
+                .map_err(|e| format!("Failed to serialize key points: {}", e))?;
+            (Some(extractive.summary), Some(json))
+        }
+        None => (None, None),
+    };
+    let breakdown = score(compute_metrics(&parsed.segments, &source.language));
+    let breakdown_json = breakdown
+        .as_ref()
+        .map(serde_json::to_string)
+        .transpose()
+        .map_err(|e| format!("Failed to serialize score breakdown: {}", e))?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO Transcript (
+            TranscriptID, FileID, Content, WordCount, Language, LanguageConfidence, LanguageSource, SourceEncoding,
+            Summary, KeyPoints, SummarySource, ContentScore, ScoreBreakdown, ScoreSource, TrackKind, SourceTranscriptID,
+            ImportedAt
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, datetime('now'))
+        "#
+    )
+    .bind(&transcript_id)
+    .bind(file_id)
+    .bind(&content)
+    .bind(parsed.word_count() as i64)
+    .bind(&source.language)
+    .bind(source.language_confidence)
+    .bind(&source.language_source)
+    .bind(&source.source_encoding)
+    .bind(&summary)
+    .bind(&key_points_json)
+    .bind(summary.as_ref().map(|_| "extractive"))
+    .bind(breakdown.as_ref().map(|b| b.score))
+    .bind(&breakdown_json)
+    .bind(breakdown.as_ref().map(|_| "metrics"))
+    .bind(track_kind.as_str())
+    .bind(source_transcript_id)
+    .execute(&mut **tx)
+    .await
+    .map_err(|e| format!("Failed to create transcript: {}", e))?;
+
+    store_transcript_segments(tx, &transcript_id, parsed)
+        .await
+        .map_err(|e| format!("Failed to store transcript segments: {}", e))?;
+
+    for (label, display_name) in display_names {
+        sqlx::query("UPDATE Speaker SET DisplayName = ?1 WHERE TranscriptID = ?2 AND Label = ?3")
+            .bind(display_name)
+            .bind(&transcript_id)
+            .bind(label)
+            .execute(&mut **tx)
+            .await
+            .map_err(|e| format!("Failed to update speaker: {}", e))?;
+    }
+
+    // Tags from every source, keeping the highest relevance
+    let placeholders = vec!["?"; tag_sources.len()].join(", ");
+    let sql = format!(
+        "INSERT INTO TranscriptTags (TranscriptID, TagID, Relevance) SELECT ?, TagID, MAX(Relevance) FROM TranscriptTags WHERE TranscriptID IN ({}) GROUP BY TagID",
+        placeholders
+    );
+    let mut query = sqlx::query(&sql).bind(&transcript_id);
+    for source_id in tag_sources {
+        query = query.bind(source_id);
+    }
+    query
+        .execute(&mut **tx)
+        .await
+        .map_err(|e| format!("Failed to copy tags: {}", e))?;
+
+    Ok(transcript_id)
+}
+
+// Combine the parts of a recording into a new transcript. Each part's timestamps are offset by
+// the durations of the parts before it; speakers with the same display name become one speaker,
+// and unnamed anonymous IDs are labelled with their part. Parts must be in the same language.
+// The source transcripts are kept.
+#[tauri::command]
+pub async fn merge_transcripts(
+    database: State<'_, Arc<Database>>,
+    request: MergeTranscriptsRequest,
+) -> Result<MergeTranscriptsResult, String> {
+    if request.transcript_ids.len() < 2 {
+        return Err("Select at least two transcripts to merge".to_string());
+    }
+    for (i, id) in request.transcript_ids.iter().enumerate() {
+        if request.transcript_ids[..i].contains(id) {
+            return Err(format!("Transcript listed more than once: {}", id));
+        }
+    }
+
+    let mut tx = database.pool.begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    let mut sources = Vec::new();
+    for transcript_id in &request.transcript_ids {
+        sources.push(load_source(&mut tx, transcript_id).await?);
+    }
+
+    let (offsets, total_duration) = merge_timeline(&sources)?;
+
+    let mut segments = Vec::new();
+    let mut parts = Vec::new();
+    for (part, (source, &offset)) in sources.iter().zip(&offsets).enumerate() {
+        for segment in &source.segments {
+            // Speakers are matched across parts by the name the user gave them. Anonymous IDs
+            // that were never renamed are kept apart, since "SPEAKER_00" in one part needn't be
+            // "SPEAKER_00" in the next.
+            let mut shifted = shift_segment(segment, offset);
+            shifted.speaker = segment.speaker.as_ref().map(|label| {
+                match source.display_names.iter().find(|(l, _)| l == label) {
+                    Some((_, name)) if name != label => name.clone(),
+                    _ if is_generic_speaker_label(label) => format!("{} (part {})", label, part + 1),
+                    _ => label.clone(),
+                }
+            });
+            segments.push(shifted);
+        }
+        parts.push(MergedPart {
+            transcript_id: source.transcript_id.clone(),
+            offset,
+            segment_count: source.segments.len(),
+        });
+    }
+    let parsed = ParsedTranscript { segments, language: None };
+
+    let (file_id, track_kind, track_source) = match &request.file_id {
+        Some(file_id) => {
+            let (exists,) = sqlx::query_as::<_, (bool,)>("SELECT EXISTS(SELECT 1 FROM File WHERE FileID = ?1)")
+                .bind(file_id)
+                .fetch_one(&mut *tx)
+                .await
+                .map_err(|e| format!("Failed to check file: {}", e))?;
+            if !exists {
+                return Err("File not found".to_string());
+            }
+            // The file may already have tracks in other languages
+            let (kind, source) = file_track(&mut tx, file_id, &sources[0].language, None).await?;
+            (file_id.clone(), kind, source)
+        }
+        None => {
+            let content_size = parsed.plain_text().len();
+            let file_id = insert_placeholder_file(&mut tx, &sources[0], "merged", Some(total_duration), content_size).await?;
+            (file_id, TrackKind::Original, None)
+        }
+    };
+
+    let transcript_id = insert_derived_transcript(
+        &mut tx,
+        &file_id,
+        &sources[0],
+        &parsed,
+        &[],
+        &request.transcript_ids,
+        (track_kind, track_source.as_deref()),
+    )
+    .await?;
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit transaction: {}", e))?;
+
+    Ok(MergeTranscriptsResult {
+        transcript_id,
+        file_id,
+        parts,
+        word_count: parsed.word_count(),
+        segment_count: parsed.segments.len(),
+    })
+}
+
+// Split a transcript in two. The original keeps the first part (recorded as a revision); the
+// second part becomes a new transcript on a placeholder file with the same speakers and tags.
+#[tauri::command]
+pub async fn split_transcript(
+    database: State<'_, Arc<Database>>,
+    request: SplitTranscriptRequest,
+) -> Result<SplitTranscriptResult, String> {
+    let rebase_times = request.rebase_times.unwrap_or(true);
+
+    let mut tx = database.pool.begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    let source = load_source(&mut tx, &request.transcript_id).await?;
+
+    let split_at = match (request.at_segment, request.at_time) {
+        (Some(segment), _) => segment,
+        (None, Some(time)) => source
+            .segments
+            .iter()
+            .position(|s| s.start_time.is_some_and(|start| start >= time))
+            .ok_or_else(|| format!("No segment starts at or after {} seconds", time))?,
+        (None, None) => return Err("Give a timestamp or segment to split at".to_string()),
+    };
+    if split_at == 0 || split_at >= source.segments.len() {
+        return Err("Both parts of the split must contain at least one segment".to_string());
+    }
+
+    let split_time = source.segments[split_at].start_time;
+    let offset = if rebase_times { -split_time.unwrap_or(0.0) } else { 0.0 };
+    let second = ParsedTranscript {
+        segments: source.segments[split_at..].iter().map(|s| shift_segment(s, offset)).collect(),
+        language: None,
+    };
+
+    let first_segments = source.segments[..split_at].to_vec();
+    let summary = format!("Split at segment {}; the rest moved to a new transcript", split_at);
+    let edit = apply_edit(
+        &mut tx,
+        &request.transcript_id,
+        |_| {
+            let parsed = ParsedTranscript { segments: first_segments, language: None };
+            Ok(TranscriptSnapshot {
+                content: parsed.plain_text(),
+                segments: parsed.segments,
+            })
+        },
+        &request.author,
+        Some(&summary),
+    )
+    .await?;
+
+    let second_duration = second.segments.iter().filter_map(|s| s.end_time).reduce(f64::max);
+    let second_file_id =
+        insert_placeholder_file(&mut tx, &source, "part 2", second_duration, second.plain_text().len()).await?;
+    let second_transcript_id = insert_derived_transcript(
+        &mut tx,
+        &second_file_id,
+        &source,
+        &second,
+        &source.display_names,
+        std::slice::from_ref(&request.transcript_id),
+        (TrackKind::Original, None),
+    )
+    .await?;
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit transaction: {}", e))?;
+
+    Ok(SplitTranscriptResult {
+        first_transcript_id: request.transcript_id,
+        second_transcript_id,
+        second_file_id,
+        split_time,
+        first_segment_count: edit.segment_count,
+        second_segment_count: second.segments.len(),
+        revision: edit.revision,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn part(name: &str, language: &str, video_duration: Option<f64>, times: &[(Option<f64>, Option<f64>)]) -> SourceTranscript {
+        SourceTranscript {
+            transcript_id: name.to_string(),
+            file_id: name.to_string(),
+            file_path: format!("/media/{}", name),
+            original_name: name.to_string(),
+            duration: video_duration,
+            language: language.to_string(),
+            language_confidence: None,
+            language_source: None,
+            source_encoding: None,
+            segments: times
+                .iter()
+                .map(|&(start_time, end_time)| ParsedSegment { start_time, end_time, speaker: None, text: "Hello".to_string(), words: Vec::new() })
+                .collect(),
+            display_names: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn offsets_parts_by_video_or_segment_duration() {
+        let sources = vec![
+            part("one.mp4", "en", Some(90.0), &[(Some(0.0), Some(30.0))]),
+            part("two.srt", "en", Some(500.0), &[(Some(0.0), Some(40.0)), (Some(41.0), None)]),
+            part("three.txt", "en", None, &[(Some(5.0), None)]),
+            part("four.txt", "en", None, &[(None, None)]),
+        ];
+        // The .srt's stored duration isn't a video's, and a start time counts when there's no end
+        assert_eq!(merge_timeline(&sources), Ok((vec![0.0, 90.0, 131.0, 136.0], 136.0)));
+    }
+
+    #[test]
+    fn rejects_parts_of_unknown_length_before_timed_parts() {
+        let sources = vec![
+            part("intro.txt", "en", None, &[(None, None)]),
+            part("talk.srt", "en", None, &[(Some(0.0), Some(60.0))]),
+        ];
+        let error = merge_timeline(&sources).unwrap_err();
+        assert!(error.contains("Part 1 (intro.txt)"), "{}", error);
+
+        let untimed = vec![part("a.txt", "en", None, &[(None, None)]), part("b.txt", "en", None, &[(None, None)])];
+        assert_eq!(merge_timeline(&untimed), Ok((vec![0.0, 0.0], 0.0)));
+    }
+
+    #[test]
+    fn rejects_parts_in_different_languages() {
+        let sources = vec![
+            part("one.srt", "en", None, &[(Some(0.0), Some(10.0))]),
+            part("two.srt", "en", None, &[(Some(0.0), Some(10.0))]),
+            part("drei.srt", "de", None, &[(Some(0.0), Some(10.0))]),
+        ];
+        let error = merge_timeline(&sources).unwrap_err();
+        assert!(error.contains("drei.srt is 'de'"), "{}", error);
+    }
+}
@@ -0,0 +1,524 @@
+// Transcript editing with revision history: full-text and segment edits, diffs and restores
+use serde::{Deserialize, Serialize};
+use sqlx::{Sqlite, Transaction};
+use std::sync::Arc;
+use tauri::State;
+use uuid::Uuid;
+use crate::commands::redaction::{scan_target, RedactionTarget};
+use crate::commands::transcripts::{store_transcript_segments, DEFAULT_KEY_POINTS, DEFAULT_SUMMARY_SENTENCES};
+use crate::db::Database;
+use crate::services::content_metrics::{compute_metrics, score};
+use crate::services::summarizer::summarize;
+use crate::services::text_encoding::clean_text;
+use crate::services::transcript_edit::{apply_text_edit, diff_words, with_text, TextChange};
+use crate::services::transcript_parser::{ParsedSegment, ParsedTranscript};
+
+// Author recorded on the baseline revision created before a transcript's first edit
+const IMPORT_AUTHOR: &str = "import";
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateTranscriptRequest {
+    pub transcript_id: String,
+    pub content: String,
+    pub author: String,
+    pub summary: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateSegmentRequest {
+    pub transcript_id: String,
+    pub segment_index: i64,
+    pub text: String,
+    pub author: String,
+    pub summary: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RestoreRevisionRequest {
+    pub transcript_id: String,
+    pub revision_number: i64,
+    pub author: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TranscriptRevisionInfo {
+    pub revision_id: String,
+    pub revision_number: i64,
+    pub author: String,
+    pub summary: Option<String>,
+    pub word_count: i64,
+    pub created_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TranscriptEditResult {
+    pub transcript_id: String,
+    pub revision: TranscriptRevisionInfo,
+    pub word_count: i64,
+    pub segment_count: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TranscriptRevisionDiff {
+    pub transcript_id: String,
+    pub from_revision: i64,
+    pub to_revision: i64,
+    pub words_added: usize,
+    pub words_removed: usize,
+    pub changes: Vec<TextChange>,
+}
+
+// Current text and segments of a transcript, with speakers identified by label
+pub(crate) struct TranscriptSnapshot {
+    pub(crate) content: String,
+    pub(crate) segments: Vec<ParsedSegment>,
+}
+
+impl TranscriptSnapshot {
+    fn word_count(&self) -> i64 {
+        self.segments.iter().map(|s| s.text.split_whitespace().count() as i64).sum()
+    }
+}
+
+async fn load_snapshot(
+    tx: &mut Transaction<'_, Sqlite>,
+    transcript_id: &str,
+) -> Result<TranscriptSnapshot, String> {
+    let (content,) = sqlx::query_as::<_, (String,)>("SELECT Content FROM Transcript WHERE TranscriptID = ?1")
+        .bind(transcript_id)
+        .fetch_optional(&mut **tx)
+        .await
+        .map_err(|e| format!("Failed to fetch transcript: {}", e))?
+        .ok_or_else(|| "Transcript not found".to_string())?;
+
+    let rows = sqlx::query_as::<_, (Option<f64>, Option<f64>, Option<String>, String, Option<String>)>(
+        r#"
+        SELECT seg.StartTime, seg.EndTime, s.Label, seg.Text, seg.Words
+        FROM TranscriptSegment seg
+        LEFT JOIN Speaker s ON seg.SpeakerID = s.SpeakerID
+        WHERE seg.TranscriptID = ?1
+        ORDER BY seg.SegmentIndex
+        "#
+    )
+    .bind(transcript_id)
+    .fetch_all(&mut **tx)
+    .await
+    .map_err(|e| format!("Failed to fetch transcript segments: {}", e))?;
+
+    // Transcripts imported before segments were stored are treated as one untimed segment
+    let segments = if rows.is_empty() {
+        vec![ParsedSegment {
+            start_time: None,
+            end_time: None,
+            speaker: None,
+            text: content.split_whitespace().collect::<Vec<_>>().join(" "),
+            words: Vec::new(),
+        }]
+    } else {
+        rows.into_iter()
+            .map(|(start_time, end_time, speaker, text, words)| ParsedSegment {
+                start_time,
+                end_time,
+                speaker,
+                text,
+                words: words
+                    .and_then(|json| serde_json::from_str(&json).ok())
+                    .unwrap_or_default(),
+            })
+            .collect()
+    };
+
+    Ok(TranscriptSnapshot { content, segments })
+}
+
+async fn insert_revision(
+    tx: &mut Transaction<'_, Sqlite>,
+    transcript_id: &str,
+    snapshot: &TranscriptSnapshot,
+    author: &str,
+    summary: Option<&str>,
+) -> Result<TranscriptRevisionInfo, String> {
+    let segments = serde_json::to_string(&snapshot.segments)
+        .map_err(|e| format!("Failed to serialize segments: {}", e))?;
+    let revision_id = Uuid::new_v4().to_string();
+
+    sqlx::query(
+        r#"
+        INSERT INTO TranscriptRevision (RevisionID, TranscriptID, RevisionNumber, Author, Summary, Content, Segments, WordCount)
+        SELECT ?1, ?2, COALESCE(MAX(RevisionNumber), 0) + 1, ?3, ?4, ?5, ?6, ?7
+        FROM TranscriptRevision WHERE TranscriptID = ?2
+        "#
+    )
+    .bind(&revision_id)
+    .bind(transcript_id)
+    .bind(author)
+    .bind(summary)
+    .bind(&snapshot.content)
+    .bind(&segments)
+    .bind(snapshot.word_count())
+    .execute(&mut **tx)
+    .await
+    .map_err(|e| format!("Failed to save revision: {}", e))?;
+
+    let (revision_number, created_at) = sqlx::query_as::<_, (i64, String)>(
+        "SELECT RevisionNumber, CreatedAt FROM TranscriptRevision WHERE RevisionID = ?1"
+    )
+    .bind(&revision_id)
+    .fetch_one(&mut **tx)
+    .await
+    .map_err(|e| format!("Failed to fetch revision: {}", e))?;
+
+    Ok(TranscriptRevisionInfo {
+        revision_id,
+        revision_number,
+        author: author.to_string(),
+        summary: summary.map(str::to_string),
+        word_count: snapshot.word_count(),
+        created_at,
+    })
+}
+
+// Record the imported state as revision 1 so the first edit can be diffed and undone
+async fn ensure_baseline_revision(
+    tx: &mut Transaction<'_, Sqlite>,
+    transcript_id: &str,
+    current: &TranscriptSnapshot,
+) -> Result<(), String> {
+    let (count,) = sqlx::query_as::<_, (i64,)>("SELECT COUNT(*) FROM TranscriptRevision WHERE TranscriptID = ?1")
+        .bind(transcript_id)
+        .fetch_one(&mut **tx)
+        .await
+        .map_err(|e| format!("Failed to count revisions: {}", e))?;
+
+    if count == 0 {
+        insert_revision(tx, transcript_id, current, IMPORT_AUTHOR, Some("Imported version")).await?;
+    }
+
+    Ok(())
+}
+
+// Write a new state to Transcript and TranscriptSegment, then record it as a revision.
+// Updating Content fires the FTS triggers, so the search index follows every edit.
+async fn save_edit(
+    database: &Database,
+    transcript_id: &str,
+    edit: impl FnOnce(&TranscriptSnapshot) -> Result<TranscriptSnapshot, String>,
+    author: &str,
+    summary: Option<&str>,
+) -> Result<TranscriptEditResult, String> {
+    let mut tx = database.pool.begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    let result = apply_edit(&mut tx, transcript_id, edit, author, summary).await?;
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit transaction: {}", e))?;
+
+    Ok(result)
+}
+
+// Recompute the summary and content score from the edited segments, drop chapters, embeddings
+// and related-transcript rankings so they are regenerated from the new text, and rescan a
+// transcript that was scanned for redactions so its hits and redacted copy match the new text
+async fn refresh_derived_data(
+    tx: &mut Transaction<'_, Sqlite>,
+    transcript_id: &str,
+    segments: &[ParsedSegment],
+) -> Result<(), String> {
+    let (language,) = sqlx::query_as::<_, (String,)>("SELECT Language FROM Transcript WHERE TranscriptID = ?1")
+        .bind(transcript_id)
+        .fetch_one(&mut **tx)
+        .await
+        .map_err(|e| format!("Failed to fetch transcript: {}", e))?;
+
+    let extractive = summarize(segments, &language, DEFAULT_SUMMARY_SENTENCES, DEFAULT_KEY_POINTS);
+    let key_points = extractive
+        .as_ref()
+        .map(|s| serde_json::to_string(&s.key_points))
+        .transpose()
+        .map_err(|e| format!("Failed to serialize key points: {}", e))?;
+    let breakdown = score(compute_metrics(segments, &language));
+    let breakdown_json = breakdown
+        .as_ref()
+        .map(serde_json::to_string)
+        .transpose()
+        .map_err(|e| format!("Failed to serialize score breakdown: {}", e))?;
+
+    sqlx::query(
+        r#"
+        UPDATE Transcript
+        SET Summary = ?1, KeyPoints = ?2, SummarySource = ?3,
+            ContentScore = ?4, ScoreBreakdown = ?5, ScoreSource = ?6
+        WHERE TranscriptID = ?7
+        "#
+    )
+    .bind(extractive.as_ref().map(|s| s.summary.clone()))
+    .bind(&key_points)
+    .bind(extractive.as_ref().map(|_| "extractive"))
+    .bind(breakdown.as_ref().map(|b| b.score))
+    .bind(&breakdown_json)
+    .bind(breakdown.as_ref().map(|_| "metrics"))
+    .bind(transcript_id)
+    .execute(&mut **tx)
+    .await
+    .map_err(|e| format!("Failed to update transcript analysis: {}", e))?;
+
+    for (sql, what) in [
+        ("DELETE FROM TranscriptChapter WHERE TranscriptID = ?1", "chapters"),
+        ("DELETE FROM TranscriptChunk WHERE TranscriptID = ?1", "embeddings"),
+        ("DELETE FROM TranscriptEmbeddingIndex WHERE TranscriptID = ?1", "embedding index"),
+        ("DELETE FROM RelatedTranscript WHERE TranscriptID = ?1 OR RelatedTranscriptID = ?1", "related transcripts"),
+        ("DELETE FROM RelatedTranscriptState WHERE TranscriptID = ?1", "related transcripts"),
+    ] {
+        sqlx::query(sql)
+            .bind(transcript_id)
+            .execute(&mut **tx)
+            .await
+            .map_err(|e| format!("Failed to clear {}: {}", what, e))?;
+    }
+
+    let (scanned,) = sqlx::query_as::<_, (bool,)>(
+        "SELECT RedactedContent IS NOT NULL OR EXISTS(SELECT 1 FROM RedactionHit WHERE TargetType = 'transcript' AND TargetID = ?1) FROM Transcript WHERE TranscriptID = ?1"
+    )
+    .bind(transcript_id)
+    .fetch_one(&mut **tx)
+    .await
+    .map_err(|e| format!("Failed to check redaction scan: {}", e))?;
+    if scanned {
+        scan_target(tx, RedactionTarget::Transcript, transcript_id).await?;
+    }
+
+    Ok(())
+}
+
+// The body of save_edit, for callers that make further changes in the same transaction
+pub(crate) async fn apply_edit(
+    tx: &mut Transaction<'_, Sqlite>,
+    transcript_id: &str,
+    edit: impl FnOnce(&TranscriptSnapshot) -> Result<TranscriptSnapshot, String>,
+    author: &str,
+    summary: Option<&str>,
+) -> Result<TranscriptEditResult, String> {
+    let author = author.trim();
+    if author.is_empty() {
+        return Err("Revision author is required".to_string());
+    }
+
+    let current = load_snapshot(tx, transcript_id).await?;
+    ensure_baseline_revision(tx, transcript_id, &current).await?;
+
+    let updated = edit(&current)?;
+    if updated.segments.is_empty() || updated.content.trim().is_empty() {
+        return Err("Transcript cannot be empty".to_string());
+    }
+
+    // The cleaned variant and filler statistics describe the old text until re-analyzed
+    sqlx::query(
+        "UPDATE Transcript SET Content = ?1, WordCount = ?2, CleanedContent = NULL, FillerDensity = NULL WHERE TranscriptID = ?3"
+    )
+    .bind(&updated.content)
+    .bind(updated.word_count())
+    .bind(transcript_id)
+    .execute(&mut **tx)
+    .await
+    .map_err(|e| format!("Failed to update transcript: {}", e))?;
+
+    sqlx::query("DELETE FROM SpeakerDisfluency WHERE TranscriptID = ?1")
+        .bind(transcript_id)
+        .execute(&mut **tx)
+        .await
+        .map_err(|e| format!("Failed to clear disfluency statistics: {}", e))?;
+
+    sqlx::query("DELETE FROM TranscriptSegment WHERE TranscriptID = ?1")
+        .bind(transcript_id)
+        .execute(&mut **tx)
+        .await
+        .map_err(|e| format!("Failed to clear transcript segments: {}", e))?;
+
+    let parsed = ParsedTranscript {
+        segments: updated.segments.clone(),
+        language: None,
+    };
+    store_transcript_segments(tx, transcript_id, &parsed)
+        .await
+        .map_err(|e| format!("Failed to store transcript segments: {}", e))?;
+
+    refresh_derived_data(tx, transcript_id, &updated.segments).await?;
+
+    let revision = insert_revision(tx, transcript_id, &updated, author, summary).await?;
+
+    Ok(TranscriptEditResult {
+        transcript_id: transcript_id.to_string(),
+        word_count: updated.word_count(),
+        segment_count: updated.segments.len(),
+        revision,
+    })
+}
+
+// Replace the whole transcript text; segment timings are kept for the words that survive the edit
+#[tauri::command]
+pub async fn update_transcript(
+    database: State<'_, Arc<Database>>,
+    request: UpdateTranscriptRequest,
+) -> Result<TranscriptEditResult, String> {
+    let content = clean_text(&request.content);
+
+    save_edit(
+        &database,
+        &request.transcript_id,
+        |current| {
+            Ok(TranscriptSnapshot {
+                segments: apply_text_edit(&current.segments, &current.content, &content),
+                content: content.clone(),
+            })
+        },
+        &request.author,
+        request.summary.as_deref(),
+    )
+    .await
+}
+
+#[tauri::command]
+pub async fn update_transcript_segment(
+    database: State<'_, Arc<Database>>,
+    request: UpdateSegmentRequest,
+) -> Result<TranscriptEditResult, String> {
+    let text = clean_text(&request.text);
+
+    save_edit(
+        &database,
+        &request.transcript_id,
+        |current| {
+            let index = usize::try_from(request.segment_index)
+                .ok()
+                .filter(|i| *i < current.segments.len())
+                .ok_or_else(|| format!("Segment {} not found", request.segment_index))?;
+
+            let mut segments = current.segments.clone();
+            if text.trim().is_empty() {
+                segments.remove(index);
+            } else {
+                segments[index] = with_text(&segments[index], &text);
+            }
+
+            // The stored text is rebuilt from the segments so the two never disagree
+            let parsed = ParsedTranscript { segments, language: None };
+            Ok(TranscriptSnapshot {
+                content: parsed.plain_text(),
+                segments: parsed.segments,
+            })
+        },
+        &request.author,
+        request.summary.as_deref(),
+    )
+    .await
+}
+
+#[tauri::command]
+pub async fn get_transcript_revisions(
+    database: State<'_, Arc<Database>>,
+    transcript_id: String,
+) -> Result<Vec<TranscriptRevisionInfo>, String> {
+    let rows = sqlx::query_as::<_, (String, i64, String, Option<String>, i64, String)>(
+        r#"
+        SELECT RevisionID, RevisionNumber, Author, Summary, WordCount, CreatedAt
+        FROM TranscriptRevision
+        WHERE TranscriptID = ?1
+        ORDER BY RevisionNumber DESC
+        "#
+    )
+    .bind(&transcript_id)
+    .fetch_all(&database.pool)
+    .await
+    .map_err(|e| format!("Failed to fetch revisions: {}", e))?;
+
+    Ok(rows
+        .into_iter()
+        .map(|(revision_id, revision_number, author, summary, word_count, created_at)| TranscriptRevisionInfo {
+            revision_id,
+            revision_number,
+            author,
+            summary,
+            word_count,
+            created_at,
+        })
+        .collect())
+}
+
+async fn revision_content(
+    database: &Database,
+    transcript_id: &str,
+    revision_number: i64,
+) -> Result<String, String> {
+    sqlx::query_as::<_, (String,)>(
+        "SELECT Content FROM TranscriptRevision WHERE TranscriptID = ?1 AND RevisionNumber = ?2"
+    )
+    .bind(transcript_id)
+    .bind(revision_number)
+    .fetch_optional(&database.pool)
+    .await
+    .map_err(|e| format!("Failed to fetch revision: {}", e))?
+    .map(|(content,)| content)
+    .ok_or_else(|| format!("Revision {} not found", revision_number))
+}
+
+#[tauri::command]
+pub async fn diff_transcript_revisions(
+    database: State<'_, Arc<Database>>,
+    transcript_id: String,
+    from_revision: i64,
+    to_revision: i64,
+) -> Result<TranscriptRevisionDiff, String> {
+    let old = revision_content(&database, &transcript_id, from_revision).await?;
+    let new = revision_content(&database, &transcript_id, to_revision).await?;
+
+    let changes = diff_words(&old, &new);
+    let count = |kind: &str| -> usize {
+        changes
+            .iter()
+            .filter(|c| c.kind == kind)
+            .map(|c| c.text.split_whitespace().count())
+            .sum()
+    };
+
+    Ok(TranscriptRevisionDiff {
+        transcript_id,
+        from_revision,
+        to_revision,
+        words_added: count("insert"),
+        words_removed: count("delete"),
+        changes,
+    })
+}
+
+// Restoring never rewrites history: the old state is saved again as the newest revision
+#[tauri::command]
+pub async fn restore_transcript_revision(
+    database: State<'_, Arc<Database>>,
+    request: RestoreRevisionRequest,
+) -> Result<TranscriptEditResult, String> {
+    let (content, segments) = sqlx::query_as::<_, (String, String)>(
+        "SELECT Content, Segments FROM TranscriptRevision WHERE TranscriptID = ?1 AND RevisionNumber = ?2"
+    )
+    .bind(&request.transcript_id)
+    .bind(request.revision_number)
+    .fetch_optional(&database.pool)
+    .await
+    .map_err(|e| format!("Failed to fetch revision: {}", e))?
+    .ok_or_else(|| format!("Revision {} not found", request.revision_number))?;
+
+    let segments: Vec<ParsedSegment> = serde_json::from_str(&segments)
+        .map_err(|e| format!("Failed to read revision segments: {}", e))?;
+    let summary = format!("Restored revision {}", request.revision_number);
+
+    save_edit(
+        &database,
+        &request.transcript_id,
+        |_| Ok(TranscriptSnapshot { content, segments }),
+        &request.author,
+        Some(&summary),
+    )
+    .await
+}
@@ -0,0 +1,113 @@
+// Offline extractive summaries and key points for transcripts
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tauri::State;
+use crate::commands::transcripts::{load_transcript_segments, DEFAULT_KEY_POINTS, DEFAULT_SUMMARY_SENTENCES};
+use crate::db::Database;
+use crate::services::summarizer::{summarize, KeyPoint};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SummarizeTranscriptRequest {
+    pub transcript_id: String,
+    pub summary_sentences: Option<usize>, // Default 3
+    pub key_points: Option<usize>, // Default 5
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TranscriptSummaryResult {
+    pub transcript_id: String,
+    pub summary: Option<String>, // None when the transcript has no sentences long enough to rank
+    pub key_points: Vec<KeyPoint>,
+    pub sentence_count: usize,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SummaryBackfillReport {
+    pub summarized: Vec<String>,
+    pub skipped: Vec<String>, // Too short to summarize
+    pub failed: Vec<(String, String)>, // Transcript ID and error
+}
+
+async fn summarize_and_store(
+    pool: &sqlx::SqlitePool,
+    transcript_id: &str,
+    summary_sentences: usize,
+    key_point_count: usize,
+) -> Result<TranscriptSummaryResult, String> {
+    let (language,) = sqlx::query_as::<_, (String,)>("SELECT Language FROM Transcript WHERE TranscriptID = ?1")
+        .bind(transcript_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| format!("Failed to fetch transcript: {}", e))?
+        .ok_or_else(|| "Transcript not found".to_string())?;
+
+    let segments = load_transcript_segments(pool, transcript_id)
+        .await
+        .map_err(|e| format!("Failed to fetch transcript segments: {}", e))?;
+
+    let extractive = summarize(&segments, &language, summary_sentences, key_point_count);
+    let sentence_count = extractive.as_ref().map_or(0, |e| e.sentence_count);
+    let (summary, key_points) = match extractive {
+        Some(extractive) => (Some(extractive.summary), extractive.key_points),
+        None => (None, Vec::new()),
+    };
+
+    let key_points_json = serde_json::to_string(&key_points)
+        .map_err(|e| format!("Failed to serialize key points: {}", e))?;
+
+    // The FTS update trigger re-indexes the new summary
+    sqlx::query(
+        "UPDATE Transcript SET Summary = ?1, KeyPoints = ?2, SummarySource = ?3 WHERE TranscriptID = ?4"
+    )
+    .bind(&summary)
+    .bind(summary.as_ref().map(|_| key_points_json))
+    .bind(summary.as_ref().map(|_| "extractive"))
+    .bind(transcript_id)
+    .execute(pool)
+    .await
+    .map_err(|e| format!("Failed to store summary: {}", e))?;
+
+    Ok(TranscriptSummaryResult {
+        transcript_id: transcript_id.to_string(),
+        summary,
+        key_points,
+        sentence_count,
+    })
+}
+
+// Regenerate a transcript's summary and key points, e.g. after editing it
+#[tauri::command]
+pub async fn summarize_transcript(
+    database: State<'_, Arc<Database>>,
+    request: SummarizeTranscriptRequest,
+) -> Result<TranscriptSummaryResult, String> {
+    let summary_sentences = request.summary_sentences.unwrap_or(DEFAULT_SUMMARY_SENTENCES).clamp(1, 20);
+    let key_points = request.key_points.unwrap_or(DEFAULT_KEY_POINTS).clamp(0, 50);
+
+    summarize_and_store(&database.pool, &request.transcript_id, summary_sentences, key_points).await
+}
+
+// Summarize transcripts that have no summary yet, such as those imported before summaries existed
+#[tauri::command]
+pub async fn summarize_missing_transcripts(
+    database: State<'_, Arc<Database>>,
+) -> Result<SummaryBackfillReport, String> {
+    let transcript_ids = sqlx::query_as::<_, (String,)>(
+        "SELECT TranscriptID FROM Transcript WHERE Summary IS NULL OR TRIM(Summary) = ''"
+    )
+    .fetch_all(&database.pool)
+    .await
+    .map_err(|e| format!("Failed to fetch transcripts: {}", e))?;
+
+    let mut report = SummaryBackfillReport::default();
+
+    for (transcript_id,) in transcript_ids {
+        match summarize_and_store(&database.pool, &transcript_id, DEFAULT_SUMMARY_SENTENCES, DEFAULT_KEY_POINTS).await {
+            Ok(result) if result.summary.is_some() => report.summarized.push(transcript_id),
+            Ok(_) => report.skipped.push(transcript_id),
+            Err(e) => report.failed.push((transcript_id, e)),
+        }
+    }
+
+    Ok(report)
+}
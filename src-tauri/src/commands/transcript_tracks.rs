@@ -0,0 +1,303 @@
+// Transcript tracks: several transcripts of one media file, keyed by language and kind
+use serde::{Deserialize, Serialize};
+use sqlx::{Sqlite, SqlitePool, Transaction};
+use std::sync::Arc;
+use tauri::State;
+use crate::commands::transcript_merge::{insert_derived_transcript, load_source};
+use crate::db::Database;
+use crate::services::disfluency::analyze_text;
+use crate::services::language_detect::normalize_language_code;
+use crate::services::transcript_parser::{ParsedSegment, ParsedTranscript};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum TrackKind {
+    Original, // As transcribed from the recording
+    Translation, // The original in another language
+    Cleaned, // Filler words and disfluencies removed
+}
+
+impl TrackKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TrackKind::Original => "original",
+            TrackKind::Translation => "translation",
+            TrackKind::Cleaned => "cleaned",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<TrackKind> {
+        match value {
+            "original" => Some(TrackKind::Original),
+            "translation" => Some(TrackKind::Translation),
+            "cleaned" => Some(TrackKind::Cleaned),
+            _ => None,
+        }
+    }
+
+    // Preference when several tracks share a language
+    fn rank(&self) -> usize {
+        match self {
+            TrackKind::Original => 0,
+            TrackKind::Translation => 1,
+            TrackKind::Cleaned => 2,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranscriptTrack {
+    pub transcript_id: String,
+    pub file_id: String,
+    pub language: String,
+    pub track_kind: String, // "original", "translation" or "cleaned"
+    pub source_transcript_id: Option<String>, // Track a translation or cleaned track was made from
+    pub word_count: usize,
+    pub imported_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SetTrackKindRequest {
+    pub transcript_id: String,
+    pub track_kind: TrackKind,
+    pub source_transcript_id: Option<String>, // Another track of the same file; ignored for originals
+}
+
+type TrackRow = (String, String, String, String, Option<String>, i64, String);
+
+const TRACK_COLUMNS: &str = "TranscriptID, FileID, Language, TrackKind, SourceTranscriptID, COALESCE(WordCount, 0), CAST(ImportedAt AS TEXT)";
+
+fn track_from_row(row: TrackRow) -> TranscriptTrack {
+    let (transcript_id, file_id, language, track_kind, source_transcript_id, word_count, imported_at) = row;
+    TranscriptTrack {
+        transcript_id,
+        file_id,
+        language,
+        track_kind,
+        source_transcript_id,
+        word_count: word_count as usize,
+        imported_at,
+    }
+}
+
+// Every track of the file the transcript belongs to, originals first
+async fn sibling_tracks(pool: &SqlitePool, transcript_id: &str) -> Result<Vec<TranscriptTrack>, String> {
+    let sql = format!(
+        r#"
+        SELECT {} FROM Transcript
+        WHERE FileID = (SELECT FileID FROM Transcript WHERE TranscriptID = ?1)
+        ORDER BY CASE TrackKind WHEN 'original' THEN 0 WHEN 'translation' THEN 1 ELSE 2 END, Language, ImportedAt
+        "#,
+        TRACK_COLUMNS
+    );
+    let rows = sqlx::query_as::<_, TrackRow>(&sql)
+        .bind(transcript_id)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| format!("Failed to fetch transcript tracks: {}", e))?;
+
+    if rows.is_empty() {
+        return Err("Transcript not found".to_string());
+    }
+    Ok(rows.into_iter().map(track_from_row).collect())
+}
+
+/// Kind and source track for a new transcript on `file_id` in `language`. Without a requested
+/// kind, the first transcript of a file is its original and transcripts in other languages are
+/// translations of it. Fails if the file already has a track of that language and kind.
+pub(crate) async fn file_track(
+    tx: &mut Transaction<'_, Sqlite>,
+    file_id: &str,
+    language: &str,
+    requested: Option<TrackKind>,
+) -> Result<(TrackKind, Option<String>), String> {
+    let tracks = sqlx::query_as::<_, (String, String, String)>(
+        "SELECT TranscriptID, Language, TrackKind FROM Transcript WHERE FileID = ?1 ORDER BY ImportedAt"
+    )
+    .bind(file_id)
+    .fetch_all(&mut **tx)
+    .await
+    .map_err(|e| format!("Failed to fetch transcript tracks: {}", e))?;
+
+    let original = tracks.iter().find(|(_, _, kind)| kind == "original");
+    let kind = requested.unwrap_or(match original {
+        Some((_, original_language, _)) if original_language != language => TrackKind::Translation,
+        _ => TrackKind::Original,
+    });
+
+    if tracks.iter().any(|(_, l, k)| l == language && k == kind.as_str()) {
+        return Err(format!("That file already has a transcript track for {} ({})", language, kind.as_str()));
+    }
+
+    let source = match kind {
+        TrackKind::Original => None,
+        TrackKind::Translation => original.map(|(id, _, _)| id.clone()),
+        // Cleaned text comes from the uncleaned track in the same language
+        TrackKind::Cleaned => tracks
+            .iter()
+            .find(|(_, l, k)| l == language && k != "cleaned")
+            .map(|(id, _, _)| id.clone()),
+    };
+
+    Ok((kind, source))
+}
+
+/// The track of the same recording in `language`: the transcript itself if it is in that
+/// language, otherwise a track of the same kind, then an original, translation or cleaned one.
+/// None when the recording has no track in that language.
+pub(crate) async fn select_track(
+    pool: &SqlitePool,
+    transcript_id: &str,
+    language: &str,
+) -> Result<Option<TranscriptTrack>, String> {
+    let code = normalize_language_code(language)
+        .ok_or_else(|| format!("Invalid language code: {}", language))?;
+    let tracks = sibling_tracks(pool, transcript_id).await?;
+
+    let own_kind = tracks
+        .iter()
+        .find(|t| t.transcript_id == transcript_id)
+        .map(|t| t.track_kind.clone())
+        .unwrap_or_default();
+
+    Ok(tracks
+        .into_iter()
+        .filter(|t| t.language == code)
+        .min_by_key(|t| {
+            (
+                t.transcript_id != transcript_id,
+                t.track_kind != own_kind,
+                TrackKind::parse(&t.track_kind).map_or(usize::MAX, |k| k.rank()),
+            )
+        }))
+}
+
+#[tauri::command]
+pub async fn get_transcript_tracks(
+    database: State<'_, Arc<Database>>,
+    transcript_id: String,
+) -> Result<Vec<TranscriptTrack>, String> {
+    sibling_tracks(&database.pool, &transcript_id).await
+}
+
+// The track to generate content from for a target language
+#[tauri::command]
+pub async fn resolve_transcript_track(
+    database: State<'_, Arc<Database>>,
+    transcript_id: String,
+    language: String,
+) -> Result<TranscriptTrack, String> {
+    select_track(&database.pool, &transcript_id, &language)
+        .await?
+        .ok_or_else(|| format!("This recording has no transcript in {}", language))
+}
+
+#[tauri::command]
+pub async fn set_transcript_track_kind(
+    database: State<'_, Arc<Database>>,
+    request: SetTrackKindRequest,
+) -> Result<TranscriptTrack, String> {
+    let tracks = sibling_tracks(&database.pool, &request.transcript_id).await?;
+    let track = tracks
+        .iter()
+        .find(|t| t.transcript_id == request.transcript_id)
+        .ok_or_else(|| "Transcript not found".to_string())?;
+
+    let kind = request.track_kind.as_str();
+    if tracks.iter().any(|t| t.transcript_id != track.transcript_id && t.language == track.language && t.track_kind == kind) {
+        return Err(format!("That file already has a transcript track for {} ({})", track.language, kind));
+    }
+
+    let source = match (request.track_kind, &request.source_transcript_id) {
+        (TrackKind::Original, _) => None,
+        (_, Some(source_id)) if source_id == &request.transcript_id => {
+            return Err("A track cannot be derived from itself".to_string());
+        }
+        (_, Some(source_id)) => {
+            if !tracks.iter().any(|t| &t.transcript_id == source_id) {
+                return Err("The source track must belong to the same file".to_string());
+            }
+            Some(source_id.clone())
+        }
+        (_, None) => track.source_transcript_id.clone(),
+    };
+
+    sqlx::query("UPDATE Transcript SET TrackKind = ?1, SourceTranscriptID = ?2 WHERE TranscriptID = ?3")
+        .bind(kind)
+        .bind(&source)
+        .bind(&request.transcript_id)
+        .execute(&database.pool)
+        .await
+        .map_err(|e| format!("Failed to update transcript track: {}", e))?;
+
+    Ok(TranscriptTrack {
+        track_kind: kind.to_string(),
+        source_transcript_id: source,
+        ..track.clone()
+    })
+}
+
+// Add a cleaned track next to a transcript: the same segments with filler words and
+// disfluencies removed. Word timings are dropped since the words no longer line up.
+#[tauri::command]
+pub async fn create_cleaned_track(
+    database: State<'_, Arc<Database>>,
+    transcript_id: String,
+) -> Result<TranscriptTrack, String> {
+    let mut tx = database.pool.begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    let source = load_source(&mut tx, &transcript_id).await?;
+
+    let (kind,) = sqlx::query_as::<_, (String,)>("SELECT TrackKind FROM Transcript WHERE TranscriptID = ?1")
+        .bind(&transcript_id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to fetch transcript: {}", e))?;
+    if kind == TrackKind::Cleaned.as_str() {
+        return Err("This transcript is already a cleaned track".to_string());
+    }
+    file_track(&mut tx, &source.file_id, &source.language, Some(TrackKind::Cleaned)).await?;
+
+    let segments: Vec<ParsedSegment> = source
+        .segments
+        .iter()
+        .filter_map(|segment| {
+            let cleaned = analyze_text(&segment.text, &source.language).cleaned;
+            (!cleaned.trim().is_empty()).then(|| ParsedSegment {
+                text: cleaned,
+                words: Vec::new(),
+                ..segment.clone()
+            })
+        })
+        .collect();
+    if segments.is_empty() {
+        return Err("Nothing is left of the transcript once cleaned".to_string());
+    }
+    let parsed = ParsedTranscript { segments, language: None };
+
+    let cleaned_id = insert_derived_transcript(
+        &mut tx,
+        &source.file_id,
+        &source,
+        &parsed,
+        &source.display_names,
+        std::slice::from_ref(&transcript_id),
+        (TrackKind::Cleaned, Some(&transcript_id)),
+    )
+    .await?;
+
+    let sql = format!("SELECT {} FROM Transcript WHERE TranscriptID = ?1", TRACK_COLUMNS);
+    let row = sqlx::query_as::<_, TrackRow>(&sql)
+        .bind(&cleaned_id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to fetch transcript track: {}", e))?;
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit transaction: {}", e))?;
+
+    Ok(track_from_row(row))
+}
@@ -5,11 +5,24 @@ use tauri::{AppHandle, State};
 use uuid::Uuid;
 use std::fs;
 use std::sync::Arc;
+use crate::commands::tags::resolve_tag_id;
+use crate::commands::transcript_links::{find_video_candidates, is_placeholder_path, is_video_path, pick_auto_link};
+use crate::commands::transcript_tracks::{file_track, TrackKind};
 use crate::db::Database;
+use crate::services::language_detect::{detect_language, normalize_language_code, normalize_language_filter};
+use crate::services::caption_export::format_timestamp;
+use crate::services::search_query::{build_fts_query, build_segment_fts_queries, extract_highlights, MATCH_END, MATCH_START};
+use crate::services::summarizer::{summarize, KeyPoint};
+use crate::services::content_metrics::{compute_metrics, score};
+use crate::services::text_encoding::{clean_text, decode_text};
+use crate::services::transcript_parser::{extract_docx_text, parse_transcript, ParsedSegment, ParsedTranscript};
+use sqlx::{Sqlite, Transaction};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TranscriptImportRequest {
     pub file_paths: Vec<String>,
+    pub file_id: Option<String>, // Media file to add the transcripts to as tracks; matched by name otherwise
+    pub track_kind: Option<TrackKind>, // Worked out from the file's existing tracks otherwise
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -20,6 +33,10 @@ pub struct ImportedTranscript {
     pub content: String,
     pub word_count: usize,
     pub language: String,
+    pub track_kind: String,
+    pub encoding: Option<String>,
+    pub summary: Option<String>,
+    pub key_points: Vec<KeyPoint>,
     pub status: String,
 }
 
@@ -46,10 +63,16 @@ pub struct TranscriptSummary {
     pub imported_at: String,
     pub status: String,
     pub content_score: Option<f64>, // Task #14
+    pub language_confidence: Option<f64>,
+    pub track_kind: String,
 }
 
+// Length of the extractive summary generated on import
+pub(crate) const DEFAULT_SUMMARY_SENTENCES: usize = 3;
+pub(crate) const DEFAULT_KEY_POINTS: usize = 5;
+
 // Valid transcript file extensions
-const VALID_TRANSCRIPT_EXTENSIONS: &[&str] = &["txt", "srt", "vtt"];
+pub(crate) const VALID_TRANSCRIPT_EXTENSIONS: &[&str] = &["txt", "srt", "vtt", "sbv", "ttml", "dfxp", "json", "docx"];
 
 #[tauri::command]
 pub async fn validate_transcript_files(file_paths: Vec<String>) -> Result<Vec<String>, String> {
@@ -85,7 +108,7 @@ pub async fn import_transcript_files(
     let mut errors = Vec::new();
 
     for file_path in request.file_paths {
-        match import_single_transcript(&app_handle, &database, &file_path).await {
+        match import_single_transcript(&app_handle, &database, &file_path, request.file_id.as_deref(), request.track_kind).await {
             Ok(imported_transcript) => imported_transcripts.push(imported_transcript),
             Err(error) => errors.push(TranscriptImportError {
                 file_path: file_path.clone(),
@@ -101,13 +124,15 @@ pub async fn import_transcript_files(
     })
 }
 
-async fn import_single_transcript(
-    _app_handle: &AppHandle,
-    database: &Arc<Database>,
-    file_path: &str,
-) -> Result<ImportedTranscript, Box<dyn std::error::Error>> {
-    let path = Path::new(file_path);
-    
+// A transcript file read and parsed the way it is stored
+pub(crate) struct TranscriptFile {
+    pub(crate) content: String, // The original text for TXT/DOCX; timed formats are flattened
+    pub(crate) parsed: ParsedTranscript,
+    pub(crate) encoding: Option<String>,
+    pub(crate) text_size: usize, // Bytes of decoded text
+}
+
+pub(crate) fn read_transcript_file(path: &Path) -> Result<TranscriptFile, Box<dyn std::error::Error>> {
     // Validate file exists and is transcript
     if !path.exists() {
         return Err("File does not exist".into());
@@ -119,68 +144,168 @@ async fn import_single_transcript(
         .to_lowercase();
     
     if !VALID_TRANSCRIPT_EXTENSIONS.contains(&extension.as_str()) {
-        return Err("Invalid transcript format".into());
+        return Err(format!("Invalid transcript format: .{}", extension).into());
     }
     
-    // Read transcript content
-    let content = fs::read_to_string(path)?;
+    // Read transcript content (DOCX is a zip archive, so extract its text first)
+    let bytes = fs::read(path)?;
+    let (raw_content, encoding) = if extension == "docx" {
+        (extract_docx_text(&bytes)?, None)
+    } else {
+        let decoded = decode_text(&bytes)?;
+        (decoded.text, Some(decoded.encoding.to_string()))
+    };
+    
+    // Normalize line endings and drop invisible characters before parsing
+    let content = clean_text(&raw_content);
     
     // Basic content validation
     if content.trim().is_empty() {
         return Err("Transcript file is empty".into());
     }
     
-    // Clean content for SRT files (remove timestamps)
-    let cleaned_content = if extension == "srt" {
-        clean_srt_content(&content)
-    } else {
+    // Split into timed, speaker-attributed segments
+    let parsed = parse_transcript(&content, &extension)?;
+    
+    // Plain text keeps the original for TXT/DOCX; timed formats are flattened (timestamps removed)
+    let cleaned_content = if extension == "txt" || extension == "docx" {
         content.clone()
+    } else {
+        parsed.plain_text()
     };
     
+    Ok(TranscriptFile {
+        content: cleaned_content,
+        parsed,
+        encoding,
+        text_size: content.len(),
+    })
+}
+
+pub(crate) async fn import_single_transcript(
+    _app_handle: &AppHandle,
+    database: &Arc<Database>,
+    file_path: &str,
+    target_file_id: Option<&str>,
+    requested_kind: Option<TrackKind>,
+) -> Result<ImportedTranscript, Box<dyn std::error::Error>> {
+    let path = Path::new(file_path);
+    let TranscriptFile { content: cleaned_content, parsed, encoding, text_size } = read_transcript_file(path)?;
+    
     let filename = path.file_name()
         .and_then(|name| name.to_str())
         .ok_or("Invalid filename")?
         .to_string();
     
-    let word_count = count_words(&cleaned_content);
+    let word_count = parsed.word_count();
+    
+    // Prefer the language the file declares (Whisper, TTML); otherwise identify it from the text
+    let (language, language_confidence, language_source) =
+        match parsed.language.as_deref().and_then(normalize_language_code) {
+            Some(code) => (code, None, "file"),
+            None => match detect_language(&cleaned_content) {
+                Some(guess) => (guess.language, Some(guess.confidence), "detected"),
+                None => ("en".to_string(), None, "default"),
+            },
+        };
+    
+    // Extractive summary and key points, so templates have them without an LLM call
+    let extractive = summarize(&parsed.segments, &language, DEFAULT_SUMMARY_SENTENCES, DEFAULT_KEY_POINTS);
+    let (summary, key_points) = match extractive {
+        Some(extractive) => (Some(extractive.summary), extractive.key_points),
+        None => (None, Vec::new()),
+    };
+    let key_points_json = summary.as_ref().map(|_| serde_json::to_string(&key_points)).transpose()?;
+    
+    // Content score from readability and delivery metrics; None for very short transcripts
+    let breakdown = score(compute_metrics(&parsed.segments, &language));
+    let breakdown_json = breakdown.as_ref().map(serde_json::to_string).transpose()?;
     
     // Generate unique ID
     let transcript_id = Uuid::new_v4().to_string();
     
-    // Create a placeholder file record (for standalone transcripts)
-    let file_id = Uuid::new_v4().to_string();
+    // Add to the chosen media file, or link to the matching video if there is exactly one without
+    // a transcript in this language; otherwise use a placeholder file record
+    let linked_file_id = match target_file_id {
+        Some(target) => {
+            let (target_path,) = sqlx::query_as::<_, (String,)>("SELECT FilePath FROM File WHERE FileID = ?1")
+                .bind(target)
+                .fetch_optional(&database.pool)
+                .await?
+                .ok_or("File not found")?;
+            if is_placeholder_path(&target_path) {
+                return Err("Transcripts can only be added to media files".into());
+            }
+            Some(target.to_string())
+        }
+        None => {
+            let candidates = find_video_candidates(&database.pool, &filename, &language).await?;
+            pick_auto_link(&candidates).map(|c| c.file_id.clone())
+        }
+    };
+    let file_id = linked_file_id.clone().unwrap_or_else(|| Uuid::new_v4().to_string());
     
-    // Insert file record first
-    sqlx::query(
-        "INSERT INTO File (FileID, FilePath, OriginalName, FileSize, Status, ImportedAt) VALUES (?1, ?2, ?3, ?4, ?5, datetime('now'))"
-    )
-    .bind(&file_id)
-    .bind(file_path)
-    .bind(&filename)
-    .bind(content.len() as i64)
-    .bind("Imported")
-    .execute(&database.pool)
-    .await?;
+    let mut tx = database.pool.begin().await?;
+    
+    // A second language on a video is a translation of its original
+    let (track_kind, source_transcript_id) = match &linked_file_id {
+        Some(linked) => file_track(&mut tx, linked, &language, requested_kind).await?,
+        None => (requested_kind.unwrap_or(TrackKind::Original), None),
+    };
+    
+    // Insert placeholder file record first (for standalone transcripts)
+    if linked_file_id.is_none() {
+        sqlx::query(
+            "INSERT INTO File (FileID, FilePath, OriginalName, FileSize, Status, ImportedAt) VALUES (?1, ?2, ?3, ?4, ?5, datetime('now'))"
+        )
+        .bind(&file_id)
+        .bind(file_path)
+        .bind(&filename)
+        .bind(text_size as i64)
+        .bind("Imported")
+        .execute(&mut *tx)
+        .await?;
+    }
     
     // Insert transcript into database
     sqlx::query(
-        "INSERT INTO Transcript (TranscriptID, FileID, Content, WordCount, Language, ImportedAt) VALUES (?1, ?2, ?3, ?4, ?5, datetime('now'))"
+        "INSERT INTO Transcript (TranscriptID, FileID, Content, WordCount, Language, LanguageConfidence, LanguageSource, SourceEncoding, Summary, KeyPoints, SummarySource, ContentScore, ScoreBreakdown, ScoreSource, TrackKind, SourceTranscriptID, ImportedAt) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, datetime('now'))"
     )
     .bind(&transcript_id)
     .bind(&file_id)
     .bind(&cleaned_content)
     .bind(word_count as i64)
-    .bind("en") // Default to English for now
-    .execute(&database.pool)
+    .bind(&language)
+    .bind(language_confidence)
+    .bind(language_source)
+    .bind(&encoding)
+    .bind(&summary)
+    .bind(&key_points_json)
+    .bind(summary.as_ref().map(|_| "extractive"))
+    .bind(breakdown.as_ref().map(|b| b.score))
+    .bind(&breakdown_json)
+    .bind(breakdown.as_ref().map(|_| "metrics"))
+    .bind(track_kind.as_str())
+    .bind(&source_transcript_id)
+    .execute(&mut *tx)
     .await?;
     
+    // Store speakers and segments
+    store_transcript_segments(&mut tx, &transcript_id, &parsed).await?;
+    
+    tx.commit().await?;
+    
     Ok(ImportedTranscript {
         id: transcript_id,
         file_path: file_path.to_string(),
         filename,
         content: cleaned_content,
         word_count,
-        language: "en".to_string(),
+        language,
+        track_kind: track_kind.as_str().to_string(),
+        encoding,
+        summary,
+        key_points,
         status: "imported".to_string(),
     })
 }
@@ -188,22 +313,27 @@ async fn import_single_transcript(
 #[tauri::command]
 pub async fn get_imported_transcripts(
     database: State<'_, Arc<Database>>,
+    language: Option<String>,
+    track_kind: Option<TrackKind>,
 ) -> Result<Vec<TranscriptSummary>, String> {
+    let language = normalize_language_filter(language.as_deref())?;
     // Task #14: Include ContentScore in query
-    let rows = sqlx::query_as::<_, (String, String, i64, String, String, String, Option<f64>)>(
-        "SELECT t.TranscriptID, f.OriginalName, t.WordCount, t.Language, t.Content, t.ImportedAt, t.ContentScore FROM Transcript t JOIN File f ON t.FileID = f.FileID ORDER BY t.ImportedAt DESC"
+    let rows = sqlx::query_as::<_, (String, String, i64, String, String, String, Option<f64>, Option<f64>, String)>(
+        "SELECT t.TranscriptID, f.OriginalName, t.WordCount, t.Language, t.Content, t.ImportedAt, t.ContentScore, t.LanguageConfidence, t.TrackKind FROM Transcript t JOIN File f ON t.FileID = f.FileID WHERE (?1 IS NULL OR t.Language = ?1) AND (?2 IS NULL OR t.TrackKind = ?2) ORDER BY t.ImportedAt DESC"
     )
+    .bind(&language)
+    .bind(track_kind.map(|kind| kind.as_str()))
     .fetch_all(&database.pool)
     .await
     .map_err(|e| format!("Query failed: {}", e))?;
     
     let transcripts = rows
         .into_iter()
-        .map(|(id, filename, word_count, language, content, imported_at, content_score)| {
-            let content_preview = if content.len() > 200 {
-                format!("{}...", &content[..200])
-            } else {
-                content
+        .map(|(id, filename, word_count, language, content, imported_at, content_score, language_confidence, track_kind)| {
+            // First 200 characters, cut on a character boundary
+            let content_preview = match content.char_indices().nth(200) {
+                Some((end, _)) => format!("{}...", &content[..end]),
+                None => content,
             };
             
             TranscriptSummary {
@@ -215,6 +345,8 @@ pub async fn get_imported_transcripts(
                 imported_at,
                 status: "imported".to_string(),
                 content_score, // Task #14
+                language_confidence,
+                track_kind,
             }
         })
         .collect();
@@ -239,8 +371,8 @@ pub async fn get_transcript_by_id(
     database: State<'_, Arc<Database>>,
     transcript_id: String,
 ) -> Result<ImportedTranscript, String> {
-    let row = sqlx::query_as::<_, (String, String, String, String, i64, String)>(
-        "SELECT t.TranscriptID, f.FilePath, f.OriginalName, t.Content, t.WordCount, t.Language FROM Transcript t JOIN File f ON t.FileID = f.FileID WHERE t.TranscriptID = ?1"
+    let row = sqlx::query_as::<_, (String, String, String, String, i64, String, Option<String>, Option<String>, Option<String>, String)>(
+        "SELECT t.TranscriptID, f.FilePath, f.OriginalName, t.Content, t.WordCount, t.Language, t.SourceEncoding, t.Summary, t.KeyPoints, t.TrackKind FROM Transcript t JOIN File f ON t.FileID = f.FileID WHERE t.TranscriptID = ?1"
     )
     .bind(&transcript_id)
     .fetch_one(&database.pool)
@@ -254,43 +386,204 @@ pub async fn get_transcript_by_id(
         content: row.3,
         word_count: row.4 as usize,
         language: row.5,
+        track_kind: row.9,
+        encoding: row.6,
+        summary: row.7,
+        key_points: row.8
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default(),
         status: "imported".to_string(),
     })
 }
 
-// Helper function to clean SRT content
-fn clean_srt_content(srt_content: &str) -> String {
-    let mut cleaned_lines = Vec::new();
-    let lines: Vec<&str> = srt_content.lines().collect();
+// Language detection and manual override
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TranscriptLanguage {
+    pub transcript_id: String,
+    pub language: String,
+    pub confidence: Option<f64>,
+    pub source: String,
+}
+
+#[tauri::command]
+pub async fn set_transcript_language(
+    database: State<'_, Arc<Database>>,
+    transcript_id: String,
+    language: String,
+) -> Result<TranscriptLanguage, String> {
+    let code = normalize_language_code(&language)
+        .ok_or_else(|| format!("Invalid language code: {}", language))?;
+    
+    let result = sqlx::query(
+        "UPDATE Transcript SET Language = ?1, LanguageConfidence = NULL, LanguageSource = 'manual' WHERE TranscriptID = ?2"
+    )
+    .bind(&code)
+    .bind(&transcript_id)
+    .execute(&database.pool)
+    .await
+    .map_err(|e| format!("Failed to update language: {}", e))?;
+    
+    if result.rows_affected() == 0 {
+        return Err("Transcript not found".to_string());
+    }
+    
+    Ok(TranscriptLanguage {
+        transcript_id,
+        language: code,
+        confidence: None,
+        source: "manual".to_string(),
+    })
+}
+
+// Re-run detection; manual overrides are kept unless `force` is set
+#[tauri::command]
+pub async fn detect_transcript_language(
+    database: State<'_, Arc<Database>>,
+    transcript_id: String,
+    force: Option<bool>,
+) -> Result<TranscriptLanguage, String> {
+    let (content, language, confidence, source) = sqlx::query_as::<_, (String, String, Option<f64>, String)>(
+        "SELECT Content, Language, LanguageConfidence, LanguageSource FROM Transcript WHERE TranscriptID = ?1"
+    )
+    .bind(&transcript_id)
+    .fetch_optional(&database.pool)
+    .await
+    .map_err(|e| format!("Failed to fetch transcript: {}", e))?
+    .ok_or_else(|| "Transcript not found".to_string())?;
+    
+    if source == "manual" && !force.unwrap_or(false) {
+        return Ok(TranscriptLanguage { transcript_id, language, confidence, source });
+    }
+    
+    let guess = detect_language(&content)
+        .ok_or_else(|| "Could not identify the transcript's language".to_string())?;
+    
+    sqlx::query(
+        "UPDATE Transcript SET Language = ?1, LanguageConfidence = ?2, LanguageSource = 'detected' WHERE TranscriptID = ?3"
+    )
+    .bind(&guess.language)
+    .bind(guess.confidence)
+    .bind(&transcript_id)
+    .execute(&database.pool)
+    .await
+    .map_err(|e| format!("Failed to update language: {}", e))?;
+    
+    Ok(TranscriptLanguage {
+        transcript_id,
+        language: guess.language,
+        confidence: Some(guess.confidence),
+        source: "detected".to_string(),
+    })
+}
+
+// Insert one TranscriptSegment row per parsed segment, adding a Speaker row for each label not seen
+// before. Existing speakers are reused so renamed display names survive edits and restores.
+pub(crate) async fn store_transcript_segments(
+    tx: &mut Transaction<'_, Sqlite>,
+    transcript_id: &str,
+    parsed: &ParsedTranscript,
+) -> Result<(), sqlx::Error> {
+    let mut speaker_ids = std::collections::HashMap::new();
     
-    let mut i = 0;
-    while i < lines.len() {
-        let line = lines[i].trim();
+    for label in parsed.speakers() {
+        sqlx::query(
+            "INSERT INTO Speaker (SpeakerID, TranscriptID, Label, DisplayName) VALUES (?1, ?2, ?3, ?4) ON CONFLICT(TranscriptID, Label) DO NOTHING"
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(transcript_id)
+        .bind(&label)
+        .bind(&label)
+        .execute(&mut **tx)
+        .await?;
         
-        // Skip sequence numbers
-        if line.parse::<u32>().is_ok() {
-            i += 1;
-            continue;
-        }
+        let (speaker_id,) = sqlx::query_as::<_, (String,)>(
+            "SELECT SpeakerID FROM Speaker WHERE TranscriptID = ?1 AND Label = ?2"
+        )
+        .bind(transcript_id)
+        .bind(&label)
+        .fetch_one(&mut **tx)
+        .await?;
         
-        // Skip timestamp lines (contain -->)
-        if line.contains("-->") {
-            i += 1;
-            continue;
-        }
+        speaker_ids.insert(label, speaker_id);
+    }
+    
+    for (index, segment) in parsed.segments.iter().enumerate() {
+        let speaker_id = segment.speaker.as_ref().and_then(|label| speaker_ids.get(label));
+        let words = if segment.words.is_empty() {
+            None
+        } else {
+            serde_json::to_string(&segment.words).ok()
+        };
         
-        // Skip empty lines
-        if line.is_empty() {
-            i += 1;
-            continue;
-        }
+        sqlx::query(
+            r#"
+            INSERT INTO TranscriptSegment (
+                SegmentID, TranscriptID, SegmentIndex, SpeakerID, StartTime, EndTime, Text, WordCount, Words
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+            "#
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(transcript_id)
+        .bind(index as i64)
+        .bind(speaker_id)
+        .bind(segment.start_time)
+        .bind(segment.end_time)
+        .bind(&segment.text)
+        .bind(count_words(&segment.text) as i64)
+        .bind(words)
+        .execute(&mut **tx)
+        .await?;
+    }
+    
+    Ok(())
+}
+
+// Load stored segments in order, with speakers resolved to their display names.
+// Transcripts imported before segments were stored fall back to one untimed segment.
+pub(crate) async fn load_transcript_segments(
+    pool: &sqlx::SqlitePool,
+    transcript_id: &str,
+) -> Result<Vec<ParsedSegment>, sqlx::Error> {
+    let rows = sqlx::query_as::<_, (Option<f64>, Option<f64>, Option<String>, String, Option<String>)>(
+        r#"
+        SELECT seg.StartTime, seg.EndTime, s.DisplayName, seg.Text, seg.Words
+        FROM TranscriptSegment seg
+        LEFT JOIN Speaker s ON seg.SpeakerID = s.SpeakerID
+        WHERE seg.TranscriptID = ?1
+        ORDER BY seg.SegmentIndex
+        "#
+    )
+    .bind(transcript_id)
+    .fetch_all(pool)
+    .await?;
+    
+    if rows.is_empty() {
+        let (content,) = sqlx::query_as::<_, (String,)>("SELECT Content FROM Transcript WHERE TranscriptID = ?1")
+            .bind(transcript_id)
+            .fetch_one(pool)
+            .await?;
         
-        // This is actual content
-        cleaned_lines.push(line);
-        i += 1;
+        return Ok(vec![ParsedSegment {
+            start_time: None,
+            end_time: None,
+            speaker: None,
+            text: content,
+            words: Vec::new(),
+        }]);
     }
     
-    cleaned_lines.join(" ")
+    Ok(rows
+        .into_iter()
+        .map(|(start_time, end_time, speaker, text, words)| ParsedSegment {
+            start_time,
+            end_time,
+            speaker,
+            text,
+            words: words
+                .and_then(|json| serde_json::from_str(&json).ok())
+                .unwrap_or_default(),
+        })
+        .collect())
 }
 
 // Helper function to count words
@@ -339,7 +632,7 @@ pub async fn extract_and_store_tags(
     
     // Update content score in transcript table
     sqlx::query(
-        "UPDATE Transcript SET ContentScore = ?1, AnalyzedAt = datetime('now') WHERE TranscriptID = ?2"
+        "UPDATE Transcript SET ContentScore = ?1, ScoreBreakdown = NULL, ScoreSource = 'external', AnalyzedAt = datetime('now') WHERE TranscriptID = ?2"
     )
     .bind(content_score)
     .bind(&transcript_id)
@@ -347,53 +640,70 @@ pub async fn extract_and_store_tags(
     .await
     .map_err(|e| format!("Failed to update transcript: {}", e))?;
     
-    // Insert or update tags
+    store_transcript_tags(&mut tx, &transcript_id, &tags, None).await?;
+    
+    // Commit transaction
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit transaction: {}", e))?;
+    
+    Ok(())
+}
+
+// Link tags to a transcript, creating tags that don't exist yet. Names are matched
+// case-insensitively against tag names and aliases. New tags get `category`; existing tags keep theirs.
+pub(crate) async fn store_transcript_tags(
+    tx: &mut Transaction<'_, Sqlite>,
+    transcript_id: &str,
+    tags: &[ExtractedTag],
+    category: Option<&str>,
+) -> Result<(), String> {
+    // Relevance stored per tag in this call, since several names can resolve to the same tag
+    let mut stored: Vec<(String, f64)> = Vec::new();
+    
     for tag_data in tags {
         // First, insert tag if it doesn't exist
         let tag_id = Uuid::new_v4().to_string();
         
-        // Check if tag already exists
-        let existing_tag = sqlx::query_as::<_, (String,)>(
-            "SELECT TagID FROM Tag WHERE LOWER(TagName) = LOWER(?1)"
-        )
-        .bind(&tag_data.tag)
-        .fetch_optional(&mut *tx)
-        .await
-        .map_err(|e| format!("Failed to check existing tag: {}", e))?;
+        // Check if tag already exists, under its own name or an alias
+        let existing_tag = resolve_tag_id(&mut **tx, &tag_data.tag)
+            .await
+            .map_err(|e| format!("Failed to check existing tag: {}", e))?;
         
-        let final_tag_id = if let Some((existing_id,)) = existing_tag {
+        let final_tag_id = if let Some(existing_id) = existing_tag {
             existing_id
         } else {
             // Insert new tag
             sqlx::query(
-                "INSERT INTO Tag (TagID, TagName, CreatedAt) VALUES (?1, ?2, datetime('now'))"
+                "INSERT INTO Tag (TagID, TagName, Category, CreatedAt) VALUES (?1, ?2, ?3, datetime('now'))"
             )
             .bind(&tag_id)
             .bind(&tag_data.tag)
-            .execute(&mut *tx)
+            .bind(category)
+            .execute(&mut **tx)
             .await
             .map_err(|e| format!("Failed to insert tag: {}", e))?;
             
             tag_id
         };
         
+        if stored.iter().any(|(id, relevance)| id == &final_tag_id && *relevance >= tag_data.relevance) {
+            continue;
+        }
+        stored.push((final_tag_id.clone(), tag_data.relevance));
+        
         // Insert or update transcript-tag relationship
         sqlx::query(
             "INSERT OR REPLACE INTO TranscriptTags (TranscriptID, TagID, Relevance) VALUES (?1, ?2, ?3)"
         )
-        .bind(&transcript_id)
+        .bind(transcript_id)
         .bind(&final_tag_id)
         .bind(tag_data.relevance)
-        .execute(&mut *tx)
+        .execute(&mut **tx)
         .await
         .map_err(|e| format!("Failed to insert transcript-tag relationship: {}", e))?;
     }
     
-    // Commit transaction
-    tx.commit()
-        .await
-        .map_err(|e| format!("Failed to commit transaction: {}", e))?;
-    
     Ok(())
 }
 
@@ -421,14 +731,21 @@ pub async fn get_transcript_tags(
     Ok(tags)
 }
 
+// Counts include transcripts tagged with any tag nested under a tag, each transcript once
 #[tauri::command]
 pub async fn get_all_tags(
     database: State<'_, Arc<Database>>,
 ) -> Result<Vec<(String, i64)>, String> {
     let rows = sqlx::query_as::<_, (String, i64)>(
-        "SELECT t.TagName, COUNT(tt.TranscriptID) as usage_count 
+        "WITH RECURSIVE Descendant(RootID, TagID) AS (
+             SELECT TagID, TagID FROM Tag
+             UNION
+             SELECT d.RootID, t.TagID FROM Tag t JOIN Descendant d ON t.ParentTagID = d.TagID
+         )
+         SELECT t.TagName, COUNT(DISTINCT tt.TranscriptID) as usage_count 
          FROM Tag t 
-         LEFT JOIN TranscriptTags tt ON t.TagID = tt.TagID 
+         JOIN Descendant d ON d.RootID = t.TagID 
+         LEFT JOIN TranscriptTags tt ON tt.TagID = d.TagID 
          GROUP BY t.TagID 
          ORDER BY usage_count DESC"
     )
@@ -442,9 +759,37 @@ pub async fn get_all_tags(
 // Task #12: Full-Text Search Implementation
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TranscriptSearchRequest {
-    pub query: String,
+    pub query: String, // Phrases, prefix*, AND/OR/NOT and field: scoping (see services::search_query)
     pub limit: Option<i32>,
     pub offset: Option<i32>,
+    pub language: Option<String>,
+    pub tags: Option<Vec<String>>, // Matches transcripts with any of these tags
+    pub date_from: Option<String>, // YYYY-MM-DD, inclusive, compared with ImportedAt
+    pub date_to: Option<String>, // YYYY-MM-DD, inclusive
+    pub min_content_score: Option<f64>,
+    pub max_content_score: Option<f64>,
+    pub track_kind: Option<TrackKind>, // Only search tracks of this kind
+    pub max_segment_hits: Option<i32>, // Per transcript, default 10
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SegmentSearchHit {
+    pub segment_index: i64,
+    pub start_time: Option<f64>, // Seconds, None for untimed transcripts
+    pub end_time: Option<f64>,
+    pub timecode: Option<String>, // HH:MM:SS.mmm of start_time
+    pub speaker: Option<String>,
+    pub text: String,
+    pub highlight_positions: Vec<(usize, usize)>, // UTF-16 offsets into text
+    pub exact: bool, // False when the segment matches only some of the query terms
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LinkedVideo {
+    pub file_id: String,
+    pub file_path: String,
+    pub original_name: String,
+    pub duration: Option<f64>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -455,9 +800,15 @@ pub struct TranscriptSearchResult {
     pub content_snippet: String,
     pub word_count: i32,
     pub language: String,
+    pub track_kind: String,
     pub imported_at: String,
+    pub content_score: Option<f64>,
     pub rank: f64,
-    pub highlight_positions: Vec<(usize, usize)>,
+    pub highlight_positions: Vec<(usize, usize)>, // UTF-16 offsets into content_snippet
+    pub summary: Option<String>,
+    pub summary_highlight_positions: Vec<(usize, usize)>, // UTF-16 offsets into summary
+    pub segment_hits: Vec<SegmentSearchHit>,
+    pub video: Option<LinkedVideo>, // The video this transcript is linked to, if not standalone
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -467,6 +818,32 @@ pub struct TranscriptSearchResponse {
     pub search_time_ms: f64,
 }
 
+// Validate a YYYY-MM-DD filter date
+fn parse_filter_date(value: &Option<String>, name: &str) -> Result<Option<String>, String> {
+    match value.as_deref().map(str::trim).filter(|v| !v.is_empty()) {
+        Some(date) => chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .map(|d| Some(d.to_string()))
+            .map_err(|_| format!("Invalid {} '{}', expected YYYY-MM-DD", name, date)),
+        None => Ok(None),
+    }
+}
+
+// Shared filter clause for the count and search queries; parameters ?2 to ?8
+const SEARCH_FILTERS: &str = r#"
+    TranscriptFTS MATCH ?1
+    AND (?2 IS NULL OR t.Language = ?2)
+    AND (?3 IS NULL OR EXISTS (
+        SELECT 1 FROM TranscriptTags tt JOIN Tag tg ON tt.TagID = tg.TagID
+        WHERE tt.TranscriptID = t.TranscriptID
+          AND LOWER(tg.TagName) IN (SELECT LOWER(value) FROM json_each(?3))
+    ))
+    AND (?4 IS NULL OR date(t.ImportedAt) >= date(?4))
+    AND (?5 IS NULL OR date(t.ImportedAt) <= date(?5))
+    AND (?6 IS NULL OR t.ContentScore >= ?6)
+    AND (?7 IS NULL OR t.ContentScore <= ?7)
+    AND (?8 IS NULL OR t.TrackKind = ?8)
+"#;
+
 #[tauri::command]
 pub async fn search_transcripts(
     database: State<'_, Arc<Database>>,
@@ -479,67 +856,140 @@ pub async fn search_transcripts(
         return Err("Search query cannot be empty".to_string());
     }
     
+    // User syntax is compiled to a quoted FTS5 expression, so it can't break the MATCH
+    let fts_query = build_fts_query(&request.query)?;
+    
+    let segment_queries = build_segment_fts_queries(&request.query)?;
+    
     let limit = request.limit.unwrap_or(20).min(100);
     let offset = request.offset.unwrap_or(0);
+    let max_segment_hits = request.max_segment_hits.unwrap_or(10).clamp(0, 100);
+    
+    let language = normalize_language_filter(request.language.as_deref())?;
+    let date_from = parse_filter_date(&request.date_from, "start date")?;
+    let date_to = parse_filter_date(&request.date_to, "end date")?;
+    let tags = match request.tags.as_ref().filter(|tags| !tags.is_empty()) {
+        Some(tags) => Some(serde_json::to_string(tags).map_err(|e| format!("Invalid tag filter: {}", e))?),
+        None => None,
+    };
     
     // First get total count of matching results
-    let count_row = sqlx::query_as::<_, (i32,)>(
-        "SELECT COUNT(*) FROM TranscriptFTS WHERE TranscriptFTS MATCH ?1"
-    )
-    .bind(&request.query)
-    .fetch_one(&database.pool)
-    .await
-    .map_err(|e| format!("Count query failed: {}", e))?;
+    let count_query = format!(
+        "SELECT COUNT(*) FROM TranscriptFTS fts JOIN Transcript t ON fts.rowid = t.rowid WHERE {}",
+        SEARCH_FILTERS
+    );
+    
+    let count_row = sqlx::query_as::<_, (i32,)>(&count_query)
+        .bind(&fts_query)
+        .bind(&language)
+        .bind(&tags)
+        .bind(&date_from)
+        .bind(&date_to)
+        .bind(request.min_content_score)
+        .bind(request.max_content_score)
+        .bind(request.track_kind.map(|kind| kind.as_str()))
+        .fetch_one(&database.pool)
+        .await
+        .map_err(|e| format!("Count query failed: {}", e))?;
     
     let total_count = count_row.0;
     
-    // Perform FTS5 search with ranking
+    // Perform FTS5 search with ranking; snippet() and highlight() mark matches for offset extraction
     let search_query = format!(
         r#"
         SELECT 
             t.TranscriptID,
             t.FileID,
             f.OriginalName,
-            t.Content,
+            f.FilePath,
+            f.Duration,
+            snippet(TranscriptFTS, 1, ?11, ?12, '…', 32),
+            highlight(TranscriptFTS, 2, ?11, ?12),
             t.WordCount,
             t.Language,
+            t.TrackKind,
             t.ImportedAt,
+            t.ContentScore,
             rank
         FROM TranscriptFTS fts
-        JOIN Transcript t ON fts.TranscriptID = t.TranscriptID
+        JOIN Transcript t ON fts.rowid = t.rowid
         JOIN File f ON t.FileID = f.FileID
-        WHERE TranscriptFTS MATCH ?1
+        WHERE {}
         ORDER BY rank
-        LIMIT ?2 OFFSET ?3
-        "#
+        LIMIT ?9 OFFSET ?10
+        "#,
+        SEARCH_FILTERS
     );
     
-    let rows = sqlx::query_as::<_, (String, String, String, String, i32, String, String, f64)>(
+    let rows = sqlx::query_as::<_, (String, String, String, String, Option<f64>, String, Option<String>, i32, String, String, String, Option<f64>, f64)>(
         &search_query
     )
-    .bind(&request.query)
+    .bind(&fts_query)
+    .bind(&language)
+    .bind(&tags)
+    .bind(&date_from)
+    .bind(&date_to)
+    .bind(request.min_content_score)
+    .bind(request.max_content_score)
+    .bind(request.track_kind.map(|kind| kind.as_str()))
     .bind(limit)
     .bind(offset)
+    .bind(MATCH_START)
+    .bind(MATCH_END)
     .fetch_all(&database.pool)
     .await
     .map_err(|e| format!("Search query failed: {}", e))?;
     
     let mut results = Vec::new();
     
-    for (transcript_id, file_id, filename, content, word_count, language, imported_at, rank) in rows {
-        // Create highlighted snippet
-        let (snippet, positions) = create_highlighted_snippet(&content, &request.query, 200);
+    for (transcript_id, file_id, filename, file_path, duration, snippet, summary, word_count, language, track_kind, imported_at, content_score, rank) in rows {
+        let (content_snippet, highlight_positions) = extract_highlights(&snippet);
+        let (summary, summary_highlight_positions) = match summary {
+            Some(summary) => {
+                let (text, positions) = extract_highlights(&summary);
+                (Some(text), positions)
+            }
+            None => (None, Vec::new()),
+        };
+        
+        let segment_hits = match &segment_queries {
+            Some((exact, any)) if max_segment_hits > 0 => {
+                let mut hits = find_segment_hits(&database.pool, &transcript_id, exact, max_segment_hits, true)
+                    .await
+                    .map_err(|e| format!("Segment search failed: {}", e))?;
+                if hits.is_empty() {
+                    hits = find_segment_hits(&database.pool, &transcript_id, any, max_segment_hits, false)
+                        .await
+                        .map_err(|e| format!("Segment search failed: {}", e))?;
+                }
+                hits
+            }
+            _ => Vec::new(),
+        };
+        
+        let video = is_video_path(&file_path).then(|| LinkedVideo {
+            file_id: file_id.clone(),
+            file_path,
+            original_name: filename.clone(),
+            duration,
+        });
         
         results.push(TranscriptSearchResult {
             transcript_id,
             file_id,
             filename,
-            content_snippet: snippet,
+            content_snippet,
             word_count,
             language,
+            track_kind,
             imported_at,
+            content_score,
             rank: -rank, // FTS5 rank is negative, lower is better
-            highlight_positions: positions,
+            highlight_positions,
+            summary,
+            summary_highlight_positions,
+            segment_hits,
+            video,
         });
     }
     
@@ -552,69 +1002,52 @@ pub async fn search_transcripts(
     })
 }
 
-// Helper function to create highlighted snippet
-fn create_highlighted_snippet(
-    content: &str,
-    query: &str,
-    _max_length: usize,
-) -> (String, Vec<(usize, usize)>) {
-    let _content_lower = content.to_lowercase();
-    let query_lower = query.to_lowercase();
-    let query_words: Vec<&str> = query_lower.split_whitespace().collect();
-    
-    let mut highlight_positions = Vec::new();
-    let mut best_start = 0;
-    let mut best_score = 0;
-    
-    // Find the best window that contains the most query terms
-    let words: Vec<&str> = content.split_whitespace().collect();
-    for (i, _) in words.iter().enumerate() {
-        let mut score = 0;
-        let mut found_positions = Vec::new();
-        
-        // Check window starting at position i
-        let window_end = (i + 30).min(words.len());
-        let window_text = words[i..window_end].join(" ");
-        let window_lower = window_text.to_lowercase();
-        
-        for query_word in &query_words {
-            if let Some(pos) = window_lower.find(query_word) {
-                score += 1;
-                found_positions.push((pos, pos + query_word.len()));
-            }
-        }
-        
-        if score > best_score {
-            best_score = score;
-            best_start = i;
-            highlight_positions = found_positions;
-        }
-    }
-    
-    // Create snippet
-    let snippet_end = (best_start + 30).min(words.len());
-    let snippet_words = &words[best_start..snippet_end];
-    let snippet = snippet_words.join(" ");
+// Segments of one transcript matching an FTS expression, in playback order
+async fn find_segment_hits(
+    pool: &sqlx::SqlitePool,
+    transcript_id: &str,
+    fts_query: &str,
+    limit: i32,
+    exact: bool,
+) -> Result<Vec<SegmentSearchHit>, sqlx::Error> {
+    let rows = sqlx::query_as::<_, (i64, Option<f64>, Option<f64>, Option<String>, String)>(
+        r#"
+        SELECT
+            seg.SegmentIndex,
+            seg.StartTime,
+            seg.EndTime,
+            s.DisplayName,
+            highlight(TranscriptSegmentFTS, 0, ?4, ?5)
+        FROM TranscriptSegmentFTS sf
+        JOIN TranscriptSegment seg ON sf.rowid = seg.rowid
+        LEFT JOIN Speaker s ON seg.SpeakerID = s.SpeakerID
+        WHERE TranscriptSegmentFTS MATCH ?1 AND seg.TranscriptID = ?2
+        ORDER BY seg.SegmentIndex
+        LIMIT ?3
+        "#
+    )
+    .bind(fts_query)
+    .bind(transcript_id)
+    .bind(limit)
+    .bind(MATCH_START)
+    .bind(MATCH_END)
+    .fetch_all(pool)
+    .await?;
     
-    // Adjust highlight positions relative to snippet start
-    let start_offset = words[0..best_start].iter().map(|w| w.len() + 1).sum::<usize>();
-    let adjusted_positions: Vec<(usize, usize)> = highlight_positions
+    Ok(rows
         .into_iter()
-        .map(|(start, end)| (start.saturating_sub(start_offset), end.saturating_sub(start_offset)))
-        .collect();
-    
-    // Add ellipsis if needed
-    let final_snippet = if best_start > 0 {
-        format!("...{}", snippet)
-    } else {
-        snippet
-    };
-    
-    let final_snippet = if snippet_end < words.len() {
-        format!("{}...", final_snippet)
-    } else {
-        final_snippet
-    };
-    
-    (final_snippet, adjusted_positions)
-}
\ No newline at end of file
+        .map(|(segment_index, start_time, end_time, speaker, marked)| {
+            let (text, highlight_positions) = extract_highlights(&marked);
+            SegmentSearchHit {
+                segment_index,
+                start_time,
+                end_time,
+                timecode: start_time.map(|t| format_timestamp(t, '.')),
+                speaker,
+                text,
+                highlight_positions,
+                exact,
+            }
+        })
+        .collect())
+}
@@ -1,12 +1,16 @@
 use crate::services::descript::{
     DescriptUploadService, DescriptProject, UploadRequest, BatchUploadResult, 
-    UploadProgress, ExportInstructions
+    UploadProgress, UploadStatus, ExportInstructions
 };
 use crate::commands::auth::AuthManager;
+use crate::db::Database;
 use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
 use tauri::{AppHandle, Manager, State};
+use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::sync::Mutex;
+use uuid::Uuid;
 
 #[derive(Debug, Clone)]
 pub struct UploadManager {
@@ -56,8 +60,92 @@ pub async fn initialize_upload_service(
     Ok(())
 }
 
+/// Folder Descript transcript exports are saved to, created if missing
+pub(crate) fn export_folder(app: &AppHandle) -> Result<PathBuf, String> {
+    let app_data_dir = app.path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+    
+    let export_folder = app_data_dir.join("transcript_exports");
+    std::fs::create_dir_all(&export_folder)
+        .map_err(|e| format!("Failed to create export folder: {}", e))?;
+    
+    Ok(export_folder)
+}
+
+// Remember which files went into a Descript project, so exports saved to the export folder can
+// be matched back to them
+async fn record_uploads(pool: &SqlitePool, descript_id: &str, result: &BatchUploadResult) -> Result<(), String> {
+    let mut tx = pool.begin()
+        .await
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    
+    // Projects created before uploads were recorded are named after their Descript ID
+    sqlx::query(
+        "INSERT OR IGNORE INTO DescriptProject (ProjectID, ProjectName, DescriptID, Status) VALUES (?1, ?2, ?2, 'Created')"
+    )
+    .bind(Uuid::new_v4().to_string())
+    .bind(descript_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| format!("Failed to record project: {}", e))?;
+    
+    let (project_id,) = sqlx::query_as::<_, (String,)>("SELECT ProjectID FROM DescriptProject WHERE DescriptID = ?1")
+        .bind(descript_id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to fetch project: {}", e))?;
+    
+    for upload in result.upload_results.iter().filter(|r| r.status == UploadStatus::ManualExportRequired) {
+        let file_id = sqlx::query_as::<_, (String,)>("SELECT FileID FROM File WHERE FilePath = ?1")
+            .bind(&upload.file_path)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| format!("Failed to fetch file: {}", e))?;
+        let Some((file_id,)) = file_id else { continue };
+        
+        sqlx::query("INSERT OR IGNORE INTO ProjectFiles (ProjectID, FileID) VALUES (?1, ?2)")
+            .bind(&project_id)
+            .bind(&file_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| format!("Failed to link file to project: {}", e))?;
+        
+        sqlx::query("UPDATE File SET Status = 'Uploaded' WHERE FileID = ?1")
+            .bind(&file_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| format!("Failed to update file status: {}", e))?;
+    }
+    
+    // Waiting on Descript's transcription and the manual export once anything went up; a failed
+    // batch only marks a project that had nothing uploaded yet
+    let status = if result.successful_uploads > 0 { "Processing" } else { "Failed" };
+    sqlx::query(
+        r#"
+        UPDATE DescriptProject
+        SET Status = CASE WHEN ?1 = 'Processing' OR Status = 'Created' THEN ?1 ELSE Status END,
+            FileCount = (SELECT COUNT(*) FROM ProjectFiles WHERE ProjectID = ?2),
+            UploadedAt = CASE WHEN ?1 = 'Processing' THEN CURRENT_TIMESTAMP ELSE UploadedAt END
+        WHERE ProjectID = ?2
+        "#
+    )
+    .bind(status)
+    .bind(&project_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| format!("Failed to update project: {}", e))?;
+    
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit transaction: {}", e))?;
+    
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn create_descript_project(
+    database: State<'_, Arc<Database>>,
     upload_manager: State<'_, UploadManager>,
     request: CreateProjectRequest,
 ) -> Result<DescriptProject, String> {
@@ -65,13 +153,24 @@ pub async fn create_descript_project(
     let service = service_guard.as_ref()
         .ok_or_else(|| "Upload service not initialized".to_string())?;
     
-    service.create_project(request.name)
+    let project = service.create_project(request.name)
+        .await
+        .map_err(|e| format!("Failed to create project: {}", e.message))?;
+    
+    sqlx::query("INSERT OR IGNORE INTO DescriptProject (ProjectID, ProjectName, DescriptID, Status) VALUES (?1, ?2, ?3, 'Created')")
+        .bind(Uuid::new_v4().to_string())
+        .bind(&project.name)
+        .bind(&project.id)
+        .execute(&database.pool)
         .await
-        .map_err(|e| format!("Failed to create project: {}", e.message))
+        .map_err(|e| format!("Failed to record project: {}", e))?;
+    
+    Ok(project)
 }
 
 #[tauri::command]
 pub async fn batch_upload_files(
+    database: State<'_, Arc<Database>>,
     upload_manager: State<'_, UploadManager>,
     request: BatchUploadRequest,
 ) -> Result<BatchUploadResult, String> {
@@ -89,30 +188,23 @@ pub async fn batch_upload_files(
         })
         .collect();
     
-    service.batch_upload(upload_requests)
+    let result = service.batch_upload(upload_requests)
         .await
-        .map_err(|e| format!("Batch upload failed: {}", e.message))
+        .map_err(|e| format!("Batch upload failed: {}", e.message))?;
+    
+    record_uploads(&database.pool, &request.project_id, &result).await?;
+    
+    Ok(result)
 }
 
 #[tauri::command]
 pub async fn get_export_instructions(
     app: AppHandle,
 ) -> Result<ExportInstructions, String> {
-    // Get the default export folder path
-    let app_data_dir = app.path()
-        .app_data_dir()
-        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
-    
-    let export_folder = app_data_dir
-        .join("transcript_exports")
+    let export_folder = export_folder(&app)?
         .to_string_lossy()
         .to_string();
     
-    // Ensure the export folder exists
-    if let Err(e) = std::fs::create_dir_all(&export_folder) {
-        return Err(format!("Failed to create export folder: {}", e));
-    }
-    
     Ok(ExportInstructions::new(export_folder))
 }
 
@@ -120,16 +212,7 @@ pub async fn get_export_instructions(
 pub async fn open_export_folder(
     app: AppHandle,
 ) -> Result<(), String> {
-    let app_data_dir = app.path()
-        .app_data_dir()
-        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
-    
-    let export_folder = app_data_dir.join("transcript_exports");
-    
-    // Ensure the export folder exists
-    if let Err(e) = std::fs::create_dir_all(&export_folder) {
-        return Err(format!("Failed to create export folder: {}", e));
-    }
+    let export_folder = export_folder(&app)?;
     
     // Open the folder in the system file manager
     #[cfg(target_os = "macos")]
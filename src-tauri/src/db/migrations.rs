@@ -1,10 +1,34 @@
-use sqlx::{SqlitePool, Row};
+use sqlx::{Connection, Row, SqliteConnection, SqlitePool};
 
 const MIGRATIONS: &[(&str, &str)] = &[
     ("001_initial_schema", include_str!("migrations/001_initial_schema.sql")),
     ("002_custom_templates", include_str!("migrations/002_custom_templates.sql")),
+    ("003_transcript_speakers", include_str!("migrations/003_transcript_speakers.sql")),
+    ("004_segment_words", include_str!("migrations/004_segment_words.sql")),
+    ("005_transcript_language", include_str!("migrations/005_transcript_language.sql")),
+    ("006_transcript_encoding", include_str!("migrations/006_transcript_encoding.sql")),
+    ("007_transcript_revisions", include_str!("migrations/007_transcript_revisions.sql")),
+    ("008_segment_search", include_str!("migrations/008_segment_search.sql")),
+    ("009_transcript_embeddings", include_str!("migrations/009_transcript_embeddings.sql")),
+    ("010_transcript_summary", include_str!("migrations/010_transcript_summary.sql")),
+    ("011_disfluency_analysis", include_str!("migrations/011_disfluency_analysis.sql")),
+    ("012_content_score_breakdown", include_str!("migrations/012_content_score_breakdown.sql")),
+    ("013_transcript_chapters", include_str!("migrations/013_transcript_chapters.sql")),
+    ("014_tag_taxonomy", include_str!("migrations/014_tag_taxonomy.sql")),
+    ("015_related_transcripts", include_str!("migrations/015_related_transcripts.sql")),
+    ("016_transcript_tracks", include_str!("migrations/016_transcript_tracks.sql")),
+    ("017_redaction", include_str!("migrations/017_redaction.sql")),
+    ("018_export_watch", include_str!("migrations/018_export_watch.sql")),
+    ("019_embedding_index", include_str!("migrations/019_embedding_index.sql")),
+    ("020_related_cache_state", include_str!("migrations/020_related_cache_state.sql")),
+    ("021_redacted_title", include_str!("migrations/021_redacted_title.sql")),
+    ("022_pending_redaction_hits", include_str!("migrations/022_pending_redaction_hits.sql")),
 ];
 
+// Migrations that rebuild a table start with this line. Foreign keys are switched off while they
+// run, since dropping the old table would otherwise cascade deletes into every table referencing it.
+const FOREIGN_KEYS_OFF: &str = "-- foreign_keys: off";
+
 pub async fn run_migrations(pool: &SqlitePool) -> Result<(), Box<dyn std::error::Error>> {
     // Create migrations table if it doesn't exist
     sqlx::query(
@@ -30,53 +54,20 @@ pub async fn run_migrations(pool: &SqlitePool) -> Result<(), Box<dyn std::error:
         if !exists {
             println!("Running migration: {}", id);
             
-            // Execute migration in a transaction
-            let mut tx = pool.begin().await?;
-            
-            // SQLite requires executing statements separately
-            // Split by complete statements, handling CREATE TRIGGER specially
-            let mut statements = Vec::new();
-            let mut current_statement = String::new();
-            let mut in_trigger = false;
-            
-            for line in sql.lines() {
-                let trimmed = line.trim();
-                
-                if trimmed.to_uppercase().starts_with("CREATE TRIGGER") {
-                    in_trigger = true;
-                }
-                
-                current_statement.push_str(line);
-                current_statement.push('\n');
-                
-                if in_trigger && trimmed.to_uppercase() == "END;" {
-                    statements.push(current_statement.trim().to_string());
-                    current_statement.clear();
-                    in_trigger = false;
-                } else if !in_trigger && trimmed.ends_with(';') {
-                    statements.push(current_statement.trim().to_string());
-                    current_statement.clear();
-                }
+            // The pragma has no effect inside a transaction, so it is set on the connection first
+            let mut conn = pool.acquire().await?;
+            let foreign_keys_off = sql.starts_with(FOREIGN_KEYS_OFF);
+            if foreign_keys_off {
+                sqlx::query("PRAGMA foreign_keys = OFF").execute(&mut *conn).await?;
             }
             
-            // Execute each statement
-            for statement in statements {
-                if !statement.is_empty() {
-                    sqlx::query(&statement)
-                        .execute(&mut *tx)
-                        .await
-                        .map_err(|e| format!("Failed to execute statement: {}\nError: {}", 
-                            statement.lines().next().unwrap_or(""), e))?;
-                }
-            }
+            let result = apply_migration(&mut conn, id, sql, foreign_keys_off).await;
             
-            // Record migration as applied
-            sqlx::query("INSERT INTO migrations (id) VALUES (?)")
-                .bind(id)
-                .execute(&mut *tx)
-                .await?;
-            
-            tx.commit().await?;
+            // Switched back on even when the migration failed, so the pooled connection keeps them
+            if foreign_keys_off {
+                sqlx::query("PRAGMA foreign_keys = ON").execute(&mut *conn).await?;
+            }
+            result?;
             
             println!("Migration {} applied successfully", id);
         } else {
@@ -85,4 +76,70 @@ pub async fn run_migrations(pool: &SqlitePool) -> Result<(), Box<dyn std::error:
     }
     
     Ok(())
-}
\ No newline at end of file
+}
+
+// Run one migration's statements and record it, all in one transaction
+async fn apply_migration(
+    conn: &mut SqliteConnection,
+    id: &str,
+    sql: &str,
+    foreign_keys_off: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    // Execute migration in a transaction
+    let mut tx = conn.begin().await?;
+    
+    // SQLite requires executing statements separately
+    // Split by complete statements, handling CREATE TRIGGER specially
+    let mut statements = Vec::new();
+    let mut current_statement = String::new();
+    let mut in_trigger = false;
+    
+    for line in sql.lines() {
+        let trimmed = line.trim();
+        
+        if trimmed.to_uppercase().starts_with("CREATE TRIGGER") {
+            in_trigger = true;
+        }
+        
+        current_statement.push_str(line);
+        current_statement.push('\n');
+        
+        if in_trigger && trimmed.to_uppercase() == "END;" {
+            statements.push(current_statement.trim().to_string());
+            current_statement.clear();
+            in_trigger = false;
+        } else if !in_trigger && trimmed.ends_with(';') {
+            statements.push(current_statement.trim().to_string());
+            current_statement.clear();
+        }
+    }
+    
+    // Execute each statement
+    for statement in statements {
+        if !statement.is_empty() {
+            sqlx::query(&statement)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| format!("Failed to execute statement: {}\nError: {}", 
+                    statement.lines().next().unwrap_or(""), e))?;
+        }
+    }
+    
+    // The rebuilt tables must still satisfy every foreign key
+    if foreign_keys_off {
+        let violations = sqlx::query("PRAGMA foreign_key_check").fetch_all(&mut *tx).await?;
+        if !violations.is_empty() {
+            return Err(format!("Migration {} left {} foreign key violations", id, violations.len()).into());
+        }
+    }
+    
+    // Record migration as applied
+    sqlx::query("INSERT INTO migrations (id) VALUES (?)")
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+    
+    tx.commit().await?;
+    
+    Ok(())
+}
@@ -0,0 +1,39 @@
+-- ContentFlow Transcript Speakers Migration
+-- Version: 3.0
+-- Description: Store timed transcript segments and the speakers found in them
+
+-- Speaker table (one row per distinct speaker label within a transcript)
+CREATE TABLE IF NOT EXISTS Speaker (
+    SpeakerID TEXT PRIMARY KEY,
+    TranscriptID TEXT NOT NULL,
+    Label TEXT NOT NULL, -- Label as it appeared in the source file
+    DisplayName TEXT NOT NULL, -- User-editable name
+    CreatedAt DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UpdatedAt DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (TranscriptID) REFERENCES Transcript(TranscriptID) ON DELETE CASCADE,
+    UNIQUE(TranscriptID, Label)
+);
+
+-- TranscriptSegment table (cues, voice spans or speaker turns in source order)
+CREATE TABLE IF NOT EXISTS TranscriptSegment (
+    SegmentID TEXT PRIMARY KEY,
+    TranscriptID TEXT NOT NULL,
+    SegmentIndex INTEGER NOT NULL,
+    SpeakerID TEXT,
+    StartTime REAL, -- Seconds, NULL for untimed formats
+    EndTime REAL, -- Seconds, NULL for untimed formats
+    Text TEXT NOT NULL,
+    WordCount INTEGER NOT NULL DEFAULT 0,
+    FOREIGN KEY (TranscriptID) REFERENCES Transcript(TranscriptID) ON DELETE CASCADE,
+    FOREIGN KEY (SpeakerID) REFERENCES Speaker(SpeakerID) ON DELETE SET NULL,
+    UNIQUE(TranscriptID, SegmentIndex)
+);
+
+CREATE INDEX IF NOT EXISTS idx_speaker_transcript ON Speaker(TranscriptID);
+CREATE INDEX IF NOT EXISTS idx_segment_transcript ON TranscriptSegment(TranscriptID);
+CREATE INDEX IF NOT EXISTS idx_segment_speaker ON TranscriptSegment(SpeakerID);
+
+CREATE TRIGGER IF NOT EXISTS speaker_updated_at AFTER UPDATE ON Speaker
+BEGIN
+    UPDATE Speaker SET UpdatedAt = CURRENT_TIMESTAMP WHERE SpeakerID = NEW.SpeakerID;
+END;
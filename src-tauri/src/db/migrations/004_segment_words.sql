@@ -0,0 +1,6 @@
+-- ContentFlow Segment Words Migration
+-- Version: 4.0
+-- Description: Keep word-level timings from formats that provide them (Whisper, Descript JSON)
+
+-- Words: JSON array of {text, start_time, end_time, confidence}
+ALTER TABLE TranscriptSegment ADD COLUMN Words TEXT;
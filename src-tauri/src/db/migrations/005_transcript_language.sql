@@ -0,0 +1,8 @@
+-- ContentFlow Transcript Language Migration
+-- Version: 5.0
+-- Description: Record how a transcript's language was determined
+
+ALTER TABLE Transcript ADD COLUMN LanguageConfidence REAL CHECK(LanguageConfidence >= 0 AND LanguageConfidence <= 1);
+ALTER TABLE Transcript ADD COLUMN LanguageSource TEXT NOT NULL DEFAULT 'default' CHECK(LanguageSource IN ('default', 'file', 'detected', 'manual'));
+
+CREATE INDEX IF NOT EXISTS idx_transcript_language ON Transcript(Language);
@@ -0,0 +1,5 @@
+-- ContentFlow Transcript Encoding Migration
+-- Version: 6.0
+-- Description: Record the text encoding detected in the imported transcript file
+
+ALTER TABLE Transcript ADD COLUMN SourceEncoding TEXT;
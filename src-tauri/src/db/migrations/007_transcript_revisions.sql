@@ -0,0 +1,49 @@
+-- ContentFlow Transcript Revisions Migration
+-- Version: 7.0
+-- Description: Revision history for transcript edits, and FTS triggers that handle updates correctly
+
+-- TranscriptRevision table (full snapshot of the text and segments after each edit)
+CREATE TABLE IF NOT EXISTS TranscriptRevision (
+    RevisionID TEXT PRIMARY KEY,
+    TranscriptID TEXT NOT NULL,
+    RevisionNumber INTEGER NOT NULL,
+    Author TEXT NOT NULL,
+    Summary TEXT,
+    Content TEXT NOT NULL,
+    Segments TEXT NOT NULL, -- JSON array of segments, speakers stored by label
+    WordCount INTEGER NOT NULL DEFAULT 0,
+    CreatedAt DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (TranscriptID) REFERENCES Transcript(TranscriptID) ON DELETE CASCADE,
+    UNIQUE(TranscriptID, RevisionNumber)
+);
+
+CREATE INDEX IF NOT EXISTS idx_revision_transcript ON TranscriptRevision(TranscriptID);
+
+-- TranscriptFTS uses Transcript as external content, so rows must be removed with the
+-- 'delete' command and keyed by rowid; a plain UPDATE/DELETE leaves stale tokens behind
+DROP TRIGGER IF EXISTS transcript_fts_insert;
+DROP TRIGGER IF EXISTS transcript_fts_update;
+DROP TRIGGER IF EXISTS transcript_fts_delete;
+
+CREATE TRIGGER IF NOT EXISTS transcript_fts_insert AFTER INSERT ON Transcript
+BEGIN
+    INSERT INTO TranscriptFTS(rowid, TranscriptID, Content, Summary)
+    VALUES (new.rowid, new.TranscriptID, new.Content, new.Summary);
+END;
+
+CREATE TRIGGER IF NOT EXISTS transcript_fts_update AFTER UPDATE OF Content, Summary ON Transcript
+BEGIN
+    INSERT INTO TranscriptFTS(TranscriptFTS, rowid, TranscriptID, Content, Summary)
+    VALUES ('delete', old.rowid, old.TranscriptID, old.Content, old.Summary);
+    INSERT INTO TranscriptFTS(rowid, TranscriptID, Content, Summary)
+    VALUES (new.rowid, new.TranscriptID, new.Content, new.Summary);
+END;
+
+CREATE TRIGGER IF NOT EXISTS transcript_fts_delete AFTER DELETE ON Transcript
+BEGIN
+    INSERT INTO TranscriptFTS(TranscriptFTS, rowid, TranscriptID, Content, Summary)
+    VALUES ('delete', old.rowid, old.TranscriptID, old.Content, old.Summary);
+END;
+
+-- Re-index from the Transcript table to clear anything the old triggers left inconsistent
+INSERT INTO TranscriptFTS(TranscriptFTS) VALUES ('rebuild');
@@ -0,0 +1,28 @@
+-- ContentFlow Segment Search Migration
+-- Version: 8.0
+-- Description: Full-text index over transcript segments for timestamped search hits
+
+CREATE VIRTUAL TABLE IF NOT EXISTS TranscriptSegmentFTS USING fts5(
+    Text,
+    content=TranscriptSegment,
+    content_rowid=rowid
+);
+
+CREATE TRIGGER IF NOT EXISTS segment_fts_insert AFTER INSERT ON TranscriptSegment
+BEGIN
+    INSERT INTO TranscriptSegmentFTS(rowid, Text) VALUES (new.rowid, new.Text);
+END;
+
+CREATE TRIGGER IF NOT EXISTS segment_fts_update AFTER UPDATE OF Text ON TranscriptSegment
+BEGIN
+    INSERT INTO TranscriptSegmentFTS(TranscriptSegmentFTS, rowid, Text) VALUES ('delete', old.rowid, old.Text);
+    INSERT INTO TranscriptSegmentFTS(rowid, Text) VALUES (new.rowid, new.Text);
+END;
+
+CREATE TRIGGER IF NOT EXISTS segment_fts_delete AFTER DELETE ON TranscriptSegment
+BEGIN
+    INSERT INTO TranscriptSegmentFTS(TranscriptSegmentFTS, rowid, Text) VALUES ('delete', old.rowid, old.Text);
+END;
+
+-- Index segments stored before this migration
+INSERT INTO TranscriptSegmentFTS(TranscriptSegmentFTS) VALUES ('rebuild');
@@ -0,0 +1,24 @@
+-- ContentFlow Transcript Embeddings Migration
+-- Version: 9.0
+-- Description: Chunk-level embedding vectors for semantic transcript search
+
+CREATE TABLE IF NOT EXISTS TranscriptChunk (
+    ChunkID TEXT PRIMARY KEY,
+    TranscriptID TEXT NOT NULL,
+    ChunkIndex INTEGER NOT NULL,
+    StartSegment INTEGER,
+    EndSegment INTEGER,
+    StartTime REAL,
+    EndTime REAL,
+    Text TEXT NOT NULL,
+    Model TEXT NOT NULL,
+    Dimensions INTEGER NOT NULL,
+    Vector BLOB NOT NULL,
+    SourceHash TEXT NOT NULL,
+    CreatedAt DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (TranscriptID) REFERENCES Transcript(TranscriptID) ON DELETE CASCADE,
+    UNIQUE(TranscriptID, Model, ChunkIndex)
+);
+
+CREATE INDEX IF NOT EXISTS idx_transcript_chunk_transcript ON TranscriptChunk(TranscriptID);
+CREATE INDEX IF NOT EXISTS idx_transcript_chunk_model ON TranscriptChunk(Model);
@@ -0,0 +1,9 @@
+-- ContentFlow Transcript Summary Migration
+-- Version: 10.0
+-- Description: Key points and summary provenance for offline extractive summaries
+
+-- JSON array of key points with their timestamps
+ALTER TABLE Transcript ADD COLUMN KeyPoints TEXT;
+
+-- 'extractive' when generated locally
+ALTER TABLE Transcript ADD COLUMN SummarySource TEXT;
@@ -0,0 +1,32 @@
+-- ContentFlow Disfluency Analysis Migration
+-- Version: 11.0
+-- Description: Filler word and disfluency counts per segment and speaker, and cleaned transcript text
+
+-- Counts from the last analysis; NULL until analyzed
+ALTER TABLE TranscriptSegment ADD COLUMN FillerCount INTEGER;
+
+-- Repetitions and false starts
+ALTER TABLE TranscriptSegment ADD COLUMN DisfluencyCount INTEGER;
+
+-- Transcript text with fillers, repetitions and false starts removed, for content generation
+ALTER TABLE Transcript ADD COLUMN CleanedContent TEXT;
+
+-- Fillers per 100 words across the whole transcript
+ALTER TABLE Transcript ADD COLUMN FillerDensity REAL;
+
+CREATE TABLE IF NOT EXISTS SpeakerDisfluency (
+    StatID TEXT PRIMARY KEY,
+    TranscriptID TEXT NOT NULL,
+    SpeakerID TEXT, -- NULL for speech without a speaker label
+    WordCount INTEGER NOT NULL DEFAULT 0,
+    FillerCount INTEGER NOT NULL DEFAULT 0,
+    RepetitionCount INTEGER NOT NULL DEFAULT 0,
+    FalseStartCount INTEGER NOT NULL DEFAULT 0,
+    FillerDensity REAL NOT NULL DEFAULT 0,
+    TopFillers TEXT, -- JSON array of [filler, count]
+    AnalyzedAt DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (TranscriptID) REFERENCES Transcript(TranscriptID) ON DELETE CASCADE,
+    FOREIGN KEY (SpeakerID) REFERENCES Speaker(SpeakerID) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_speaker_disfluency_transcript ON SpeakerDisfluency(TranscriptID);
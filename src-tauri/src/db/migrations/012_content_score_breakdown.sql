@@ -0,0 +1,9 @@
+-- ContentFlow Content Score Breakdown Migration
+-- Version: 12.0
+-- Description: Stored breakdown and provenance for transcript content scores
+
+-- JSON breakdown (score version, weighted components, underlying metrics) behind ContentScore
+ALTER TABLE Transcript ADD COLUMN ScoreBreakdown TEXT;
+
+-- 'metrics' when computed locally, 'external' when supplied by the frontend
+ALTER TABLE Transcript ADD COLUMN ScoreSource TEXT;
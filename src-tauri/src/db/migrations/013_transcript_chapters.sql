@@ -0,0 +1,19 @@
+-- ContentFlow Transcript Chapters Migration
+-- Version: 13.0
+-- Description: Topic chapters with start times and titles for long recordings
+
+CREATE TABLE IF NOT EXISTS TranscriptChapter (
+    ChapterID TEXT PRIMARY KEY,
+    TranscriptID TEXT NOT NULL,
+    ChapterIndex INTEGER NOT NULL,
+    StartTime REAL NOT NULL, -- Seconds
+    EndTime REAL NOT NULL,
+    StartSegment INTEGER NOT NULL, -- Segment positions the chapter covers
+    EndSegment INTEGER NOT NULL,
+    Title TEXT NOT NULL,
+    CreatedAt DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (TranscriptID) REFERENCES Transcript(TranscriptID) ON DELETE CASCADE,
+    UNIQUE(TranscriptID, ChapterIndex)
+);
+
+CREATE INDEX IF NOT EXISTS idx_transcript_chapter_transcript ON TranscriptChapter(TranscriptID);
@@ -0,0 +1,19 @@
+-- ContentFlow Tag Taxonomy Migration
+-- Version: 14.0
+-- Description: Parent/child tags and aliases that resolve to a canonical tag
+
+-- Broader tag this one sits under; NULL for top-level tags
+ALTER TABLE Tag ADD COLUMN ParentTagID TEXT REFERENCES Tag(TagID) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_tag_parent ON Tag(ParentTagID);
+
+-- Alternative names; tagging with an alias links the canonical tag instead
+CREATE TABLE IF NOT EXISTS TagAlias (
+    AliasID TEXT PRIMARY KEY,
+    TagID TEXT NOT NULL,
+    Alias TEXT NOT NULL COLLATE NOCASE UNIQUE,
+    CreatedAt DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (TagID) REFERENCES Tag(TagID) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_tag_alias_tag ON TagAlias(TagID);
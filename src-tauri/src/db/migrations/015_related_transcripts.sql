@@ -0,0 +1,20 @@
+-- ContentFlow Related Transcripts Migration
+-- Version: 15.0
+-- Description: Cached related-transcript recommendations
+
+CREATE TABLE IF NOT EXISTS RelatedTranscript (
+    TranscriptID TEXT NOT NULL,
+    RelatedTranscriptID TEXT NOT NULL,
+    Rank INTEGER NOT NULL,
+    Score REAL NOT NULL,
+    TagScore REAL NOT NULL,
+    TextScore REAL NOT NULL,
+    SharedTags TEXT, -- JSON array of tag names
+    CorpusStamp TEXT NOT NULL, -- State of the corpus the ranking was computed from
+    ComputedAt DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (TranscriptID, RelatedTranscriptID),
+    FOREIGN KEY (TranscriptID) REFERENCES Transcript(TranscriptID) ON DELETE CASCADE,
+    FOREIGN KEY (RelatedTranscriptID) REFERENCES Transcript(TranscriptID) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_related_transcript_related ON RelatedTranscript(RelatedTranscriptID);
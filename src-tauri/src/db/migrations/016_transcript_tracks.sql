@@ -0,0 +1,82 @@
+-- foreign_keys: off
+-- ContentFlow Transcript Tracks Migration
+-- Version: 16.0
+-- Description: Several transcript tracks per media file, keyed by language and kind
+
+-- SQLite can't drop the UNIQUE constraint on FileID, so the table is rebuilt. Rowids are kept
+-- because TranscriptFTS indexes Transcript by rowid.
+CREATE TABLE Transcript_new (
+    TranscriptID TEXT PRIMARY KEY,
+    FileID TEXT NOT NULL,
+    Content TEXT NOT NULL,
+    WordCount INTEGER,
+    Language TEXT NOT NULL DEFAULT 'en',
+    ImportedAt DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    AnalyzedAt DATETIME,
+    ContentScore REAL CHECK(ContentScore >= 0 AND ContentScore <= 1),
+    Summary TEXT,
+    UpdatedAt DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    LanguageConfidence REAL CHECK(LanguageConfidence >= 0 AND LanguageConfidence <= 1),
+    LanguageSource TEXT NOT NULL DEFAULT 'default' CHECK(LanguageSource IN ('default', 'file', 'detected', 'manual')),
+    SourceEncoding TEXT,
+    KeyPoints TEXT,
+    SummarySource TEXT,
+    CleanedContent TEXT,
+    FillerDensity REAL,
+    ScoreBreakdown TEXT,
+    ScoreSource TEXT,
+    TrackKind TEXT NOT NULL DEFAULT 'original' CHECK(TrackKind IN ('original', 'translation', 'cleaned')),
+    SourceTranscriptID TEXT, -- Track a translation or cleaned track was made from
+    FOREIGN KEY (FileID) REFERENCES File(FileID) ON DELETE CASCADE,
+    FOREIGN KEY (SourceTranscriptID) REFERENCES Transcript(TranscriptID) ON DELETE SET NULL,
+    UNIQUE(FileID, Language, TrackKind)
+);
+
+INSERT INTO Transcript_new (
+    rowid, TranscriptID, FileID, Content, WordCount, Language, ImportedAt, AnalyzedAt, ContentScore, Summary,
+    UpdatedAt, LanguageConfidence, LanguageSource, SourceEncoding, KeyPoints, SummarySource, CleanedContent,
+    FillerDensity, ScoreBreakdown, ScoreSource
+)
+SELECT
+    rowid, TranscriptID, FileID, Content, WordCount, Language, ImportedAt, AnalyzedAt, ContentScore, Summary,
+    UpdatedAt, LanguageConfidence, LanguageSource, SourceEncoding, KeyPoints, SummarySource, CleanedContent,
+    FillerDensity, ScoreBreakdown, ScoreSource
+FROM Transcript;
+
+DROP TABLE Transcript;
+
+ALTER TABLE Transcript_new RENAME TO Transcript;
+
+CREATE INDEX IF NOT EXISTS idx_transcript_file ON Transcript(FileID);
+CREATE INDEX IF NOT EXISTS idx_transcript_analyzed ON Transcript(AnalyzedAt);
+CREATE INDEX IF NOT EXISTS idx_transcript_score ON Transcript(ContentScore);
+CREATE INDEX IF NOT EXISTS idx_transcript_language ON Transcript(Language);
+CREATE INDEX IF NOT EXISTS idx_transcript_source ON Transcript(SourceTranscriptID);
+
+-- Triggers were dropped with the old table
+CREATE TRIGGER IF NOT EXISTS transcript_updated_at AFTER UPDATE ON Transcript
+BEGIN
+    UPDATE Transcript SET UpdatedAt = CURRENT_TIMESTAMP WHERE TranscriptID = NEW.TranscriptID;
+END;
+
+CREATE TRIGGER IF NOT EXISTS transcript_fts_insert AFTER INSERT ON Transcript
+BEGIN
+    INSERT INTO TranscriptFTS(rowid, TranscriptID, Content, Summary)
+    VALUES (new.rowid, new.TranscriptID, new.Content, new.Summary);
+END;
+
+CREATE TRIGGER IF NOT EXISTS transcript_fts_update AFTER UPDATE OF Content, Summary ON Transcript
+BEGIN
+    INSERT INTO TranscriptFTS(TranscriptFTS, rowid, TranscriptID, Content, Summary)
+    VALUES ('delete', old.rowid, old.TranscriptID, old.Content, old.Summary);
+    INSERT INTO TranscriptFTS(rowid, TranscriptID, Content, Summary)
+    VALUES (new.rowid, new.TranscriptID, new.Content, new.Summary);
+END;
+
+CREATE TRIGGER IF NOT EXISTS transcript_fts_delete AFTER DELETE ON Transcript
+BEGIN
+    INSERT INTO TranscriptFTS(TranscriptFTS, rowid, TranscriptID, Content, Summary)
+    VALUES ('delete', old.rowid, old.TranscriptID, old.Content, old.Summary);
+END;
+
+INSERT INTO TranscriptFTS(TranscriptFTS) VALUES ('rebuild');
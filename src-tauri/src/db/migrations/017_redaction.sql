@@ -0,0 +1,34 @@
+-- ContentFlow Redaction Migration
+-- Version: 17.0
+-- Description: Personal data and brand-safety hits, user blocklist, redacted copies
+
+-- Names and terms that must not be published
+CREATE TABLE IF NOT EXISTS RedactionTerm (
+    TermID TEXT PRIMARY KEY,
+    Term TEXT NOT NULL COLLATE NOCASE UNIQUE,
+    Replacement TEXT, -- Shown instead of the term; "[REDACTED]" when NULL
+    CreatedAt DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- What the last scan of a transcript or generated content found
+CREATE TABLE IF NOT EXISTS RedactionHit (
+    HitID TEXT PRIMARY KEY,
+    TargetType TEXT NOT NULL CHECK(TargetType IN ('transcript', 'content')),
+    TargetID TEXT NOT NULL,
+    Location TEXT, -- JSON pointer to the string within generated content; NULL for transcripts
+    Category TEXT NOT NULL CHECK(Category IN ('email', 'phone', 'url', 'card_number', 'profanity', 'blocklist')),
+    MatchedText TEXT NOT NULL,
+    Replacement TEXT NOT NULL,
+    StartOffset INTEGER NOT NULL, -- UTF-16 offsets into the scanned text
+    EndOffset INTEGER NOT NULL,
+    Status TEXT NOT NULL DEFAULT 'open' CHECK(Status IN ('open', 'redacted', 'ignored')),
+    DetectedAt DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    ResolvedAt DATETIME
+);
+
+CREATE INDEX IF NOT EXISTS idx_redaction_hit_target ON RedactionHit(TargetType, TargetID);
+
+-- Redacted copies, with every hit that wasn't ignored replaced; redacted_data is JSON shaped like content_data
+ALTER TABLE Transcript ADD COLUMN RedactedContent TEXT;
+
+ALTER TABLE GeneratedContent ADD COLUMN redacted_data TEXT;
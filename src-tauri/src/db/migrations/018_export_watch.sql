@@ -0,0 +1,21 @@
+-- ContentFlow Export Watch Migration
+-- Version: 18.0
+-- Description: Transcripts picked up from the Descript export folder
+
+-- One row per export file seen, so files are imported once and failures aren't retried until
+-- the file changes
+CREATE TABLE IF NOT EXISTS ExportImport (
+    FilePath TEXT PRIMARY KEY,
+    FileSize INTEGER NOT NULL,
+    ModifiedAt INTEGER NOT NULL, -- Seconds since the Unix epoch
+    TranscriptID TEXT,
+    ProjectID TEXT, -- Descript project the export was matched to
+    Status TEXT NOT NULL CHECK(Status IN ('imported', 'failed')),
+    Error TEXT,
+    ProcessedAt DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (TranscriptID) REFERENCES Transcript(TranscriptID) ON DELETE SET NULL,
+    FOREIGN KEY (ProjectID) REFERENCES DescriptProject(ProjectID) ON DELETE SET NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_export_import_project ON ExportImport(ProjectID);
+CREATE UNIQUE INDEX IF NOT EXISTS idx_descript_project_descript_id ON DescriptProject(DescriptID);
@@ -0,0 +1,21 @@
+-- ContentFlow Embedding Index Migration
+-- Version: 19.0
+-- Description: Record which transcript text each embedding model has indexed
+
+-- One row per transcript and model. Transcripts too short to produce any chunks are recorded too,
+-- so they aren't re-embedded on every index run.
+CREATE TABLE IF NOT EXISTS TranscriptEmbeddingIndex (
+    TranscriptID TEXT NOT NULL,
+    Model TEXT NOT NULL,
+    SourceHash TEXT NOT NULL, -- SHA-256 of the transcript content that was indexed
+    ChunkCount INTEGER NOT NULL,
+    IndexedAt DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (TranscriptID, Model),
+    FOREIGN KEY (TranscriptID) REFERENCES Transcript(TranscriptID) ON DELETE CASCADE
+);
+
+-- Transcripts already embedded before the index was recorded
+INSERT OR IGNORE INTO TranscriptEmbeddingIndex (TranscriptID, Model, SourceHash, ChunkCount)
+SELECT TranscriptID, Model, MAX(SourceHash), COUNT(*)
+FROM TranscriptChunk
+GROUP BY TranscriptID, Model;
@@ -0,0 +1,46 @@
+-- ContentFlow Related Cache State Migration
+-- Version: 20.0
+-- Description: Track tag changes and record every computed related-transcript ranking, even empty ones
+
+-- Bumped by the triggers below whenever tag links, relevance, names or the hierarchy change
+CREATE TABLE IF NOT EXISTS TagTaxonomyVersion (
+    ID INTEGER PRIMARY KEY CHECK(ID = 1),
+    Version INTEGER NOT NULL DEFAULT 0
+);
+
+INSERT OR IGNORE INTO TagTaxonomyVersion (ID, Version) VALUES (1, 0);
+
+CREATE TRIGGER IF NOT EXISTS transcript_tags_version_insert AFTER INSERT ON TranscriptTags
+BEGIN
+    UPDATE TagTaxonomyVersion SET Version = Version + 1 WHERE ID = 1;
+END;
+
+CREATE TRIGGER IF NOT EXISTS transcript_tags_version_update AFTER UPDATE ON TranscriptTags
+BEGIN
+    UPDATE TagTaxonomyVersion SET Version = Version + 1 WHERE ID = 1;
+END;
+
+CREATE TRIGGER IF NOT EXISTS transcript_tags_version_delete AFTER DELETE ON TranscriptTags
+BEGIN
+    UPDATE TagTaxonomyVersion SET Version = Version + 1 WHERE ID = 1;
+END;
+
+CREATE TRIGGER IF NOT EXISTS tag_version_update AFTER UPDATE OF TagName, ParentTagID ON Tag
+BEGIN
+    UPDATE TagTaxonomyVersion SET Version = Version + 1 WHERE ID = 1;
+END;
+
+CREATE TRIGGER IF NOT EXISTS tag_version_delete AFTER DELETE ON Tag
+BEGIN
+    UPDATE TagTaxonomyVersion SET Version = Version + 1 WHERE ID = 1;
+END;
+
+-- One row per transcript whose ranking has been computed, so transcripts with no matches aren't
+-- recomputed on every request
+CREATE TABLE IF NOT EXISTS RelatedTranscriptState (
+    TranscriptID TEXT PRIMARY KEY,
+    CorpusStamp TEXT NOT NULL, -- State of the corpus the ranking was computed from
+    MatchCount INTEGER NOT NULL,
+    ComputedAt DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (TranscriptID) REFERENCES Transcript(TranscriptID) ON DELETE CASCADE
+);
@@ -0,0 +1,5 @@
+-- ContentFlow Redacted Title Migration
+-- Version: 21.0
+-- Description: Redacted copy of a generated content title, which is scanned alongside content_data
+
+ALTER TABLE GeneratedContent ADD COLUMN redacted_title TEXT;
@@ -0,0 +1,6 @@
+-- ContentFlow Pending Redaction Hits Migration
+-- Version: 22.0
+-- Description: Keep the hits of a content edit the Ready gate rejected, so they can be resolved before it is retried
+
+-- SHA-256 of the rejected title and content_data; NULL for hits in the stored text
+ALTER TABLE RedactionHit ADD COLUMN ProposedHash TEXT;
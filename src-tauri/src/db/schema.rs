@@ -155,6 +155,12 @@ pub struct Transcript {
     pub word_count: Option<i32>,
     #[sqlx(rename = "Language")]
     pub language: String,
+    #[sqlx(rename = "LanguageConfidence")]
+    pub language_confidence: Option<f64>,
+    #[sqlx(rename = "LanguageSource")]
+    pub language_source: String,
+    #[sqlx(rename = "SourceEncoding")]
+    pub source_encoding: Option<String>,
     #[sqlx(rename = "ImportedAt")]
     pub imported_at: DateTime<Utc>,
     #[sqlx(rename = "AnalyzedAt")]
@@ -163,10 +169,278 @@ pub struct Transcript {
     pub content_score: Option<f32>,
     #[sqlx(rename = "Summary")]
     pub summary: Option<String>,
+    #[sqlx(rename = "KeyPoints")]
+    pub key_points: Option<String>, // JSON
+    #[sqlx(rename = "SummarySource")]
+    pub summary_source: Option<String>,
+    #[sqlx(rename = "CleanedContent")]
+    pub cleaned_content: Option<String>,
+    #[sqlx(rename = "FillerDensity")]
+    pub filler_density: Option<f64>,
+    #[sqlx(rename = "ScoreBreakdown")]
+    pub score_breakdown: Option<String>, // JSON
+    #[sqlx(rename = "ScoreSource")]
+    pub score_source: Option<String>,
+    #[sqlx(rename = "TrackKind")]
+    pub track_kind: String, // "original", "translation" or "cleaned"
+    #[sqlx(rename = "SourceTranscriptID")]
+    pub source_transcript_id: Option<String>,
+    #[sqlx(rename = "RedactedContent")]
+    pub redacted_content: Option<String>,
     #[sqlx(rename = "UpdatedAt")]
     pub updated_at: DateTime<Utc>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Speaker {
+    #[sqlx(rename = "SpeakerID")]
+    pub speaker_id: String,
+    #[sqlx(rename = "TranscriptID")]
+    pub transcript_id: String,
+    #[sqlx(rename = "Label")]
+    pub label: String,
+    #[sqlx(rename = "DisplayName")]
+    pub display_name: String,
+    #[sqlx(rename = "CreatedAt")]
+    pub created_at: DateTime<Utc>,
+    #[sqlx(rename = "UpdatedAt")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TranscriptSegment {
+    #[sqlx(rename = "SegmentID")]
+    pub segment_id: String,
+    #[sqlx(rename = "TranscriptID")]
+    pub transcript_id: String,
+    #[sqlx(rename = "SegmentIndex")]
+    pub segment_index: i32,
+    #[sqlx(rename = "SpeakerID")]
+    pub speaker_id: Option<String>,
+    #[sqlx(rename = "StartTime")]
+    pub start_time: Option<f64>,
+    #[sqlx(rename = "EndTime")]
+    pub end_time: Option<f64>,
+    #[sqlx(rename = "Text")]
+    pub text: String,
+    #[sqlx(rename = "WordCount")]
+    pub word_count: i32,
+    #[sqlx(rename = "Words")]
+    pub words: Option<String>, // JSON
+    #[sqlx(rename = "FillerCount")]
+    pub filler_count: Option<i32>,
+    #[sqlx(rename = "DisfluencyCount")]
+    pub disfluency_count: Option<i32>, // Repetitions and false starts
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TranscriptRevision {
+    #[sqlx(rename = "RevisionID")]
+    pub revision_id: String,
+    #[sqlx(rename = "TranscriptID")]
+    pub transcript_id: String,
+    #[sqlx(rename = "RevisionNumber")]
+    pub revision_number: i32,
+    #[sqlx(rename = "Author")]
+    pub author: String,
+    #[sqlx(rename = "Summary")]
+    pub summary: Option<String>,
+    #[sqlx(rename = "Content")]
+    pub content: String,
+    #[sqlx(rename = "Segments")]
+    pub segments: String, // JSON
+    #[sqlx(rename = "WordCount")]
+    pub word_count: i32,
+    #[sqlx(rename = "CreatedAt")]
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TranscriptChunk {
+    #[sqlx(rename = "ChunkID")]
+    pub chunk_id: String,
+    #[sqlx(rename = "TranscriptID")]
+    pub transcript_id: String,
+    #[sqlx(rename = "ChunkIndex")]
+    pub chunk_index: i32,
+    #[sqlx(rename = "StartSegment")]
+    pub start_segment: Option<i32>, // None for transcripts stored without segments
+    #[sqlx(rename = "EndSegment")]
+    pub end_segment: Option<i32>,
+    #[sqlx(rename = "StartTime")]
+    pub start_time: Option<f64>,
+    #[sqlx(rename = "EndTime")]
+    pub end_time: Option<f64>,
+    #[sqlx(rename = "Text")]
+    pub text: String,
+    #[sqlx(rename = "Model")]
+    pub model: String,
+    #[sqlx(rename = "Dimensions")]
+    pub dimensions: i32,
+    #[sqlx(rename = "Vector")]
+    pub vector: Vec<u8>, // Little-endian f32
+    #[sqlx(rename = "SourceHash")]
+    pub source_hash: String,
+    #[sqlx(rename = "CreatedAt")]
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TranscriptEmbeddingIndex {
+    #[sqlx(rename = "TranscriptID")]
+    pub transcript_id: String,
+    #[sqlx(rename = "Model")]
+    pub model: String,
+    #[sqlx(rename = "SourceHash")]
+    pub source_hash: String,
+    #[sqlx(rename = "ChunkCount")]
+    pub chunk_count: i32, // Zero for transcripts with no text to embed
+    #[sqlx(rename = "IndexedAt")]
+    pub indexed_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct SpeakerDisfluency {
+    #[sqlx(rename = "StatID")]
+    pub stat_id: String,
+    #[sqlx(rename = "TranscriptID")]
+    pub transcript_id: String,
+    #[sqlx(rename = "SpeakerID")]
+    pub speaker_id: Option<String>,
+    #[sqlx(rename = "WordCount")]
+    pub word_count: i32,
+    #[sqlx(rename = "FillerCount")]
+    pub filler_count: i32,
+    #[sqlx(rename = "RepetitionCount")]
+    pub repetition_count: i32,
+    #[sqlx(rename = "FalseStartCount")]
+    pub false_start_count: i32,
+    #[sqlx(rename = "FillerDensity")]
+    pub filler_density: f64,
+    #[sqlx(rename = "TopFillers")]
+    pub top_fillers: Option<String>, // JSON
+    #[sqlx(rename = "AnalyzedAt")]
+    pub analyzed_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TranscriptChapter {
+    #[sqlx(rename = "ChapterID")]
+    pub chapter_id: String,
+    #[sqlx(rename = "TranscriptID")]
+    pub transcript_id: String,
+    #[sqlx(rename = "ChapterIndex")]
+    pub chapter_index: i32,
+    #[sqlx(rename = "StartTime")]
+    pub start_time: f64,
+    #[sqlx(rename = "EndTime")]
+    pub end_time: f64,
+    #[sqlx(rename = "StartSegment")]
+    pub start_segment: i32,
+    #[sqlx(rename = "EndSegment")]
+    pub end_segment: i32,
+    #[sqlx(rename = "Title")]
+    pub title: String,
+    #[sqlx(rename = "CreatedAt")]
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct RelatedTranscript {
+    #[sqlx(rename = "TranscriptID")]
+    pub transcript_id: String,
+    #[sqlx(rename = "RelatedTranscriptID")]
+    pub related_transcript_id: String,
+    #[sqlx(rename = "Rank")]
+    pub rank: i32,
+    #[sqlx(rename = "Score")]
+    pub score: f64,
+    #[sqlx(rename = "TagScore")]
+    pub tag_score: f64,
+    #[sqlx(rename = "TextScore")]
+    pub text_score: f64,
+    #[sqlx(rename = "SharedTags")]
+    pub shared_tags: Option<String>, // JSON
+    #[sqlx(rename = "CorpusStamp")]
+    pub corpus_stamp: String,
+    #[sqlx(rename = "ComputedAt")]
+    pub computed_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct RelatedTranscriptState {
+    #[sqlx(rename = "TranscriptID")]
+    pub transcript_id: String,
+    #[sqlx(rename = "CorpusStamp")]
+    pub corpus_stamp: String,
+    #[sqlx(rename = "MatchCount")]
+    pub match_count: i32, // Zero when nothing else is related
+    #[sqlx(rename = "ComputedAt")]
+    pub computed_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct RedactionTerm {
+    #[sqlx(rename = "TermID")]
+    pub term_id: String,
+    #[sqlx(rename = "Term")]
+    pub term: String,
+    #[sqlx(rename = "Replacement")]
+    pub replacement: Option<String>,
+    #[sqlx(rename = "CreatedAt")]
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct RedactionHit {
+    #[sqlx(rename = "HitID")]
+    pub hit_id: String,
+    #[sqlx(rename = "TargetType")]
+    pub target_type: String, // "transcript" or "content"
+    #[sqlx(rename = "TargetID")]
+    pub target_id: String,
+    #[sqlx(rename = "Location")]
+    pub location: Option<String>,
+    #[sqlx(rename = "Category")]
+    pub category: String,
+    #[sqlx(rename = "MatchedText")]
+    pub matched_text: String,
+    #[sqlx(rename = "Replacement")]
+    pub replacement: String,
+    #[sqlx(rename = "StartOffset")]
+    pub start_offset: i32,
+    #[sqlx(rename = "EndOffset")]
+    pub end_offset: i32,
+    #[sqlx(rename = "Status")]
+    pub status: String, // "open", "redacted" or "ignored"
+    #[sqlx(rename = "DetectedAt")]
+    pub detected_at: DateTime<Utc>,
+    #[sqlx(rename = "ResolvedAt")]
+    pub resolved_at: Option<DateTime<Utc>>,
+    #[sqlx(rename = "ProposedHash")]
+    pub proposed_hash: Option<String>, // Set for hits in a content edit that was rejected
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ExportImport {
+    #[sqlx(rename = "FilePath")]
+    pub file_path: String,
+    #[sqlx(rename = "FileSize")]
+    pub file_size: i64,
+    #[sqlx(rename = "ModifiedAt")]
+    pub modified_at: i64, // Seconds since the Unix epoch
+    #[sqlx(rename = "TranscriptID")]
+    pub transcript_id: Option<String>,
+    #[sqlx(rename = "ProjectID")]
+    pub project_id: Option<String>,
+    #[sqlx(rename = "Status")]
+    pub status: String, // "imported" or "failed"
+    #[sqlx(rename = "Error")]
+    pub error: Option<String>,
+    #[sqlx(rename = "ProcessedAt")]
+    pub processed_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct Tag {
     #[sqlx(rename = "TagID")]
@@ -175,12 +449,26 @@ pub struct Tag {
     pub tag_name: String,
     #[sqlx(rename = "Category")]
     pub category: Option<String>,
+    #[sqlx(rename = "ParentTagID")]
+    pub parent_tag_id: Option<String>,
     #[sqlx(rename = "CreatedAt")]
     pub created_at: DateTime<Utc>,
     #[sqlx(rename = "UpdatedAt")]
     pub updated_at: DateTime<Utc>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TagAlias {
+    #[sqlx(rename = "AliasID")]
+    pub alias_id: String,
+    #[sqlx(rename = "TagID")]
+    pub tag_id: String,
+    #[sqlx(rename = "Alias")]
+    pub alias: String,
+    #[sqlx(rename = "CreatedAt")]
+    pub created_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct TranscriptTags {
     #[sqlx(rename = "TranscriptID")]
@@ -221,6 +509,10 @@ pub struct GeneratedContent {
     pub content_data: String, // JSON
     #[sqlx(rename = "Status")]
     pub status: ContentStatus,
+    #[sqlx(rename = "redacted_data")]
+    pub redacted_data: Option<String>, // JSON
+    #[sqlx(rename = "redacted_title")]
+    pub redacted_title: Option<String>,
     #[sqlx(rename = "CreatedAt")]
     pub created_at: DateTime<Utc>,
     #[sqlx(rename = "UpdatedAt")]
@@ -344,10 +636,22 @@ impl Default for Transcript {
             content: String::new(),
             word_count: None,
             language: "en".to_string(),
+            language_confidence: None,
+            language_source: "default".to_string(),
+            source_encoding: None,
             imported_at: Utc::now(),
             analyzed_at: None,
             content_score: None,
             summary: None,
+            key_points: None,
+            summary_source: None,
+            cleaned_content: None,
+            filler_density: None,
+            score_breakdown: None,
+            score_source: None,
+            track_kind: "original".to_string(),
+            source_transcript_id: None,
+            redacted_content: None,
             updated_at: Utc::now(),
         }
     }
@@ -359,6 +663,7 @@ impl Default for Tag {
             tag_id: Uuid::new_v4().to_string(),
             tag_name: String::new(),
             category: None,
+            parent_tag_id: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
         }
@@ -387,6 +692,8 @@ impl Default for GeneratedContent {
             title: None,
             content_data: "{}".to_string(),
             status: ContentStatus::Draft,
+            redacted_data: None,
+            redacted_title: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
         }
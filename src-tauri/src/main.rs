@@ -11,12 +11,39 @@ use commands::files::{validate_video_files, import_video_files, import_and_organ
 use commands::transcripts::{
     validate_transcript_files, import_transcript_files, get_imported_transcripts, 
     get_transcript_count, get_transcript_by_id, search_transcripts,
-    extract_and_store_tags, get_transcript_tags, get_all_tags // Task #14
+    extract_and_store_tags, get_transcript_tags, get_all_tags, // Task #14
+    set_transcript_language, detect_transcript_language
 };
+use commands::transcript_export::export_transcript;
+use commands::transcript_links::{suggest_transcript_links, link_transcript_to_file, auto_link_transcripts};
+use commands::transcript_merge::{merge_transcripts, split_transcript};
+use commands::transcript_tracks::{get_transcript_tracks, resolve_transcript_track, set_transcript_track_kind, create_cleaned_track};
+use commands::transcript_revisions::{
+    update_transcript, update_transcript_segment, get_transcript_revisions,
+    diff_transcript_revisions, restore_transcript_revision
+};
+use commands::deletion::{delete_file, delete_transcript, delete_orphaned_placeholder_files};
+use commands::related::get_related_transcripts;
+use commands::redaction::{
+    scan_transcript_redactions, scan_content_redactions, get_redaction_report, resolve_redaction_hit,
+    get_redaction_terms, add_redaction_term, remove_redaction_term
+};
+use commands::semantic_search::{index_transcript_embeddings, semantic_search_transcripts};
+use commands::transcript_summaries::{summarize_transcript, summarize_missing_transcripts};
+use commands::auto_tags::extract_tags_offline;
+use commands::tags::{get_tag_tree, set_tag_parent, add_tag_alias, remove_tag_alias, merge_tags};
+use commands::chapters::{generate_transcript_chapters, get_transcript_chapters, rename_chapter, export_chapters};
+use commands::clips::find_clip_candidates;
+use commands::content_scores::{score_transcript, score_unscored_transcripts, get_content_score_breakdown};
+use commands::disfluency::{analyze_transcript_disfluencies, get_speaker_disfluency_stats, get_cleaned_transcript};
+use commands::speakers::{get_transcript_speakers, rename_speaker, get_speaker_text};
+use commands::export_watch::{start_export_watcher, scan_export_folder, get_export_imports};
 use commands::upload::{UploadManager, initialize_upload_service, create_descript_project, batch_upload_files, get_export_instructions, open_export_folder, get_upload_status, cancel_upload};
 use commands::settings::SettingsState;
 use commands::templates::{get_all_templates, get_template, create_template, update_template, delete_template};
 use commands::content::{save_generated_content, get_all_content, get_content_by_id, update_content, delete_content, search_content};
+use commands::content_versions::{get_content_versions, get_content_version, diff_content_versions, restore_content_version};
+use commands::content_export::{export_content, preview_content_export, get_export_history};
 
 #[tauri::command]
 fn greet(name: &str) -> String {
@@ -50,7 +77,11 @@ fn main() {
             });
             
             // Store database in app state
-            app.manage(Arc::new(database));
+            let database = Arc::new(database);
+            app.manage(database.clone());
+            
+            // Import transcripts saved to the Descript export folder
+            start_export_watcher(handle.clone(), database);
             
             Ok(())
         })
@@ -78,6 +109,74 @@ fn main() {
             extract_and_store_tags, // Task #14
             get_transcript_tags, // Task #14
             get_all_tags, // Task #14
+            extract_tags_offline,
+            set_transcript_language,
+            detect_transcript_language,
+            // Tag taxonomy
+            get_tag_tree,
+            set_tag_parent,
+            add_tag_alias,
+            remove_tag_alias,
+            merge_tags,
+            // Speaker commands
+            get_transcript_speakers,
+            rename_speaker,
+            get_speaker_text,
+            // Transcript export
+            export_transcript,
+            // Transcript linking
+            suggest_transcript_links,
+            link_transcript_to_file,
+            auto_link_transcripts,
+            // Transcript editing
+            update_transcript,
+            update_transcript_segment,
+            get_transcript_revisions,
+            diff_transcript_revisions,
+            restore_transcript_revision,
+            // Transcript merging
+            merge_transcripts,
+            split_transcript,
+            // Transcript tracks
+            get_transcript_tracks,
+            resolve_transcript_track,
+            set_transcript_track_kind,
+            create_cleaned_track,
+            // Summaries
+            summarize_transcript,
+            summarize_missing_transcripts,
+            // Disfluency analysis
+            analyze_transcript_disfluencies,
+            get_speaker_disfluency_stats,
+            get_cleaned_transcript,
+            // Content scores
+            score_transcript,
+            score_unscored_transcripts,
+            get_content_score_breakdown,
+            // Clip mining
+            find_clip_candidates,
+            // Chapters
+            generate_transcript_chapters,
+            get_transcript_chapters,
+            rename_chapter,
+            export_chapters,
+            // Deletion
+            delete_file,
+            delete_transcript,
+            delete_orphaned_placeholder_files,
+            // Related transcripts
+            get_related_transcripts,
+            // Redaction
+            scan_transcript_redactions,
+            scan_content_redactions,
+            get_redaction_report,
+            resolve_redaction_hit,
+            get_redaction_terms,
+            add_redaction_term,
+            remove_redaction_term,
+            // Semantic search
+            index_transcript_embeddings,
+            semantic_search_transcripts,
             // Auth commands
             initiate_auth,
             handle_auth_callback,
@@ -93,6 +192,9 @@ fn main() {
             open_export_folder,
             get_upload_status,
             cancel_upload,
+            // Descript export folder
+            scan_export_folder,
+            get_export_imports,
             // Settings commands
             commands::settings::get_settings,
             commands::settings::update_api_key,
@@ -100,6 +202,7 @@ fn main() {
             commands::settings::update_preferences,
             commands::settings::update_file_organization,
             commands::settings::update_brand_settings,
+            commands::settings::update_embedding_settings,
             commands::settings::update_usage_stats,
             commands::settings::verify_api_key,
             // Template commands
@@ -114,7 +217,16 @@ fn main() {
             get_content_by_id,
             update_content,
             delete_content,
-            search_content
+            search_content,
+            // Content versions
+            get_content_versions,
+            get_content_version,
+            diff_content_versions,
+            restore_content_version,
+            // Content export
+            export_content,
+            preview_content_export,
+            get_export_history
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -0,0 +1,247 @@
+// Caption export: re-segments timed transcript segments into caption cues and renders them
+use serde::{Deserialize, Serialize};
+
+use super::transcript_parser::{ParsedSegment, ParsedWord};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CaptionRules {
+    pub max_chars_per_line: usize,
+    pub max_lines_per_cue: usize,
+    pub min_cue_duration: f64, // Seconds
+    pub max_cue_duration: f64, // Seconds
+}
+
+impl Default for CaptionRules {
+    fn default() -> Self {
+        // Common broadcast/platform guidance: 42 chars x 2 lines, 1-7 seconds on screen
+        Self {
+            max_chars_per_line: 42,
+            max_lines_per_cue: 2,
+            min_cue_duration: 1.0,
+            max_cue_duration: 7.0,
+        }
+    }
+}
+
+impl CaptionRules {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.max_chars_per_line < 10 {
+            return Err("Max characters per line must be at least 10".to_string());
+        }
+        if self.max_lines_per_cue == 0 {
+            return Err("Max lines per cue must be at least 1".to_string());
+        }
+        if self.min_cue_duration < 0.0 || self.max_cue_duration <= self.min_cue_duration {
+            return Err("Max cue duration must be greater than min cue duration".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CaptionCue {
+    pub start_time: f64,
+    pub end_time: f64,
+    pub speaker: Option<String>,
+    pub lines: Vec<String>,
+}
+
+/// Split timed segments into cues that respect the caption rules.
+/// Cues never span a speaker change and close early at sentence ends once they are long enough.
+pub fn resegment(segments: &[ParsedSegment], rules: &CaptionRules) -> Result<Vec<CaptionCue>, String> {
+    let mut cues: Vec<CaptionCue> = Vec::new();
+
+    for segment in segments {
+        let (Some(start), Some(end)) = (segment.start_time, segment.end_time) else {
+            return Err("Transcript has no timing information; export it as TXT or JSON instead".to_string());
+        };
+
+        let words = timed_words(segment, start, end);
+        let mut current: Option<CaptionCue> = None;
+
+        for word in words {
+            let word_start = word.start_time.unwrap_or(start);
+            let word_end = word.end_time.unwrap_or(word_start);
+
+            if let Some(cue) = current.as_mut() {
+                let too_long = word_end - cue.start_time > rules.max_cue_duration;
+                if too_long || !try_append(&mut cue.lines, &word.text, rules) {
+                    cues.extend(current.take());
+                }
+            }
+
+            let cue = current.get_or_insert_with(|| CaptionCue {
+                start_time: word_start,
+                end_time: word_end,
+                speaker: segment.speaker.clone(),
+                lines: Vec::new(),
+            });
+
+            if cue.lines.is_empty() {
+                cue.lines.push(word.text.clone());
+            }
+            cue.end_time = word_end.max(cue.end_time);
+
+            let sentence_end = word.text.ends_with(['.', '?', '!']);
+            if sentence_end && cue.end_time - cue.start_time >= rules.min_cue_duration {
+                cues.extend(current.take());
+            }
+        }
+
+        cues.extend(current);
+    }
+
+    enforce_min_duration(&mut cues, rules.min_cue_duration);
+    Ok(cues)
+}
+
+// Append a word to the cue's lines, wrapping onto a new line if needed; false when the cue is full
+fn try_append(lines: &mut Vec<String>, word: &str, rules: &CaptionRules) -> bool {
+    let Some(last) = lines.last_mut() else {
+        lines.push(word.to_string());
+        return true;
+    };
+
+    if last.chars().count() + 1 + word.chars().count() <= rules.max_chars_per_line {
+        last.push(' ');
+        last.push_str(word);
+        true
+    } else if lines.len() < rules.max_lines_per_cue {
+        lines.push(word.to_string());
+        true
+    } else {
+        false
+    }
+}
+
+// Use word timings when present, otherwise spread the segment's duration by word length
+fn timed_words(segment: &ParsedSegment, start: f64, end: f64) -> Vec<ParsedWord> {
+    if !segment.words.is_empty() && segment.words.iter().all(|w| w.start_time.is_some()) {
+        return segment.words.clone();
+    }
+
+    let words: Vec<&str> = segment.text.split_whitespace().collect();
+    let total_chars: usize = words.iter().map(|w| w.chars().count()).sum();
+    let duration = (end - start).max(0.0);
+    let mut cursor = start;
+
+    words
+        .into_iter()
+        .map(|text| {
+            let share = if total_chars > 0 {
+                text.chars().count() as f64 / total_chars as f64
+            } else {
+                0.0
+            };
+            let word_start = cursor;
+            cursor += duration * share;
+            ParsedWord {
+                text: text.to_string(),
+                start_time: Some(word_start),
+                end_time: Some(cursor),
+                confidence: None,
+            }
+        })
+        .collect()
+}
+
+// Stretch short cues up to the minimum duration without overlapping the next cue
+fn enforce_min_duration(cues: &mut [CaptionCue], min_duration: f64) {
+    for i in 0..cues.len() {
+        if cues[i].end_time - cues[i].start_time >= min_duration {
+            continue;
+        }
+        let target = cues[i].start_time + min_duration;
+        let limit = cues.get(i + 1).map_or(target, |next| next.start_time);
+        cues[i].end_time = target.min(limit).max(cues[i].end_time);
+    }
+}
+
+/// Format seconds as "HH:MM:SS,mmm" (SRT) or "HH:MM:SS.mmm" (VTT)
+pub fn format_timestamp(seconds: f64, separator: char) -> String {
+    let total_ms = (seconds.max(0.0) * 1000.0).round() as u64;
+    format!(
+        "{:02}:{:02}:{:02}{}{:03}",
+        total_ms / 3_600_000,
+        (total_ms / 60_000) % 60,
+        (total_ms / 1000) % 60,
+        separator,
+        total_ms % 1000
+    )
+}
+
+/// SRT has no voice markup, so the speaker name is prefixed only when the speaker changes
+pub fn render_srt(cues: &[CaptionCue], include_speakers: bool) -> String {
+    let mut output = String::new();
+    let mut previous_speaker: Option<&String> = None;
+
+    for (index, cue) in cues.iter().enumerate() {
+        output.push_str(&format!(
+            "{}\n{} --> {}\n",
+            index + 1,
+            format_timestamp(cue.start_time, ','),
+            format_timestamp(cue.end_time, ',')
+        ));
+
+        let mut lines = cue.lines.clone();
+        if let (true, Some(speaker), Some(first)) = (include_speakers, &cue.speaker, lines.first_mut()) {
+            if previous_speaker != Some(speaker) {
+                *first = format!("{}: {}", speaker, first);
+            }
+        }
+        previous_speaker = cue.speaker.as_ref();
+        output.push_str(&lines.join("\n"));
+        output.push_str("\n\n");
+    }
+
+    output
+}
+
+/// Escape cue text for WebVTT, where "<" opens a cue tag and "&" a character reference
+pub fn escape_vtt_text(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+pub fn render_vtt(cues: &[CaptionCue], include_speakers: bool) -> String {
+    let mut output = String::from("WEBVTT\n\n");
+
+    for cue in cues {
+        output.push_str(&format!(
+            "{} --> {}\n",
+            format_timestamp(cue.start_time, '.'),
+            format_timestamp(cue.end_time, '.')
+        ));
+
+        let text = escape_vtt_text(&cue.lines.join("\n"));
+        match (&cue.speaker, include_speakers) {
+            (Some(speaker), true) => output.push_str(&format!("<v {}>{}", escape_vtt_text(speaker), text)),
+            _ => output.push_str(&text),
+        }
+        output.push_str("\n\n");
+    }
+
+    output
+}
+
+/// Plain text with one paragraph per speaker turn
+pub fn render_txt(segments: &[ParsedSegment], include_speakers: bool) -> String {
+    let mut paragraphs: Vec<(Option<&String>, Vec<&str>)> = Vec::new();
+
+    for segment in segments {
+        match paragraphs.last_mut() {
+            Some((speaker, texts)) if *speaker == segment.speaker.as_ref() => texts.push(&segment.text),
+            _ => paragraphs.push((segment.speaker.as_ref(), vec![&segment.text])),
+        }
+    }
+
+    paragraphs
+        .into_iter()
+        .map(|(speaker, texts)| match (speaker, include_speakers) {
+            (Some(speaker), true) => format!("{}: {}", speaker, texts.join(" ")),
+            _ => texts.join(" "),
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+        + "\n"
+}
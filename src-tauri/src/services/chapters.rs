@@ -0,0 +1,288 @@
+// Chapters for long recordings: TextTiling topic segmentation over timed transcript segments
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::caption_export::{escape_vtt_text, format_timestamp};
+use super::keywords::{extract_keywords, CorpusStats};
+use super::stopwords::content_words;
+use super::transcript_parser::ParsedSegment;
+
+// Content words per token sequence, and sequences per comparison block (Hearst's w and k)
+const SEQUENCE_WORDS: usize = 20;
+const BLOCK_SEQUENCES: usize = 6;
+// A boundary that lands mid-sentence moves forward at most this many segments to a sentence start
+const MAX_BOUNDARY_SHIFT: usize = 3;
+// Keywords considered for a chapter title
+const TITLE_CANDIDATES: usize = 10;
+// YouTube ignores chapter lists with chapters shorter than this
+const YOUTUBE_MIN_CHAPTER_SECONDS: f64 = 10.0;
+
+#[derive(Debug, Clone)]
+pub struct ChapterOptions {
+    pub min_chapter_seconds: f64,
+    pub max_chapters: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Chapter {
+    pub start_time: f64,
+    pub end_time: f64,
+    pub first_segment: usize, // Positions in the transcript's segment order
+    pub last_segment: usize,
+    pub title: String,
+}
+
+// Sequence of content words and the segment each word came from
+struct TokenSequence {
+    words: Vec<String>,
+    first_segment: usize,
+}
+
+fn token_sequences(segments: &[ParsedSegment], language: &str) -> Vec<TokenSequence> {
+    let mut sequences = Vec::new();
+    let mut current = TokenSequence { words: Vec::new(), first_segment: 0 };
+
+    for (index, segment) in segments.iter().enumerate() {
+        for word in content_words(language, &segment.text) {
+            if current.words.is_empty() {
+                current.first_segment = index;
+            }
+            current.words.push(word);
+            if current.words.len() == SEQUENCE_WORDS {
+                sequences.push(std::mem::replace(
+                    &mut current,
+                    TokenSequence { words: Vec::new(), first_segment: index },
+                ));
+            }
+        }
+    }
+    if !current.words.is_empty() {
+        sequences.push(current);
+    }
+
+    sequences
+}
+
+fn block_vector(sequences: &[TokenSequence]) -> HashMap<&str, f64> {
+    let mut vector = HashMap::new();
+    for word in sequences.iter().flat_map(|s| &s.words) {
+        *vector.entry(word.as_str()).or_insert(0.0) += 1.0;
+    }
+    vector
+}
+
+fn cosine(a: &HashMap<&str, f64>, b: &HashMap<&str, f64>) -> f64 {
+    let dot: f64 = a.iter().filter_map(|(w, x)| b.get(w).map(|y| x * y)).sum();
+    let norm_a = a.values().map(|x| x * x).sum::<f64>().sqrt();
+    let norm_b = b.values().map(|x| x * x).sum::<f64>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a * norm_b)
+    }
+}
+
+// Depth of each gap's cohesion valley: how far it sits below the nearest peaks on both sides
+fn depth_scores(cohesion: &[f64]) -> Vec<f64> {
+    (0..cohesion.len())
+        .map(|i| {
+            let mut left = cohesion[i];
+            for &score in cohesion[..i].iter().rev() {
+                if score < left {
+                    break;
+                }
+                left = score;
+            }
+            let mut right = cohesion[i];
+            for &score in &cohesion[i + 1..] {
+                if score < right {
+                    break;
+                }
+                right = score;
+            }
+            (left - cohesion[i]) + (right - cohesion[i])
+        })
+        .collect()
+}
+
+fn starts_sentence(segments: &[ParsedSegment], index: usize) -> bool {
+    index == 0
+        || segments[index].speaker != segments[index - 1].speaker
+        || segments[index - 1]
+            .text
+            .trim_end()
+            .trim_end_matches(['"', '\'', '”', '’', ')'])
+            .ends_with(['.', '!', '?', '…'])
+}
+
+fn title_case(phrase: &str) -> String {
+    phrase
+        .split(' ')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Split a timed transcript into chapters with TextTiling (Hearst, 1997): lexical cohesion is
+/// measured between adjacent blocks of content words, and boundaries go where it dips deepest.
+/// Boundaries are moved to segment starts (sentence starts where possible), kept at least
+/// `min_chapter_seconds` apart, and capped at `max_chapters`. Titles are the most recurring of
+/// each chapter's top keywords, ranked against the other chapters. Returns a single chapter for transcripts too
+/// short or uniform to split, and nothing for untimed ones.
+pub fn generate_chapters(segments: &[ParsedSegment], language: &str, options: &ChapterOptions) -> Vec<Chapter> {
+    let positions: Vec<usize> = (0..segments.len())
+        .filter(|&i| segments[i].start_time.is_some() && segments[i].end_time.is_some())
+        .collect();
+    let timed: Vec<ParsedSegment> = positions.iter().map(|&i| segments[i].clone()).collect();
+    let (Some(first), Some(last)) = (timed.first(), timed.last()) else {
+        return Vec::new();
+    };
+    let recording_start = first.start_time.unwrap_or(0.0);
+    let recording_end = last.end_time.unwrap_or(recording_start);
+    let min_chapter_seconds = options.min_chapter_seconds.max(YOUTUBE_MIN_CHAPTER_SECONDS);
+
+    let sequences = token_sequences(&timed, language);
+    let mut boundaries: Vec<usize> = Vec::new(); // Segment positions where a chapter starts
+
+    if sequences.len() > 2 * BLOCK_SEQUENCES {
+        // Cohesion at each gap between sequences, then depth of each valley
+        let cohesion: Vec<f64> = (1..sequences.len())
+            .map(|gap| {
+                let left = &sequences[gap.saturating_sub(BLOCK_SEQUENCES)..gap];
+                let right = &sequences[gap..(gap + BLOCK_SEQUENCES).min(sequences.len())];
+                cosine(&block_vector(left), &block_vector(right))
+            })
+            .collect();
+        let depths = depth_scores(&cohesion);
+
+        // Hearst's cutoff: valleys deeper than the mean depth minus half a standard deviation
+        let mean = depths.iter().sum::<f64>() / depths.len() as f64;
+        let variance = depths.iter().map(|d| (d - mean).powi(2)).sum::<f64>() / depths.len() as f64;
+        let cutoff = mean - variance.sqrt() / 2.0;
+
+        let mut gaps: Vec<(usize, f64)> = depths
+            .iter()
+            .enumerate()
+            .filter(|(_, &depth)| depth > 0.0 && depth > cutoff)
+            .map(|(i, &depth)| (i + 1, depth))
+            .collect();
+        gaps.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+        let start_of = |segment: usize| timed[segment].start_time.unwrap_or(recording_start);
+
+        for (gap, _) in gaps {
+            if boundaries.len() + 1 >= options.max_chapters {
+                break;
+            }
+
+            // Snap to a sentence start shortly after the gap, if there is one
+            let mut segment = sequences[gap].first_segment;
+            if let Some(shift) = (0..=MAX_BOUNDARY_SHIFT).find(|s| segment + s < timed.len() && starts_sentence(&timed, segment + s)) {
+                segment += shift;
+            }
+
+            let time = start_of(segment);
+            let too_close = time - recording_start < min_chapter_seconds
+                || recording_end - time < min_chapter_seconds
+                || boundaries.iter().any(|&b| (start_of(b) - time).abs() < min_chapter_seconds);
+            if segment > 0 && !too_close {
+                boundaries.push(segment);
+            }
+        }
+        boundaries.sort_unstable();
+    }
+
+    // Chapter spans as segment ranges
+    let mut spans: Vec<(usize, usize)> = Vec::new();
+    let mut start = 0;
+    for &boundary in &boundaries {
+        spans.push((start, boundary - 1));
+        start = boundary;
+    }
+    spans.push((start, timed.len() - 1));
+
+    let texts: Vec<String> = spans
+        .iter()
+        .map(|&(a, b)| timed[a..=b].iter().map(|s| s.text.as_str()).collect::<Vec<_>>().join("\n"))
+        .collect();
+    let mut corpus = CorpusStats::default();
+    for text in &texts {
+        corpus.add_document(language, text);
+    }
+
+    let mut used_titles: Vec<String> = Vec::new();
+    spans
+        .iter()
+        .zip(&texts)
+        .enumerate()
+        .map(|(index, (&(a, b), text))| {
+            // Of the chapter's top keywords, the one it keeps coming back to makes the best title
+            let mut keywords = extract_keywords(text, language, &corpus, TITLE_CANDIDATES);
+            keywords.sort_by(|a, b| {
+                (b.relevance * b.frequency as f64).total_cmp(&(a.relevance * a.frequency as f64))
+            });
+            let title = keywords
+                .into_iter()
+                .map(|k| title_case(&k.term))
+                .find(|t| !used_titles.contains(t))
+                .unwrap_or_else(|| format!("Chapter {}", index + 1));
+            used_titles.push(title.clone());
+
+            Chapter {
+                start_time: if index == 0 { recording_start } else { timed[a].start_time.unwrap_or(recording_start) },
+                end_time: timed[b].end_time.unwrap_or(recording_end),
+                first_segment: positions[a],
+                last_segment: positions[b],
+                title,
+            }
+        })
+        .collect()
+}
+
+// "05:07" under an hour, "1:05:07" from an hour on
+fn youtube_timestamp(seconds: f64, with_hours: bool) -> String {
+    let total = seconds.max(0.0).floor() as u64;
+    if with_hours {
+        format!("{}:{:02}:{:02}", total / 3600, (total / 60) % 60, total % 60)
+    } else {
+        format!("{:02}:{:02}", total / 60, total % 60)
+    }
+}
+
+/// Chapter list for a YouTube description. YouTube requires the first chapter at 00:00.
+pub fn render_youtube_chapters(chapters: &[Chapter]) -> String {
+    let with_hours = chapters.last().is_some_and(|c| c.start_time >= 3600.0);
+    chapters
+        .iter()
+        .enumerate()
+        .map(|(index, chapter)| {
+            let start = if index == 0 { 0.0 } else { chapter.start_time };
+            format!("{} {}", youtube_timestamp(start, with_hours), chapter.title)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// WebVTT chapters track; each cue runs until the next chapter starts
+pub fn render_vtt_chapters(chapters: &[Chapter]) -> String {
+    let mut output = String::from("WEBVTT\n\n");
+
+    for (index, chapter) in chapters.iter().enumerate() {
+        let end = chapters.get(index + 1).map_or(chapter.end_time, |next| next.start_time);
+        output.push_str(&format!(
+            "{}\n{} --> {}\n{}\n\n",
+            index + 1,
+            format_timestamp(chapter.start_time, '.'),
+            format_timestamp(end, '.'),
+            escape_vtt_text(&chapter.title)
+        ));
+    }
+
+    output
+}
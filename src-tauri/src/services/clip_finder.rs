@@ -0,0 +1,238 @@
+// Short-form clip candidates: timed transcript windows scored for self-containment, hooks and keywords
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+use super::stopwords::{content_words, tokenize_words};
+use super::transcript_parser::ParsedSegment;
+
+// Weights of the three signals in a candidate's score
+const SELF_CONTAINMENT_WEIGHT: f64 = 0.4;
+const HOOK_WEIGHT: f64 = 0.35;
+const KEYWORD_WEIGHT: f64 = 0.25;
+// Only the opening of a clip counts towards its hook
+const HOOK_SECONDS: f64 = 12.0;
+// A silence this long inside a window usually means a topic change or an edit point
+const MAX_INTERNAL_GAP: f64 = 4.0;
+// Share of keyword words at which the keyword signal is maxed out
+const FULL_KEYWORD_DENSITY: f64 = 0.25;
+
+// Openers that lean on what came before ("And that's why...")
+const DEPENDENT_OPENERS: &[&str] = &[
+    "and", "but", "so", "because", "which", "that", "also", "then", "or", "plus", "anyway", "it", "this",
+    "they", "he", "she", "those", "these",
+];
+
+// Words that signal a strong claim or opinion (English)
+const OPINION_MARKERS: &[&str] = &[
+    "never", "always", "best", "worst", "biggest", "secret", "mistake", "mistakes", "wrong", "truth",
+    "honestly", "everyone", "nobody", "must", "should", "shouldn't", "stop", "hate", "love", "believe",
+    "crazy", "insane", "important", "problem", "surprising", "myth", "lie",
+];
+
+// Number words that make a claim concrete; digits count in any language
+const NUMBER_WORDS: &[&str] = &["hundred", "thousand", "million", "billion", "percent", "half", "double", "triple"];
+
+#[derive(Debug, Clone)]
+pub struct ClipOptions {
+    pub min_duration: f64,
+    pub max_duration: f64,
+    pub limit: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClipCandidate {
+    pub start_time: f64,
+    pub end_time: f64,
+    pub duration: f64,
+    pub first_segment: usize, // Positions in the transcript's segment order
+    pub last_segment: usize,
+    pub text: String,
+    pub speakers: Vec<String>,
+    pub score: f64, // 0.0 to 1.0
+    pub self_containment: f64,
+    pub hook_strength: f64,
+    pub keyword_density: f64,
+    pub hooks: Vec<String>, // "question", "number" and/or "opinion"
+    pub keywords: Vec<String>, // Transcript keywords the clip mentions
+}
+
+fn is_sentence_end(text: &str) -> bool {
+    text.trim_end()
+        .trim_end_matches(['"', '\'', '”', '’', ')'])
+        .ends_with(['.', '!', '?', '…', '。', '！', '？'])
+}
+
+// Whether a segment begins a sentence: first segment, a new speaker, or the previous one closed a sentence
+fn starts_sentence(segments: &[ParsedSegment], index: usize) -> bool {
+    index == 0
+        || segments[index].speaker != segments[index - 1].speaker
+        || is_sentence_end(&segments[index - 1].text)
+}
+
+fn self_containment(segments: &[ParsedSegment], first: usize, last: usize) -> f64 {
+    let mut score = 0.0;
+    if starts_sentence(segments, first) {
+        score += 0.3;
+    }
+    if is_sentence_end(&segments[last].text) {
+        score += 0.3;
+    }
+
+    let opener = segments[first]
+        .text
+        .split_whitespace()
+        .next()
+        .map(|w| w.trim_matches(|c: char| !c.is_alphanumeric() && c != '\'').to_lowercase())
+        .unwrap_or_default();
+    if !DEPENDENT_OPENERS.contains(&opener.as_str()) {
+        score += 0.2;
+    }
+
+    let has_gap = segments[first..=last]
+        .windows(2)
+        .any(|pair| match (pair[0].end_time, pair[1].start_time) {
+            (Some(end), Some(start)) => start - end > MAX_INTERNAL_GAP,
+            _ => false,
+        });
+    if !has_gap {
+        score += 0.2;
+    }
+
+    score
+}
+
+// Questions, numbers and strong opinions in the first seconds of the clip
+fn hook_strength(segments: &[ParsedSegment], first: usize, last: usize, start_time: f64) -> (f64, Vec<String>) {
+    let opening: Vec<&str> = segments[first..=last]
+        .iter()
+        .take_while(|s| s.start_time.is_none_or(|t| t - start_time < HOOK_SECONDS))
+        .map(|s| s.text.as_str())
+        .collect();
+    let opening = opening.join(" ");
+    let words: Vec<String> = opening
+        .split_whitespace()
+        .map(|w| w.trim_matches(|c: char| !c.is_alphanumeric() && c != '\'').to_lowercase())
+        .collect();
+
+    let mut hooks = Vec::new();
+    let mut score = 0.0;
+
+    if opening.contains(['?', '？']) {
+        hooks.push("question".to_string());
+        score += 0.4;
+    }
+    if opening.contains(['%', '$', '€', '£']) || words.iter().any(|w| {
+        w.chars().any(|c| c.is_ascii_digit()) || NUMBER_WORDS.contains(&w.as_str())
+    }) {
+        hooks.push("number".to_string());
+        score += 0.3;
+    }
+    if words.iter().any(|w| OPINION_MARKERS.contains(&w.as_str())) {
+        hooks.push("opinion".to_string());
+        score += 0.3;
+    }
+
+    (score, hooks)
+}
+
+// Share of the clip's content words that belong to the transcript's keywords
+fn keyword_density(text: &str, language: &str, keywords: &[String]) -> (f64, Vec<String>) {
+    let words = content_words(language, text);
+    if words.is_empty() {
+        return (0.0, Vec::new());
+    }
+
+    let keyword_words: HashSet<&str> = keywords.iter().flat_map(|k| k.split(' ')).collect();
+    let hits = words.iter().filter(|w| keyword_words.contains(w.as_str())).count();
+
+    // Whole-word matches, so "make" isn't found in "maker"
+    let padded = format!(" {} ", tokenize_words(text).join(" "));
+    let mentioned = keywords.iter().filter(|k| padded.contains(&format!(" {} ", k))).cloned().collect();
+
+    (hits as f64 / words.len() as f64, mentioned)
+}
+
+/// Rank clip windows over timed segments. Windows start and end on segment boundaries and last
+/// between `min_duration` and `max_duration` seconds. The score is a weighted mean of:
+/// self-containment (starts and ends on sentence boundaries, no dependent opener such as "and so",
+/// no long silence inside), hook strength (a question, a number or a strong opinion in the opening
+/// seconds) and keyword density (share of content words that are transcript keywords).
+/// Returned candidates don't overlap; untimed segments are ignored.
+pub fn find_clips(
+    segments: &[ParsedSegment],
+    language: &str,
+    keywords: &[String],
+    options: &ClipOptions,
+) -> Vec<ClipCandidate> {
+    let keywords: Vec<String> = keywords.iter().map(|k| k.to_lowercase()).collect();
+    let mut candidates = Vec::new();
+
+    for first in 0..segments.len() {
+        let Some(start_time) = segments[first].start_time else { continue };
+
+        for last in first..segments.len() {
+            let Some(end_time) = segments[last].end_time else { break };
+            let duration = end_time - start_time;
+            if duration > options.max_duration {
+                break;
+            }
+            if duration < options.min_duration {
+                continue;
+            }
+
+            let text = segments[first..=last]
+                .iter()
+                .map(|s| s.text.trim())
+                .collect::<Vec<_>>()
+                .join(" ");
+
+            let self_containment = self_containment(segments, first, last);
+            let (hook_strength, hooks) = hook_strength(segments, first, last, start_time);
+            let (density, mentioned) = keyword_density(&text, language, &keywords);
+            let keyword_score = (density / FULL_KEYWORD_DENSITY).min(1.0);
+
+            let mut speakers: Vec<String> = Vec::new();
+            for speaker in segments[first..=last].iter().filter_map(|s| s.speaker.as_ref()) {
+                if !speakers.contains(speaker) {
+                    speakers.push(speaker.clone());
+                }
+            }
+
+            candidates.push(ClipCandidate {
+                start_time,
+                end_time,
+                duration,
+                first_segment: first,
+                last_segment: last,
+                text,
+                speakers,
+                score: SELF_CONTAINMENT_WEIGHT * self_containment
+                    + HOOK_WEIGHT * hook_strength
+                    + KEYWORD_WEIGHT * keyword_score,
+                self_containment,
+                hook_strength,
+                keyword_density: density,
+                hooks,
+                keywords: mentioned,
+            });
+        }
+    }
+
+    // Best first; ties go to the earlier clip
+    candidates.sort_by(|a, b| b.score.total_cmp(&a.score).then_with(|| a.start_time.total_cmp(&b.start_time)));
+
+    let mut selected: Vec<ClipCandidate> = Vec::new();
+    for candidate in candidates {
+        if selected.len() >= options.limit {
+            break;
+        }
+        let overlaps = selected
+            .iter()
+            .any(|s| candidate.start_time < s.end_time && s.start_time < candidate.end_time);
+        if !overlaps {
+            selected.push(candidate);
+        }
+    }
+
+    selected
+}
@@ -0,0 +1,246 @@
+// Readability and delivery metrics for transcripts, combined into a reproducible content score
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+use super::disfluency::{analyze_text, DisfluencyCounts};
+use super::stopwords::tokenize_words;
+use super::summarizer::split_sentences;
+use super::transcript_parser::ParsedSegment;
+
+/// Bumped whenever the metrics, bands or weights below change, so stored scores can be told apart
+pub const SCORE_VERSION: u32 = 1;
+
+// Below this length the metrics are too noisy to score
+const MIN_SCORED_WORDS: usize = 30;
+// Window for the moving-average type-token ratio
+const MATTR_WINDOW: usize = 50;
+// Speaking rate needs at least this much timed speech
+const MIN_TIMED_SECONDS: f64 = 10.0;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranscriptMetrics {
+    pub word_count: usize,
+    pub sentence_count: usize,
+    pub avg_sentence_length: f64, // Words per sentence
+    pub avg_syllables_per_word: f64,
+    pub reading_ease: f64, // Flesch reading ease, with the language's own adaptation where one exists
+    pub grade_level: f64, // Flesch–Kincaid grade (English scale)
+    pub lexical_diversity: f64, // Moving-average type-token ratio, 0.0 to 1.0
+    pub words_per_minute: Option<f64>, // None without segment timings
+    pub question_ratio: f64, // Share of sentences that are questions
+    pub filler_density: f64, // Fillers per 100 words
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScoreComponent {
+    pub name: String,
+    pub value: Option<f64>, // The metric the component is based on
+    pub score: Option<f64>, // 0.0 to 1.0; None when the metric isn't available
+    pub weight: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContentScoreBreakdown {
+    pub version: u32,
+    pub score: f64, // 0.0 to 1.0, stored as Transcript.ContentScore
+    pub components: Vec<ScoreComponent>,
+    pub metrics: TranscriptMetrics,
+}
+
+// Flesch reading ease as (base, sentence length factor, syllables per word factor), by language.
+// Spanish: Fernández Huerta; French: Kandel & Moles; German: Amstad; Italian: Flesch–Vacca;
+// Portuguese: Martins et al. Anything else uses the English formula.
+const READING_EASE_FORMULAS: &[(&str, (f64, f64, f64))] = &[
+    ("en", (206.835, 1.015, 84.6)),
+    ("es", (206.84, 1.02, 60.0)),
+    ("fr", (207.0, 1.015, 73.6)),
+    ("de", (180.0, 1.0, 58.5)),
+    ("it", (206.0, 1.0, 65.0)),
+    ("pt", (248.835, 1.015, 84.6)),
+];
+
+// Vowel groups approximate syllables well enough for readability formulas in these languages
+fn count_syllables(word: &str, language: &str) -> usize {
+    let is_vowel = |c: char| "aeiouyàáâãäåèéêëìíîïòóôõöùúûüý".contains(c);
+    let chars: Vec<char> = word.chars().collect();
+
+    let mut syllables = 0;
+    let mut previous_vowel = false;
+    for &c in &chars {
+        let vowel = is_vowel(c);
+        if vowel && !previous_vowel {
+            syllables += 1;
+        }
+        previous_vowel = vowel;
+    }
+
+    // English silent final "e" ("make"), but not "-le" ("table")
+    if language == "en" && syllables > 1 && word.ends_with('e') && !word.ends_with("le") {
+        syllables -= 1;
+    }
+
+    syllables.max(1)
+}
+
+// Type-token ratio averaged over sliding windows, so long transcripts aren't penalized for length
+fn lexical_diversity(words: &[String]) -> f64 {
+    if words.is_empty() {
+        return 0.0;
+    }
+    if words.len() <= MATTR_WINDOW {
+        let unique: HashSet<&String> = words.iter().collect();
+        return unique.len() as f64 / words.len() as f64;
+    }
+
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for word in &words[..MATTR_WINDOW] {
+        *counts.entry(word).or_insert(0) += 1;
+    }
+
+    let mut total = counts.len() as f64;
+    for i in MATTR_WINDOW..words.len() {
+        let leaving = words[i - MATTR_WINDOW].as_str();
+        if let Some(count) = counts.get_mut(leaving) {
+            *count -= 1;
+            if *count == 0 {
+                counts.remove(leaving);
+            }
+        }
+        *counts.entry(&words[i]).or_insert(0) += 1;
+        total += counts.len() as f64;
+    }
+
+    total / (words.len() - MATTR_WINDOW + 1) as f64 / MATTR_WINDOW as f64
+}
+
+// Words over the time from the first timed segment's start to the last one's end, pauses included
+fn words_per_minute(segments: &[ParsedSegment]) -> Option<f64> {
+    let timed: Vec<(f64, f64, usize)> = segments
+        .iter()
+        .filter_map(|s| match (s.start_time, s.end_time) {
+            (Some(start), Some(end)) if end >= start => Some((start, end, s.text.split_whitespace().count())),
+            _ => None,
+        })
+        .collect();
+
+    let start = timed.iter().map(|t| t.0).fold(f64::INFINITY, f64::min);
+    let end = timed.iter().map(|t| t.1).fold(f64::NEG_INFINITY, f64::max);
+    let seconds = end - start;
+    if timed.is_empty() || seconds < MIN_TIMED_SECONDS {
+        return None;
+    }
+
+    let words: usize = timed.iter().map(|t| t.2).sum();
+    Some(words as f64 * 60.0 / seconds)
+}
+
+pub fn compute_metrics(segments: &[ParsedSegment], language: &str) -> TranscriptMetrics {
+    let sentences = split_sentences(segments);
+    let words: Vec<String> = segments.iter().flat_map(|s| tokenize_words(&s.text)).collect();
+    let word_count = words.len();
+    let sentence_count = sentences.len();
+
+    let avg_sentence_length = if sentence_count == 0 {
+        0.0
+    } else {
+        sentences.iter().map(|s| s.word_count).sum::<usize>() as f64 / sentence_count as f64
+    };
+    let avg_syllables_per_word = if word_count == 0 {
+        0.0
+    } else {
+        words.iter().map(|w| count_syllables(w, language)).sum::<usize>() as f64 / word_count as f64
+    };
+
+    let (base, sentence_factor, syllable_factor) = READING_EASE_FORMULAS
+        .iter()
+        .find(|(code, _)| *code == language)
+        .unwrap_or(&READING_EASE_FORMULAS[0])
+        .1;
+    let reading_ease = base - sentence_factor * avg_sentence_length - syllable_factor * avg_syllables_per_word;
+    let grade_level = 0.39 * avg_sentence_length + 11.8 * avg_syllables_per_word - 15.59;
+
+    let questions = sentences
+        .iter()
+        .filter(|s| s.text.trim_end_matches(['"', '\'', '”', ')']).ends_with(['?', '？']))
+        .count();
+    let question_ratio = if sentence_count == 0 { 0.0 } else { questions as f64 / sentence_count as f64 };
+
+    let mut disfluencies = DisfluencyCounts::default();
+    for segment in segments {
+        disfluencies.add(&analyze_text(&segment.text, language).counts);
+    }
+
+    TranscriptMetrics {
+        word_count,
+        sentence_count,
+        avg_sentence_length,
+        avg_syllables_per_word,
+        reading_ease,
+        grade_level,
+        lexical_diversity: lexical_diversity(&words),
+        words_per_minute: words_per_minute(segments),
+        question_ratio,
+        filler_density: disfluencies.filler_density(),
+    }
+}
+
+// Linear from 0.0 at `zero` to 1.0 at `best`; works in either direction
+fn ramp(value: f64, zero: f64, best: f64) -> f64 {
+    ((value - zero) / (best - zero)).clamp(0.0, 1.0)
+}
+
+// 1.0 between `best_low` and `best_high`, falling linearly to 0.0 at `zero_low` and `zero_high`
+fn band(value: f64, zero_low: f64, best_low: f64, best_high: f64, zero_high: f64) -> f64 {
+    if value < best_low {
+        ramp(value, zero_low, best_low)
+    } else {
+        ramp(value, zero_high, best_high)
+    }
+}
+
+/// Score a transcript from its metrics. Each component maps one metric to 0.0–1.0 and the score
+/// is their weighted mean; components whose metric is unavailable are left out of the mean.
+///
+/// | Component         | Weight | Full marks               | Zero at          |
+/// |-------------------|--------|--------------------------|------------------|
+/// | readability       | 0.25   | reading ease 60–85       | 30 and 110       |
+/// | sentence_length   | 0.15   | 10–20 words              | 4 and 40 words   |
+/// | lexical_diversity | 0.20   | MATTR 0.72 and above     | 0.45             |
+/// | speaking_rate     | 0.15   | 130–170 words/minute     | 90 and 220       |
+/// | questions         | 0.10   | 5–25% of sentences       | 0% and 60%       |
+/// | fillers           | 0.15   | 1 per 100 words or fewer | 8 per 100 words  |
+///
+/// Returns None for transcripts under 30 words.
+pub fn score(metrics: TranscriptMetrics) -> Option<ContentScoreBreakdown> {
+    if metrics.word_count < MIN_SCORED_WORDS {
+        return None;
+    }
+
+    let component = |name: &str, weight: f64, value: Option<f64>, score: fn(f64) -> f64| ScoreComponent {
+        name: name.to_string(),
+        value,
+        score: value.map(score),
+        weight,
+    };
+
+    let components = vec![
+        component("readability", 0.25, Some(metrics.reading_ease), |v| band(v, 30.0, 60.0, 85.0, 110.0)),
+        component("sentence_length", 0.15, Some(metrics.avg_sentence_length), |v| band(v, 4.0, 10.0, 20.0, 40.0)),
+        component("lexical_diversity", 0.20, Some(metrics.lexical_diversity), |v| ramp(v, 0.45, 0.72)),
+        component("speaking_rate", 0.15, metrics.words_per_minute, |v| band(v, 90.0, 130.0, 170.0, 220.0)),
+        component("questions", 0.10, Some(metrics.question_ratio), |v| band(v, 0.0, 0.05, 0.25, 0.6)),
+        component("fillers", 0.15, Some(metrics.filler_density), |v| ramp(v, 8.0, 1.0)),
+    ];
+
+    let (weighted, total_weight) = components
+        .iter()
+        .filter_map(|c| c.score.map(|s| (s * c.weight, c.weight)))
+        .fold((0.0, 0.0), |(sum, weights), (s, w)| (sum + s, weights + w));
+
+    Some(ContentScoreBreakdown {
+        version: SCORE_VERSION,
+        score: (weighted / total_weight).clamp(0.0, 1.0),
+        components,
+        metrics,
+    })
+}
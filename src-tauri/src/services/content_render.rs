@@ -0,0 +1,425 @@
+// Rendering generated content to Markdown, HTML, plain text and structured JSON
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ContentExportFormat {
+    Markdown,
+    Html,
+    Text,
+    Json,
+}
+
+impl ContentExportFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ContentExportFormat::Markdown => "markdown",
+            ContentExportFormat::Html => "html",
+            ContentExportFormat::Text => "text",
+            ContentExportFormat::Json => "json",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ContentExportFormat::Markdown => "md",
+            ContentExportFormat::Html => "html",
+            ContentExportFormat::Text => "txt",
+            ContentExportFormat::Json => "json",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ContentKind {
+    Thread, // Numbered posts
+    Carousel, // Slides with the title as caption
+    Article, // Prose with headings; also blogs, newsletters and custom template types
+    Script, // Prose sections with timing and production cues
+}
+
+impl ContentKind {
+    pub fn from_template_type(template_type: &str) -> ContentKind {
+        match template_type.to_lowercase().as_str() {
+            "thread" => ContentKind::Thread,
+            "carousel" => ContentKind::Carousel,
+            "script" | "video-script" | "video_script" => ContentKind::Script,
+            _ => ContentKind::Article,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ContentKind::Thread => "thread",
+            ContentKind::Carousel => "carousel",
+            ContentKind::Article => "article",
+            ContentKind::Script => "script",
+        }
+    }
+
+    // Where the built-in template for this kind publishes
+    pub fn default_platform(&self) -> &'static str {
+        match self {
+            ContentKind::Thread => "twitter",
+            ContentKind::Carousel => "instagram",
+            ContentKind::Article => "linkedin",
+            ContentKind::Script => "youtube",
+        }
+    }
+
+    fn is_posts(&self) -> bool {
+        matches!(self, ContentKind::Thread | ContentKind::Carousel)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Block {
+    Heading { level: usize, text: String },
+    Paragraph { text: String },
+    List { ordered: bool, items: Vec<String> },
+    Cue { text: String }, // Bracketed production direction in a script, e.g. "[B-roll: city at night]"
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContentSection {
+    pub heading: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<String>, // Scripts only, from a heading like "Intro (0:00)"
+    pub blocks: Vec<Block>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ContentDocument {
+    pub kind: ContentKind,
+    pub title: String,
+    pub posts: Vec<String>, // Threads and carousels, without any numbering the generator added
+    pub blocks: Vec<Block>, // Articles and scripts
+    pub metadata: Option<Value>,
+}
+
+#[derive(Debug, Serialize)]
+struct JsonPost<'a> {
+    index: usize,
+    text: &'a str,
+    characters: usize,
+}
+
+#[derive(Debug, Serialize)]
+struct JsonExport<'a> {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    title: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    posts: Option<Vec<JsonPost<'a>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sections: Option<Vec<ContentSection>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    metadata: Option<&'a Value>,
+}
+
+// "3/7 Text" from the thread formatter
+fn strip_post_number(post: &str) -> &str {
+    let Some((number, rest)) = post.split_once(char::is_whitespace) else { return post };
+    let numbered = number
+        .split_once('/')
+        .is_some_and(|(i, n)| !i.is_empty() && !n.is_empty() && (i.to_string() + n).chars().all(|c| c.is_ascii_digit()));
+    if numbered { rest.trim_start() } else { post }
+}
+
+// "[Slide 2]\nText" from the carousel formatter
+fn strip_slide_label(slide: &str) -> &str {
+    let trimmed = slide.trim_start();
+    let Some(rest) = trimmed.strip_prefix("[Slide ") else { return slide };
+    match rest.split_once(']') {
+        Some((number, text)) if number.chars().all(|c| c.is_ascii_digit()) => text.trim_start(),
+        _ => slide,
+    }
+}
+
+fn value_text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        other => serde_json::to_string_pretty(other).unwrap_or_default(),
+    }
+}
+
+/// Read stored content (`{"title", "content", "metadata"}` as the generator saves it, or a bare
+/// string or array) into a document of the given kind. Posts given as one string are split on
+/// blank lines, and prose given as a list is joined into paragraphs.
+pub fn parse_content(kind: ContentKind, content: &Value, fallback_title: Option<&str>) -> ContentDocument {
+    let body = content.get("content").unwrap_or(content);
+    let title = content
+        .get("title")
+        .and_then(Value::as_str)
+        .or(fallback_title)
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .unwrap_or("Untitled")
+        .to_string();
+    let metadata = content.get("metadata").filter(|m| !m.is_null()).cloned();
+
+    let items: Vec<String> = match body {
+        Value::Array(items) => items.iter().map(value_text).collect(),
+        other => vec![value_text(other)],
+    };
+
+    let mut document = ContentDocument { kind, title, posts: Vec::new(), blocks: Vec::new(), metadata };
+    if kind.is_posts() {
+        document.posts = items
+            .iter()
+            .flat_map(|item| item.split("\n\n"))
+            .map(|post| match kind {
+                ContentKind::Thread => strip_post_number(post.trim()),
+                _ => strip_slide_label(post.trim()),
+            })
+            .map(|post| post.trim().to_string())
+            .filter(|post| !post.is_empty())
+            .collect();
+    } else {
+        document.blocks = parse_blocks(&items.join("\n\n"));
+    }
+    document
+}
+
+fn list_item(line: &str) -> Option<(bool, &str)> {
+    for marker in ["- ", "* ", "• "] {
+        if let Some(rest) = line.strip_prefix(marker) {
+            return Some((false, rest.trim()));
+        }
+    }
+    let digits = line.chars().take_while(|c| c.is_ascii_digit()).count();
+    if digits > 0 {
+        let rest = &line[digits..];
+        if let Some(text) = rest.strip_prefix(". ").or_else(|| rest.strip_prefix(") ")) {
+            return Some((true, text.trim()));
+        }
+    }
+    None
+}
+
+/// Split Markdown-style prose into headings, paragraphs, lists and bracketed cues
+pub fn parse_blocks(text: &str) -> Vec<Block> {
+    let mut blocks: Vec<Block> = Vec::new();
+    let mut paragraph: Vec<&str> = Vec::new();
+
+    let flush = |paragraph: &mut Vec<&str>, blocks: &mut Vec<Block>| {
+        if !paragraph.is_empty() {
+            blocks.push(Block::Paragraph { text: paragraph.join("\n") });
+            paragraph.clear();
+        }
+    };
+
+    for line in text.lines().map(str::trim) {
+        if line.is_empty() {
+            flush(&mut paragraph, &mut blocks);
+            continue;
+        }
+
+        let hashes = line.chars().take_while(|c| *c == '#').count();
+        if (1..=6).contains(&hashes) && line[hashes..].starts_with(' ') {
+            flush(&mut paragraph, &mut blocks);
+            blocks.push(Block::Heading { level: hashes, text: line[hashes..].trim().to_string() });
+        } else if line.starts_with('[') && line.ends_with(']') {
+            flush(&mut paragraph, &mut blocks);
+            blocks.push(Block::Cue { text: line[1..line.len() - 1].trim().to_string() });
+        } else if let Some((ordered, item)) = list_item(line) {
+            flush(&mut paragraph, &mut blocks);
+            match blocks.last_mut() {
+                Some(Block::List { ordered: current, items }) if *current == ordered => items.push(item.to_string()),
+                _ => blocks.push(Block::List { ordered, items: vec![item.to_string()] }),
+            }
+        } else {
+            paragraph.push(line);
+        }
+    }
+    flush(&mut paragraph, &mut blocks);
+    blocks
+}
+
+// "0:15", "1:02:30" or "00:15" anywhere in a heading, with any brackets around it
+fn find_timestamp(heading: &str) -> Option<String> {
+    heading
+        .split(|c: char| c.is_whitespace() || "()[]-–".contains(c))
+        .find(|token| {
+            let parts: Vec<&str> = token.split(':').collect();
+            (2..=3).contains(&parts.len())
+                && parts.iter().all(|p| !p.is_empty() && p.len() <= 2 && p.chars().all(|c| c.is_ascii_digit()))
+        })
+        .map(str::to_string)
+}
+
+/// Group blocks under their headings; blocks before the first heading form an untitled section
+pub fn sections(kind: ContentKind, blocks: &[Block]) -> Vec<ContentSection> {
+    let mut sections: Vec<ContentSection> = Vec::new();
+    for block in blocks {
+        match block {
+            Block::Heading { text, .. } => sections.push(ContentSection {
+                heading: Some(text.clone()),
+                timestamp: (kind == ContentKind::Script).then(|| find_timestamp(text)).flatten(),
+                blocks: Vec::new(),
+            }),
+            other => match sections.last_mut() {
+                Some(section) => section.blocks.push(other.clone()),
+                None => sections.push(ContentSection { heading: None, timestamp: None, blocks: vec![other.clone()] }),
+            },
+        }
+    }
+    sections
+}
+
+// Generated prose often repeats the title as its first heading
+fn body_blocks(document: &ContentDocument) -> &[Block] {
+    match document.blocks.first() {
+        Some(Block::Heading { level: 1, text }) if text.eq_ignore_ascii_case(&document.title) => &document.blocks[1..],
+        _ => &document.blocks,
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+// Escaped text with **bold** spans and line breaks
+fn inline_html(text: &str) -> String {
+    let escaped = escape_html(text);
+    let parts: Vec<&str> = escaped.split("**").collect();
+    let mut html = String::new();
+    for (i, part) in parts.iter().enumerate() {
+        if i % 2 == 0 {
+            html.push_str(part);
+        } else if i < parts.len() - 1 {
+            html.push_str("<strong>");
+            html.push_str(part);
+            html.push_str("</strong>");
+        } else {
+            // An unpaired final marker is kept as typed
+            html.push_str("**");
+            html.push_str(part);
+        }
+    }
+    html.replace('\n', "<br>\n")
+}
+
+fn plain_inline(text: &str) -> String {
+    text.replace("**", "")
+}
+
+fn render_markdown(document: &ContentDocument) -> String {
+    let mut parts = vec![format!("# {}", document.title)];
+    let count = document.posts.len();
+    for (i, post) in document.posts.iter().enumerate() {
+        parts.push(match document.kind {
+            ContentKind::Thread => format!("**{}/{}** {}", i + 1, count, post),
+            _ => format!("## Slide {}\n\n{}", i + 1, post),
+        });
+    }
+    for block in body_blocks(document) {
+        parts.push(match block {
+            Block::Heading { level, text } => format!("{} {}", "#".repeat(*level), text),
+            Block::Paragraph { text } => text.clone(),
+            Block::List { ordered, items } => items
+                .iter()
+                .enumerate()
+                .map(|(i, item)| if *ordered { format!("{}. {}", i + 1, item) } else { format!("- {}", item) })
+                .collect::<Vec<_>>()
+                .join("\n"),
+            Block::Cue { text } => format!("*[{}]*", text),
+        });
+    }
+    parts.join("\n\n") + "\n"
+}
+
+fn render_html(document: &ContentDocument) -> String {
+    let mut body = vec![format!("<h1>{}</h1>", escape_html(&document.title))];
+    let count = document.posts.len();
+    if !document.posts.is_empty() {
+        let class = if document.kind == ContentKind::Thread { "posts" } else { "slides" };
+        body.push(format!("<ol class=\"{}\">", class));
+        for (i, post) in document.posts.iter().enumerate() {
+            let label = match document.kind {
+                ContentKind::Thread => format!("{}/{}", i + 1, count),
+                _ => format!("Slide {}", i + 1),
+            };
+            body.push(format!("<li><span class=\"label\">{}</span> <p>{}</p></li>", label, inline_html(post)));
+        }
+        body.push("</ol>".to_string());
+    }
+    for block in body_blocks(document) {
+        body.push(match block {
+            Block::Heading { level, text } => format!("<h{0}>{1}</h{0}>", level, inline_html(text)),
+            Block::Paragraph { text } => format!("<p>{}</p>", inline_html(text)),
+            Block::List { ordered, items } => {
+                let tag = if *ordered { "ol" } else { "ul" };
+                let items: String = items.iter().map(|item| format!("<li>{}</li>", inline_html(item))).collect();
+                format!("<{0}>{1}</{0}>", tag, items)
+            }
+            Block::Cue { text } => format!("<p class=\"cue\"><em>[{}]</em></p>", inline_html(text)),
+        });
+    }
+
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n</head>\n<body>\n<article class=\"{}\">\n{}\n</article>\n</body>\n</html>\n",
+        escape_html(&document.title),
+        document.kind.as_str(),
+        body.join("\n")
+    )
+}
+
+fn render_text(document: &ContentDocument) -> String {
+    let mut parts = vec![document.title.clone()];
+    let count = document.posts.len();
+    for (i, post) in document.posts.iter().enumerate() {
+        parts.push(match document.kind {
+            ContentKind::Thread => format!("{}/{} {}", i + 1, count, plain_inline(post)),
+            _ => format!("[Slide {}]\n{}", i + 1, plain_inline(post)),
+        });
+    }
+    for block in body_blocks(document) {
+        parts.push(match block {
+            Block::Heading { text, .. } => plain_inline(text),
+            Block::Paragraph { text } => plain_inline(text),
+            Block::List { ordered, items } => items
+                .iter()
+                .enumerate()
+                .map(|(i, item)| if *ordered { format!("{}. {}", i + 1, plain_inline(item)) } else { format!("- {}", plain_inline(item)) })
+                .collect::<Vec<_>>()
+                .join("\n"),
+            Block::Cue { text } => format!("[{}]", text),
+        });
+    }
+    parts.join("\n\n") + "\n"
+}
+
+fn render_json(document: &ContentDocument) -> Result<String, String> {
+    let export = JsonExport {
+        kind: document.kind.as_str(),
+        title: &document.title,
+        posts: document.kind.is_posts().then(|| {
+            document
+                .posts
+                .iter()
+                .enumerate()
+                .map(|(i, text)| JsonPost { index: i + 1, text, characters: text.chars().count() })
+                .collect()
+        }),
+        sections: (!document.kind.is_posts()).then(|| sections(document.kind, body_blocks(document))),
+        metadata: document.metadata.as_ref(),
+    };
+    serde_json::to_string_pretty(&export).map_err(|e| format!("Failed to serialize content: {}", e))
+}
+
+/// Render a document in the given format
+pub fn render(document: &ContentDocument, format: ContentExportFormat) -> Result<String, String> {
+    match format {
+        ContentExportFormat::Markdown => Ok(render_markdown(document)),
+        ContentExportFormat::Html => Ok(render_html(document)),
+        ContentExportFormat::Text => Ok(render_text(document)),
+        ContentExportFormat::Json => render_json(document),
+    }
+}
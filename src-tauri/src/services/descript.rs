@@ -408,7 +408,7 @@ impl ExportInstructions {
                 "Once transcription is done, click on the project".to_string(),
                 "Go to File → Export → Transcript".to_string(),
                 "Choose 'Text (.txt)' or 'SRT (.srt)' format".to_string(),
-                format!("Save the file to: {}", export_folder),
+                format!("Save the file to: {} and keep the project name as the file name", export_folder),
                 "ContentFlow will automatically detect and import the transcript".to_string(),
             ],
             export_folder,
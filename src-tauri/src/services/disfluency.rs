@@ -0,0 +1,276 @@
+// Filler words, repetitions and false starts in spoken transcripts, and text with them removed
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+// Hesitation sounds that are fillers wherever they appear, in any language
+const UNIVERSAL_FILLERS: &[&str] = &["um", "uh", "uhm", "hmm", "mm", "mhm"];
+
+// Per-language hesitation sounds, counted wherever they appear
+const LANGUAGE_FILLERS: &[(&str, &[&str])] = &[
+    ("en", &["er", "erm", "ah"]),
+    ("es", &["eh", "em", "ehm"]),
+    ("pt", &["hum", "ahn", "hã"]),
+    ("fr", &["euh", "heu", "hum"]),
+    ("de", &["äh", "ähm", "öh", "hm"]),
+    ("it", &["ehm", "eh"]),
+];
+
+// Words and phrases that are only fillers when set off by punctuation ("it was, like, huge"),
+// since they are ordinary words elsewhere ("I like it", "I mean it")
+const DELIMITED_FILLERS: &[(&str, &[&str])] = &[
+    ("en", &["like", "you know", "i mean", "you see", "sort of", "kind of", "basically", "literally"]),
+    ("es", &["este", "o sea", "pues", "bueno", "sabes"]),
+    ("pt", &["tipo", "né", "sabe", "então"]),
+    ("fr", &["ben", "bah", "hein", "genre", "tu sais", "tu vois", "quoi"]),
+    ("de", &["halt", "sozusagen", "weißt du", "also"]),
+    ("it", &["cioè", "tipo", "insomma", "diciamo", "praticamente"]),
+];
+
+// Doubled words that are usually grammatical rather than stutters
+const ALLOWED_REPEATS: &[&str] = &["that", "had", "is", "very", "so", "no", "bye", "now", "many", "much"];
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DisfluencyCounts {
+    pub word_count: usize,
+    pub filler_count: usize,
+    pub repetition_count: usize,
+    pub false_start_count: usize,
+    pub fillers: HashMap<String, usize>, // Filler word or phrase and how often it occurred
+}
+
+impl DisfluencyCounts {
+    pub fn add(&mut self, other: &DisfluencyCounts) {
+        self.word_count += other.word_count;
+        self.filler_count += other.filler_count;
+        self.repetition_count += other.repetition_count;
+        self.false_start_count += other.false_start_count;
+        for (filler, count) in &other.fillers {
+            *self.fillers.entry(filler.clone()).or_insert(0) += count;
+        }
+    }
+
+    /// Fillers per 100 words
+    pub fn filler_density(&self) -> f64 {
+        if self.word_count == 0 {
+            0.0
+        } else {
+            self.filler_count as f64 * 100.0 / self.word_count as f64
+        }
+    }
+
+    /// Most frequent fillers first
+    pub fn top_fillers(&self, limit: usize) -> Vec<(String, usize)> {
+        let mut fillers: Vec<(String, usize)> = self.fillers.iter().map(|(f, c)| (f.clone(), *c)).collect();
+        fillers.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        fillers.truncate(limit);
+        fillers
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DisfluencyAnalysis {
+    pub counts: DisfluencyCounts,
+    pub cleaned: String,
+}
+
+struct Token<'a> {
+    text: &'a str,
+    norm: String,
+    drop: bool,
+}
+
+// Lowercase without surrounding punctuation
+fn normalize(word: &str) -> String {
+    word.trim_matches(|c: char| !c.is_alphanumeric() && c != '\'').to_lowercase()
+}
+
+// "ummm" and "uhhh" are the same filler as "um" and "uh"
+fn collapse_repeats(word: &str) -> String {
+    let mut collapsed = String::new();
+    for c in word.chars() {
+        if !collapsed.ends_with(c) {
+            collapsed.push(c);
+        }
+    }
+    collapsed
+}
+
+fn fillers_for<'a>(table: &'a [(&str, &'a [&'a str])], language: &str) -> &'a [&'a str] {
+    table
+        .iter()
+        .find(|(code, _)| *code == language)
+        .or_else(|| table.iter().find(|(code, _)| *code == "en"))
+        .map_or(&[], |(_, words)| *words)
+}
+
+fn ends_with_pause(word: &str) -> bool {
+    word.ends_with([',', '.', ';', ':', '!', '?', '…', '—'])
+}
+
+fn ends_sentence(word: &str) -> bool {
+    word.ends_with(['.', '!', '?', '…'])
+}
+
+// A word cut off mid-way ("wh-", "was—", "I--"), as opposed to a hyphenated compound
+fn is_false_start(word: &str) -> bool {
+    let trimmed = word.trim_end_matches(['"', '\'', ')']);
+    trimmed.chars().count() > 1
+        && (trimmed.ends_with('-') || trimmed.ends_with('—') || trimmed.ends_with("--"))
+        && trimmed.chars().any(|c| c.is_alphabetic())
+}
+
+/// Count fillers, repetitions and false starts in one stretch of speech and produce a cleaned
+/// version of it. Cleaning removes those words and repairs the punctuation and capitalization
+/// around them ("Um, so it was, you know, great." becomes "So it was great.").
+pub fn analyze_text(text: &str, language: &str) -> DisfluencyAnalysis {
+    let mut tokens: Vec<Token> = text
+        .split_whitespace()
+        .map(|word| Token { text: word, norm: normalize(word), drop: false })
+        .collect();
+
+    let mut counts = DisfluencyCounts {
+        word_count: tokens.len(),
+        ..Default::default()
+    };
+
+    let sounds: Vec<String> = UNIVERSAL_FILLERS
+        .iter()
+        .chain(fillers_for(LANGUAGE_FILLERS, language))
+        .map(|f| collapse_repeats(f))
+        .collect();
+    let mut delimited: Vec<Vec<&str>> = fillers_for(DELIMITED_FILLERS, language)
+        .iter()
+        .map(|f| f.split(' ').collect())
+        .collect();
+    // Longer phrases first so "you know" isn't missed in favour of a one-word entry
+    delimited.sort_by_key(|words| std::cmp::Reverse(words.len()));
+
+    // Hesitation sounds
+    for token in &mut tokens {
+        if !token.norm.is_empty() && sounds.contains(&collapse_repeats(&token.norm)) {
+            token.drop = true;
+            counts.filler_count += 1;
+            *counts.fillers.entry(collapse_repeats(&token.norm)).or_insert(0) += 1;
+        }
+    }
+
+    // Filler words and phrases set off by punctuation or at the edges of the text
+    let mut i = 0;
+    while i < tokens.len() {
+        let matched = delimited.iter().find(|phrase| {
+            let end = i + phrase.len();
+            end <= tokens.len()
+                && tokens[i..end].iter().zip(phrase.iter()).all(|(t, w)| !t.drop && t.norm == *w)
+                // Only the last word of the phrase may carry punctuation
+                && tokens[i..end - 1].iter().all(|t| !ends_with_pause(t.text))
+                && (i == 0 || ends_with_pause(tokens[i - 1].text))
+                && (end == tokens.len() || ends_with_pause(tokens[end - 1].text))
+        });
+
+        match matched {
+            Some(phrase) => {
+                for token in &mut tokens[i..i + phrase.len()] {
+                    token.drop = true;
+                }
+                counts.filler_count += 1;
+                *counts.fillers.entry(phrase.join(" ")).or_insert(0) += 1;
+                i += phrase.len();
+            }
+            None => i += 1,
+        }
+    }
+
+    // False starts
+    for token in &mut tokens {
+        if !token.drop && is_false_start(token.text) {
+            token.drop = true;
+            counts.false_start_count += 1;
+        }
+    }
+
+    // Repeated words and word pairs ("I I think", "we were we were"); the first occurrence is
+    // dropped so the kept one carries the punctuation that follows
+    let kept: Vec<usize> = (0..tokens.len()).filter(|&i| !tokens[i].drop).collect();
+    let mut k = 0;
+    while k < kept.len() {
+        let pair_repeat = k + 3 < kept.len()
+            && tokens[kept[k]].norm == tokens[kept[k + 2]].norm
+            && tokens[kept[k + 1]].norm == tokens[kept[k + 3]].norm
+            && !ends_with_pause(tokens[kept[k + 1]].text)
+            && !tokens[kept[k]].norm.is_empty();
+        if pair_repeat {
+            tokens[kept[k]].drop = true;
+            tokens[kept[k + 1]].drop = true;
+            counts.repetition_count += 1;
+            k += 2;
+            continue;
+        }
+
+        let word_repeat = k + 1 < kept.len()
+            && tokens[kept[k]].norm == tokens[kept[k + 1]].norm
+            && !ends_with_pause(tokens[kept[k]].text)
+            && tokens[kept[k]].norm.chars().any(|c| c.is_alphabetic())
+            && !ALLOWED_REPEATS.contains(&tokens[kept[k]].norm.as_str());
+        if word_repeat {
+            tokens[kept[k]].drop = true;
+            counts.repetition_count += 1;
+        }
+        k += 1;
+    }
+
+    DisfluencyAnalysis {
+        counts,
+        cleaned: rebuild(&tokens),
+    }
+}
+
+// Join the kept words, moving sentence-ending punctuation off dropped words and capitalizing
+// a sentence whose first word was dropped
+fn rebuild(tokens: &[Token]) -> String {
+    let mut words: Vec<String> = Vec::new();
+    let mut capitalize_next = false;
+    let mut i = 0;
+
+    while i < tokens.len() {
+        if !tokens[i].drop {
+            let mut word = tokens[i].text.to_string();
+            if capitalize_next {
+                let mut chars = word.chars();
+                if let Some(first) = chars.next() {
+                    word = first.to_uppercase().chain(chars).collect();
+                }
+                capitalize_next = false;
+            }
+            words.push(word);
+            i += 1;
+            continue;
+        }
+
+        // A run of dropped words
+        let start = i;
+        while i < tokens.len() && tokens[i].drop {
+            i += 1;
+        }
+        let run = &tokens[start..i];
+        let last = run[run.len() - 1].text;
+
+        let at_sentence_start = words.last().is_none_or(|w| ends_sentence(w));
+        if at_sentence_start && run[0].text.starts_with(|c: char| c.is_uppercase()) {
+            capitalize_next = true;
+        }
+
+        if let Some(previous) = words.last_mut() {
+            if ends_sentence(last) && !ends_sentence(previous) {
+                // "great, um." -> "great."
+                let terminal: String = last.chars().rev().take_while(|c| matches!(c, '.' | '!' | '?' | '…')).collect();
+                *previous = previous.trim_end_matches([',', ';', ':']).to_string();
+                previous.extend(terminal.chars().rev());
+            } else if ends_with_pause(last) && previous.ends_with(',') {
+                // "it was, you know, great" -> "it was great"
+                previous.pop();
+            }
+        }
+    }
+
+    words.join(" ")
+}
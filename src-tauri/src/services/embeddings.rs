@@ -0,0 +1,409 @@
+// Text embeddings for semantic search: an OpenAI-compatible HTTP provider and an offline hashing fallback
+use reqwest::{Client, Url};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use super::settings::EmbeddingSettings;
+use super::transcript_parser::ParsedSegment;
+
+// Dimensions of the built-in hashing embedder
+const HASHING_DIMENSIONS: usize = 512;
+// Texts sent per request to an HTTP provider
+const HTTP_BATCH_SIZE: usize = 64;
+// Chunks close at a segment boundary once they reach this many words; longer segments are split
+const CHUNK_TARGET_WORDS: usize = 120;
+
+pub enum EmbeddingProvider {
+    Http(HttpEmbedder),
+    Hashing(HashingEmbedder),
+}
+
+impl EmbeddingProvider {
+    pub fn from_settings(settings: &EmbeddingSettings, api_key: Option<String>) -> Result<Self, String> {
+        match settings.provider.as_str() {
+            "hashing" => Ok(EmbeddingProvider::Hashing(HashingEmbedder)),
+            "http" => Ok(EmbeddingProvider::Http(HttpEmbedder::new(
+                &settings.endpoint,
+                &settings.model,
+                api_key,
+            )?)),
+            other => Err(format!("Unknown embedding provider: {}", other)),
+        }
+    }
+
+    /// Identifies the vector space; vectors from different models are never compared. The same
+    /// model name can mean different models on different servers, so HTTP IDs include the endpoint.
+    pub fn model_id(&self) -> String {
+        match self {
+            EmbeddingProvider::Http(http) => http_model_id(&http.endpoint, &http.model),
+            EmbeddingProvider::Hashing(_) => hashing_model_id(),
+        }
+    }
+
+    /// The ID `from_settings` would produce, without building a client or reading the API key
+    pub fn model_id_for_settings(settings: &EmbeddingSettings) -> Result<String, String> {
+        match settings.provider.as_str() {
+            "hashing" => Ok(hashing_model_id()),
+            "http" => Ok(http_model_id(&normalize_endpoint(&settings.endpoint)?, &settings.model)),
+            other => Err(format!("Unknown embedding provider: {}", other)),
+        }
+    }
+
+    pub async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, String> {
+        match self {
+            EmbeddingProvider::Http(http) => http.embed(texts).await,
+            EmbeddingProvider::Hashing(hashing) => Ok(texts.iter().map(|t| hashing.embed(t)).collect()),
+        }
+    }
+}
+
+fn http_model_id(endpoint: &str, model: &str) -> String {
+    format!("http:{}|{}", endpoint, model)
+}
+
+fn hashing_model_id() -> String {
+    format!("hashing-{}", HASHING_DIMENSIONS)
+}
+
+pub struct HttpEmbedder {
+    client: Client,
+    endpoint: String,
+    url: String,
+    model: String,
+    api_key: Option<String>,
+}
+
+#[derive(Serialize)]
+struct EmbeddingRequest<'a> {
+    model: &'a str,
+    input: &'a [String],
+}
+
+#[derive(Deserialize)]
+struct EmbeddingResponse {
+    data: Vec<EmbeddingData>,
+}
+
+#[derive(Deserialize)]
+struct EmbeddingData {
+    index: usize,
+    embedding: Vec<f32>,
+}
+
+impl HttpEmbedder {
+    pub fn new(endpoint: &str, model: &str, api_key: Option<String>) -> Result<Self, String> {
+        let endpoint = normalize_endpoint(endpoint)?;
+
+        let client = Client::builder()
+            .timeout(Duration::from_secs(60))
+            .build()
+            .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+
+        Ok(Self {
+            client,
+            url: format!("{}/embeddings", endpoint),
+            endpoint,
+            model: model.to_string(),
+            api_key,
+        })
+    }
+
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, String> {
+        let mut vectors = Vec::with_capacity(texts.len());
+
+        for batch in texts.chunks(HTTP_BATCH_SIZE) {
+            let mut request = self.client.post(&self.url).json(&EmbeddingRequest {
+                model: &self.model,
+                input: batch,
+            });
+            if let Some(key) = &self.api_key {
+                request = request.bearer_auth(key);
+            }
+
+            let response = request
+                .send()
+                .await
+                .map_err(|e| format!("Embedding request failed: {}", e))?;
+
+            let status = response.status();
+            if !status.is_success() {
+                let body = response.text().await.unwrap_or_default();
+                return Err(format!("Embedding endpoint returned {}: {}", status, body));
+            }
+
+            let mut parsed: EmbeddingResponse = response
+                .json()
+                .await
+                .map_err(|e| format!("Invalid embedding response: {}", e))?;
+
+            if parsed.data.len() != batch.len() {
+                return Err(format!(
+                    "Embedding endpoint returned {} vectors for {} inputs",
+                    parsed.data.len(),
+                    batch.len()
+                ));
+            }
+
+            parsed.data.sort_by_key(|d| d.index);
+            vectors.extend(parsed.data.into_iter().map(|d| normalize(d.embedding)));
+        }
+
+        Ok(vectors)
+    }
+}
+
+// Parsed and re-serialized so "HTTPS://Host:443/v1/" and "https://host/v1" name the same server
+fn normalize_endpoint(endpoint: &str) -> Result<String, String> {
+    let trimmed = endpoint.trim();
+    let url = Url::parse(trimmed)
+        .ok()
+        .filter(|url| url.scheme() == "http" || url.scheme() == "https")
+        .ok_or_else(|| format!("Embedding endpoint must be an http(s) URL: {}", trimmed))?;
+    Ok(url.as_str().trim_end_matches('/').to_string())
+}
+
+/// Offline fallback: signed feature hashing of words, word pairs and character trigrams.
+/// It only captures shared vocabulary and word forms, not meaning, but keeps semantic search
+/// usable without a model server.
+pub struct HashingEmbedder;
+
+impl HashingEmbedder {
+    pub fn embed(&self, text: &str) -> Vec<f32> {
+        let mut vector = vec![0f32; HASHING_DIMENSIONS];
+        let words: Vec<String> = text
+            .split(|c: char| !c.is_alphanumeric() && c != '\'')
+            .filter(|w| !w.is_empty())
+            .map(|w| w.to_lowercase())
+            .collect();
+
+        let mut add = |feature: &str, weight: f32| {
+            let hash = fnv1a(feature.as_bytes());
+            let index = (hash % HASHING_DIMENSIONS as u64) as usize;
+            let sign = if (hash >> 63) & 1 == 0 { 1.0 } else { -1.0 };
+            vector[index] += sign * weight;
+        };
+
+        for word in &words {
+            add(&format!("w:{}", word), 1.0);
+
+            let padded: Vec<char> = format!("<{}>", word).chars().collect();
+            for trigram in padded.windows(3) {
+                add(&format!("c:{}", trigram.iter().collect::<String>()), 0.25);
+            }
+        }
+
+        for pair in words.windows(2) {
+            add(&format!("b:{} {}", pair[0], pair[1]), 0.5);
+        }
+
+        // Dampen repeated features the way sublinear tf does
+        for value in vector.iter_mut() {
+            *value = value.signum() * value.abs().sqrt();
+        }
+
+        normalize(vector)
+    }
+}
+
+// 64-bit FNV-1a; stable across builds, unlike std's hasher
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+fn normalize(mut vector: Vec<f32>) -> Vec<f32> {
+    let norm = vector.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        vector.iter_mut().for_each(|v| *v /= norm);
+    }
+    vector
+}
+
+/// Cosine similarity; vectors are stored normalized, but this doesn't rely on it
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() || a.is_empty() {
+        return 0.0;
+    }
+
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|v| v * v).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|v| v * v).sum::<f32>().sqrt();
+
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a * norm_b)
+    }
+}
+
+/// Vectors are stored as little-endian f32 BLOBs
+pub fn vector_to_blob(vector: &[f32]) -> Vec<u8> {
+    vector.iter().flat_map(|v| v.to_le_bytes()).collect()
+}
+
+pub fn blob_to_vector(blob: &[u8]) -> Vec<f32> {
+    blob.chunks_exact(4)
+        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect()
+}
+
+#[derive(Debug, Clone)]
+pub struct TextChunk {
+    pub segments: Option<(usize, usize)>, // First and last segment index; None for unsegmented text
+    pub start_time: Option<f64>,
+    pub end_time: Option<f64>,
+    pub text: String,
+}
+
+/// Group consecutive segments into passages of roughly CHUNK_TARGET_WORDS words.
+/// Chunks end on a segment boundary so they keep usable timestamps, except that a segment longer
+/// than a chunk (an untimed paragraph, a long monologue cue) is split into pieces of its own.
+pub fn chunk_segments(segments: &[ParsedSegment]) -> Vec<TextChunk> {
+    let mut chunks: Vec<TextChunk> = Vec::new();
+    let mut current: Option<(TextChunk, usize)> = None;
+
+    for (index, segment) in segments.iter().enumerate() {
+        let words = segment.text.split_whitespace().count();
+        if words == 0 {
+            continue;
+        }
+
+        if words > CHUNK_TARGET_WORDS {
+            chunks.extend(current.take().map(|(chunk, _)| chunk));
+            chunks.extend(split_segment(index, segment));
+            continue;
+        }
+
+        let (chunk, count) = current.get_or_insert_with(|| {
+            (
+                TextChunk {
+                    segments: Some((index, index)),
+                    start_time: segment.start_time,
+                    end_time: segment.end_time,
+                    text: String::new(),
+                },
+                0,
+            )
+        });
+
+        if !chunk.text.is_empty() {
+            chunk.text.push(' ');
+        }
+        chunk.text.push_str(&segment.text);
+        chunk.segments = chunk.segments.map(|(first, _)| (first, index));
+        chunk.end_time = segment.end_time.or(chunk.end_time);
+        *count += words;
+
+        if *count >= CHUNK_TARGET_WORDS {
+            chunks.extend(current.take().map(|(chunk, _)| chunk));
+        }
+    }
+
+    chunks.extend(current.map(|(chunk, _)| chunk));
+    chunks
+}
+
+// Even pieces of at most CHUNK_TARGET_WORDS words, with times interpolated across the segment
+fn split_segment(index: usize, segment: &ParsedSegment) -> Vec<TextChunk> {
+    let words: Vec<&str> = segment.text.split_whitespace().collect();
+    let pieces = words.len().div_ceil(CHUNK_TARGET_WORDS);
+    let piece_words = words.len().div_ceil(pieces);
+
+    let time_at = |word: usize| match (segment.start_time, segment.end_time) {
+        (Some(start), Some(end)) if end > start => Some(start + (end - start) * word as f64 / words.len() as f64),
+        _ => None,
+    };
+
+    words
+        .chunks(piece_words)
+        .enumerate()
+        .map(|(piece, text)| {
+            let first = piece * piece_words;
+            let last = first + text.len();
+            TextChunk {
+                segments: Some((index, index)),
+                start_time: time_at(first).or(segment.start_time),
+                end_time: time_at(last).or(segment.end_time),
+                text: text.join(" "),
+            }
+        })
+        .collect()
+}
+
+/// Chunks for transcripts stored without segments: untimed passages of CHUNK_TARGET_WORDS words
+pub fn chunk_plain_text(content: &str) -> Vec<TextChunk> {
+    let words: Vec<&str> = content.split_whitespace().collect();
+
+    words
+        .chunks(CHUNK_TARGET_WORDS)
+        .map(|words| TextChunk {
+            segments: None,
+            start_time: None,
+            end_time: None,
+            text: words.join(" "),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(text: &str, start_time: Option<f64>, end_time: Option<f64>) -> ParsedSegment {
+        ParsedSegment { start_time, end_time, speaker: None, text: text.to_string(), words: Vec::new() }
+    }
+
+    #[test]
+    fn http_model_ids_include_the_endpoint() {
+        let id = |endpoint: &str, model: &str| {
+            EmbeddingProvider::Http(HttpEmbedder::new(endpoint, model, None).unwrap()).model_id()
+        };
+        assert_eq!(id(" HTTPS://API.Example.com:443/v1/ ", "embed-small"), "http:https://api.example.com/v1|embed-small");
+        assert_eq!(id("https://api.example.com/v1", "embed-small"), id("https://api.example.com/v1/", "embed-small"));
+        assert_ne!(id("http://localhost:11434/v1", "embed-small"), id("https://api.example.com/v1", "embed-small"));
+        assert!(HttpEmbedder::new("ftp://example.com", "embed-small", None).is_err());
+        assert!(HttpEmbedder::new("localhost:8080", "embed-small", None).is_err());
+
+        let settings = EmbeddingSettings {
+            provider: "http".to_string(),
+            endpoint: "https://api.example.com/v1/".to_string(),
+            model: "embed-small".to_string(),
+            api_key_service: "openai".to_string(),
+        };
+        assert_eq!(EmbeddingProvider::model_id_for_settings(&settings), Ok(id("https://api.example.com/v1", "embed-small")));
+        let hashing = EmbeddingSettings { provider: "hashing".to_string(), ..settings };
+        assert_eq!(EmbeddingProvider::model_id_for_settings(&hashing), Ok(EmbeddingProvider::Hashing(HashingEmbedder).model_id()));
+    }
+
+    #[test]
+    fn chunks_close_at_segment_boundaries() {
+        let sentence = "word ".repeat(50);
+        let segments: Vec<ParsedSegment> = (0..5)
+            .map(|i| segment(&sentence, Some(i as f64 * 10.0), Some(i as f64 * 10.0 + 10.0)))
+            .collect();
+        let chunks = chunk_segments(&segments);
+        let ranges: Vec<_> = chunks.iter().map(|c| (c.segments, c.start_time, c.end_time)).collect();
+        assert_eq!(ranges, vec![(Some((0, 2)), Some(0.0), Some(30.0)), (Some((3, 4)), Some(30.0), Some(50.0))]);
+    }
+
+    #[test]
+    fn splits_segments_longer_than_a_chunk() {
+        let long = (0..300).map(|i| format!("w{}", i)).collect::<Vec<_>>().join(" ");
+        let segments = vec![segment("Short intro.", Some(0.0), Some(2.0)), segment(&long, Some(2.0), Some(302.0)), segment(&long, None, None)];
+        let chunks = chunk_segments(&segments);
+
+        let summary: Vec<_> = chunks.iter().map(|c| (c.segments, c.start_time, c.end_time, c.text.split_whitespace().count())).collect();
+        assert_eq!(summary, vec![
+            (Some((0, 0)), Some(0.0), Some(2.0), 2),
+            (Some((1, 1)), Some(2.0), Some(102.0), 100),
+            (Some((1, 1)), Some(102.0), Some(202.0), 100),
+            (Some((1, 1)), Some(202.0), Some(302.0), 100),
+            (Some((2, 2)), None, None, 100),
+            (Some((2, 2)), None, None, 100),
+            (Some((2, 2)), None, None, 100),
+        ]);
+        assert!(chunks[1].text.starts_with("w0 ") && chunks[3].text.ends_with(" w299"));
+        assert!(chunks.iter().all(|c| c.text.split_whitespace().count() <= CHUNK_TARGET_WORDS));
+    }
+}
@@ -0,0 +1,94 @@
+// Structured diffs between two JSON documents, for comparing generated content versions
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use similar::{capture_diff_slices, Algorithm, DiffOp};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonChange {
+    pub path: String, // JSON pointer; array indices are old positions for removals, new ones otherwise
+    pub kind: String, // "added", "removed" or "changed"
+    pub old_value: Option<Value>,
+    pub new_value: Option<Value>,
+}
+
+fn escape_pointer(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+fn change(path: String, kind: &str, old_value: Option<&Value>, new_value: Option<&Value>) -> JsonChange {
+    JsonChange {
+        path,
+        kind: kind.to_string(),
+        old_value: old_value.cloned(),
+        new_value: new_value.cloned(),
+    }
+}
+
+/// Every difference between `old` and `new`, in key order. Objects are compared key by key
+/// and arrays element by element after aligning them, so inserting a tweet into a thread shows as
+/// one added element rather than every later element changing. Values of different types, and
+/// changed strings or numbers, are reported as a single change at their path.
+pub fn diff_json(old: &Value, new: &Value) -> Vec<JsonChange> {
+    let mut changes = Vec::new();
+    diff_at(String::new(), old, new, &mut changes);
+    changes
+}
+
+fn diff_at(path: String, old: &Value, new: &Value, changes: &mut Vec<JsonChange>) {
+    match (old, new) {
+        (Value::Object(old_fields), Value::Object(new_fields)) => {
+            for (key, old_value) in old_fields {
+                let field_path = format!("{}/{}", path, escape_pointer(key));
+                match new_fields.get(key) {
+                    Some(new_value) => diff_at(field_path, old_value, new_value, changes),
+                    None => changes.push(change(field_path, "removed", Some(old_value), None)),
+                }
+            }
+            for (key, new_value) in new_fields {
+                if !old_fields.contains_key(key) {
+                    let field_path = format!("{}/{}", path, escape_pointer(key));
+                    changes.push(change(field_path, "added", None, Some(new_value)));
+                }
+            }
+        }
+        (Value::Array(old_items), Value::Array(new_items)) => diff_arrays(&path, old_items, new_items, changes),
+        _ if old == new => {}
+        _ => changes.push(change(path, "changed", Some(old), Some(new))),
+    }
+}
+
+fn diff_arrays(path: &str, old_items: &[Value], new_items: &[Value], changes: &mut Vec<JsonChange>) {
+    // Elements are aligned on their serialized form, since JSON values can't be hashed
+    let old_keys: Vec<String> = old_items.iter().map(Value::to_string).collect();
+    let new_keys: Vec<String> = new_items.iter().map(Value::to_string).collect();
+    let item_path = |index: usize| format!("{}/{}", path, index);
+
+    for op in capture_diff_slices(Algorithm::Myers, &old_keys, &new_keys) {
+        match op {
+            DiffOp::Equal { .. } => {}
+            DiffOp::Delete { old_index, old_len, .. } => {
+                for (index, item) in old_items.iter().enumerate().skip(old_index).take(old_len) {
+                    changes.push(change(item_path(index), "removed", Some(item), None));
+                }
+            }
+            DiffOp::Insert { new_index, new_len, .. } => {
+                for (index, item) in new_items.iter().enumerate().skip(new_index).take(new_len) {
+                    changes.push(change(item_path(index), "added", None, Some(item)));
+                }
+            }
+            // Replaced elements are compared pairwise, so an edited tweet shows what changed in it
+            DiffOp::Replace { old_index, old_len, new_index, new_len } => {
+                let paired = old_len.min(new_len);
+                for offset in 0..paired {
+                    diff_at(item_path(new_index + offset), &old_items[old_index + offset], &new_items[new_index + offset], changes);
+                }
+                for (index, item) in old_items.iter().enumerate().skip(old_index + paired).take(old_len - paired) {
+                    changes.push(change(item_path(index), "removed", Some(item), None));
+                }
+                for (index, item) in new_items.iter().enumerate().skip(new_index + paired).take(new_len - paired) {
+                    changes.push(change(item_path(index), "added", None, Some(item)));
+                }
+            }
+        }
+    }
+}
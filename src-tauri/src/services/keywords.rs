@@ -0,0 +1,190 @@
+// Offline keyword extraction: TF-IDF against the transcript corpus combined with RAKE phrase scores
+use std::collections::{HashMap, HashSet};
+
+use super::stopwords::{is_stopword, tokenize_words};
+
+// Longest phrase proposed as a tag
+const MAX_PHRASE_WORDS: usize = 3;
+// Share of the relevance that comes from TF-IDF; the rest comes from RAKE
+const TFIDF_WEIGHT: f64 = 0.6;
+// Single words that occur fewer times than this in a long transcript are ignored
+const MIN_WORD_FREQUENCY: usize = 2;
+// MIN_WORD_FREQUENCY only applies to transcripts with at least this many words
+const SHORT_TRANSCRIPT_WORDS: usize = 150;
+
+#[derive(Debug, Clone)]
+pub struct Keyword {
+    pub term: String,
+    pub relevance: f64, // 0.0 to 1.0
+    pub frequency: usize,
+}
+
+/// Document frequencies of words across the transcript corpus
+#[derive(Debug, Default)]
+pub struct CorpusStats {
+    documents: usize,
+    document_frequency: HashMap<String, usize>,
+}
+
+impl CorpusStats {
+    pub fn add_document(&mut self, language: &str, text: &str) {
+        let words: HashSet<String> = tokenize_words(text)
+            .into_iter()
+            .filter(|w| is_candidate_word(language, w))
+            .collect();
+
+        for word in words {
+            *self.document_frequency.entry(word).or_insert(0) += 1;
+        }
+        self.documents += 1;
+    }
+
+    // Smoothed inverse document frequency; words never seen in the corpus get the highest value
+    fn idf(&self, word: &str) -> f64 {
+        let df = self.document_frequency.get(word).copied().unwrap_or(0);
+        ((self.documents as f64 + 1.0) / (df as f64 + 1.0)).ln() + 1.0
+    }
+
+    /// TF-IDF weights of a document's words (log-scaled term frequency), for comparing documents
+    pub fn tfidf_vector(&self, language: &str, text: &str) -> HashMap<String, f64> {
+        let mut frequency: HashMap<String, usize> = HashMap::new();
+        for word in tokenize_words(text).into_iter().filter(|w| is_candidate_word(language, w)) {
+            *frequency.entry(word).or_insert(0) += 1;
+        }
+
+        frequency
+            .into_iter()
+            .map(|(word, count)| {
+                let weight = (1.0 + (count as f64).ln()) * self.idf(&word);
+                (word, weight)
+            })
+            .collect()
+    }
+}
+
+fn is_candidate_word(language: &str, word: &str) -> bool {
+    word.chars().count() > 2 && word.chars().any(|c| c.is_alphabetic()) && !is_stopword(language, word)
+}
+
+// RAKE candidate phrases: runs of content words between stopwords and punctuation
+fn candidate_phrases(language: &str, text: &str) -> Vec<Vec<String>> {
+    let mut phrases = Vec::new();
+
+    for fragment in text.split(['.', ',', '!', '?', ';', ':', '(', ')', '"', '\n', '—', '–', '…']) {
+        let mut current: Vec<String> = Vec::new();
+        for word in tokenize_words(fragment) {
+            if is_candidate_word(language, &word) {
+                current.push(word);
+            } else if !current.is_empty() {
+                phrases.push(std::mem::take(&mut current));
+            }
+        }
+        if !current.is_empty() {
+            phrases.push(current);
+        }
+    }
+
+    phrases
+}
+
+fn normalize_scores(scores: &mut HashMap<String, f64>) {
+    let max = scores.values().cloned().fold(0.0, f64::max);
+    if max > 0.0 {
+        scores.values_mut().for_each(|s| *s /= max);
+    }
+}
+
+/// Rank the terms and phrases that characterize `text` relative to the rest of the corpus.
+/// TF-IDF favours words that are frequent here but rare elsewhere; RAKE favours words that occur
+/// in multi-word phrases. A term is left out when it contains, or is contained in, a higher-ranked one.
+pub fn extract_keywords(text: &str, language: &str, corpus: &CorpusStats, max_keywords: usize) -> Vec<Keyword> {
+    let phrases = candidate_phrases(language, text);
+    let total_words = tokenize_words(text).len();
+
+    // RAKE word scores: degree (co-occurrence within phrases) over frequency
+    let mut word_frequency: HashMap<&str, usize> = HashMap::new();
+    let mut word_degree: HashMap<&str, usize> = HashMap::new();
+    for phrase in &phrases {
+        for word in phrase {
+            *word_frequency.entry(word).or_insert(0) += 1;
+            *word_degree.entry(word).or_insert(0) += phrase.len();
+        }
+    }
+
+    // Term frequencies over every sub-phrase of up to MAX_PHRASE_WORDS words, so "pricing strategy"
+    // is counted inside both "pricing strategy changed" and "new pricing strategy"
+    let mut term_frequency: HashMap<String, usize> = HashMap::new();
+    for phrase in &phrases {
+        for length in 1..=MAX_PHRASE_WORDS.min(phrase.len()) {
+            for window in phrase.windows(length) {
+                *term_frequency.entry(window.join(" ")).or_insert(0) += 1;
+            }
+        }
+    }
+
+    let max_frequency = term_frequency.values().copied().max().unwrap_or(1) as f64;
+    let mut tfidf: HashMap<String, f64> = HashMap::new();
+    let mut rake: HashMap<String, f64> = HashMap::new();
+
+    for (term, &frequency) in &term_frequency {
+        let words: Vec<&str> = term.split(' ').collect();
+
+        // One-off single words are noise in anything longer than a short clip
+        if words.len() == 1 && frequency < MIN_WORD_FREQUENCY && total_words >= SHORT_TRANSCRIPT_WORDS {
+            continue;
+        }
+        // Multi-word phrases need to recur to be a theme rather than a passing remark
+        if words.len() > 1 && frequency < 2 {
+            continue;
+        }
+
+        // Phrases are too sparse for their own document frequency, so use their words' mean IDF
+        let idf = words.iter().map(|w| corpus.idf(w)).sum::<f64>() / words.len() as f64;
+        tfidf.insert(term.clone(), (0.5 + 0.5 * frequency as f64 / max_frequency) * idf);
+
+        let rake_score: f64 = words
+            .iter()
+            .map(|w| word_degree[w] as f64 / word_frequency[w] as f64)
+            .sum();
+        rake.insert(term.clone(), rake_score * (frequency as f64).ln_1p());
+    }
+
+    normalize_scores(&mut tfidf);
+    normalize_scores(&mut rake);
+
+    let mut ranked: Vec<Keyword> = tfidf
+        .iter()
+        .map(|(term, &tfidf_score)| Keyword {
+            term: term.clone(),
+            relevance: TFIDF_WEIGHT * tfidf_score + (1.0 - TFIDF_WEIGHT) * rake.get(term).copied().unwrap_or(0.0),
+            frequency: term_frequency[term],
+        })
+        .collect();
+    ranked.sort_by(|a, b| b.relevance.total_cmp(&a.relevance).then_with(|| a.term.cmp(&b.term)));
+
+    let mut selected: Vec<Keyword> = Vec::new();
+    for keyword in ranked {
+        if selected.len() >= max_keywords {
+            break;
+        }
+
+        let words: HashSet<&str> = keyword.term.split(' ').collect();
+        let covered = selected.iter().any(|s| {
+            let chosen: HashSet<&str> = s.term.split(' ').collect();
+            words.is_subset(&chosen) || chosen.is_subset(&words)
+        });
+        if !covered {
+            selected.push(keyword);
+        }
+    }
+
+    // Rescale so the strongest tag has relevance 1.0
+    let top = selected.first().map_or(0.0, |k| k.relevance);
+    if top > 0.0 {
+        for keyword in &mut selected {
+            keyword.relevance = (keyword.relevance / top).clamp(0.0, 1.0);
+        }
+    }
+
+    selected
+}
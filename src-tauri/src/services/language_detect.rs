@@ -0,0 +1,310 @@
+// Offline language identification using character trigram profiles built from embedded sample text
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+// Texts shorter than this many trigrams are reported with reduced confidence
+const MIN_RELIABLE_TRIGRAMS: usize = 20;
+// Evidence is capped so long transcripts don't push every posterior to 1.0
+const MAX_EVIDENCE_TRIGRAMS: f64 = 100.0;
+// Only the start of very long transcripts is examined
+const MAX_SAMPLE_CHARS: usize = 20_000;
+// Share of the text's trigrams the best profile must contain; supported languages score about
+// 0.45 to 0.7, near neighbours such as Dutch or Swedish about 0.2 to 0.35
+const MIN_TRIGRAM_COVERAGE: f64 = 0.38;
+// Guesses below this confidence are reported as no guess, so callers fall back to their default
+const MIN_CONFIDENCE: f64 = 0.6;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LanguageGuess {
+    pub language: String,
+    pub confidence: f64,
+}
+
+struct LanguageProfile {
+    code: &'static str,
+    counts: HashMap<String, u32>,
+    total: u32,
+}
+
+// Representative everyday and spoken-register text per supported language
+const SAMPLES: &[(&str, &str)] = &[
+    ("en", "Today we are going to talk about how we build our business and what we learned along the way. \
+        I think the most important thing is that you have to be honest with the people who work with you. \
+        When we started this company we did not have any money, so we had to find customers very quickly. \
+        What would you say to someone who wants to start their own project right now? Well, I would tell them \
+        that it is going to be harder than they think, but it is also the best thing they could do. There were \
+        times when we thought about giving up, and those were the moments that taught us the most. You know, \
+        the thing about growth is that it comes from doing the work every single day, even when nobody is watching. \
+        Thank you so much for being here with us, and we will see you in the next episode of the show."),
+    ("es", "Hoy vamos a hablar de cómo construimos nuestro negocio y de lo que aprendimos en el camino. \
+        Creo que lo más importante es que tienes que ser honesto con las personas que trabajan contigo. \
+        Cuando empezamos esta empresa no teníamos dinero, así que tuvimos que encontrar clientes muy rápido. \
+        ¿Qué le dirías a alguien que quiere empezar su propio proyecto ahora mismo? Bueno, le diría que \
+        va a ser más difícil de lo que piensa, pero también es lo mejor que puede hacer. Hubo momentos en los \
+        que pensamos en rendirnos, y esos fueron los momentos que más nos enseñaron. Ya sabes, lo que pasa con \
+        el crecimiento es que viene de hacer el trabajo todos los días, incluso cuando nadie está mirando. \
+        Muchas gracias por estar aquí con nosotros, y nos vemos en el próximo episodio del programa."),
+    ("pt", "Hoje nós vamos falar sobre como construímos o nosso negócio e o que aprendemos no caminho. \
+        Eu acho que a coisa mais importante é que você tem que ser honesto com as pessoas que trabalham com você. \
+        Quando começamos esta empresa não tínhamos dinheiro, então tivemos que encontrar clientes muito rápido. \
+        O que você diria para alguém que quer começar o seu próprio projeto agora? Bom, eu diria que \
+        vai ser mais difícil do que ele pensa, mas também é a melhor coisa que ele pode fazer. Houve momentos em \
+        que pensamos em desistir, e foram esses os momentos que mais nos ensinaram. Sabe, a questão do \
+        crescimento é que ele vem de fazer o trabalho todos os dias, mesmo quando ninguém está olhando. \
+        Muito obrigado por estar aqui com a gente, e nos vemos no próximo episódio do programa. Então não é isso, são ações."),
+    ("fr", "Aujourd'hui nous allons parler de la façon dont nous avons construit notre entreprise et de ce que nous \
+        avons appris en chemin. Je pense que la chose la plus importante est d'être honnête avec les gens qui \
+        travaillent avec vous. Quand nous avons commencé cette société nous n'avions pas d'argent, donc nous avons \
+        dû trouver des clients très vite. Qu'est-ce que vous diriez à quelqu'un qui veut lancer son propre projet \
+        maintenant? Eh bien, je lui dirais que ce sera plus difficile qu'il ne le pense, mais c'est aussi la \
+        meilleure chose qu'il puisse faire. Merci beaucoup d'être avec nous, et à bientôt pour le prochain épisode."),
+    ("de", "Heute sprechen wir darüber, wie wir unser Unternehmen aufgebaut haben und was wir dabei gelernt haben. \
+        Ich glaube, das Wichtigste ist, dass man ehrlich zu den Menschen sein muss, die mit einem arbeiten. \
+        Als wir diese Firma gegründet haben, hatten wir kein Geld, also mussten wir sehr schnell Kunden finden. \
+        Was würdest du jemandem sagen, der jetzt sein eigenes Projekt starten will? Nun, ich würde ihm sagen, \
+        dass es schwieriger wird, als er denkt, aber dass es auch das Beste ist, was er tun kann. Vielen Dank, \
+        dass ihr dabei wart, und wir sehen uns in der nächsten Folge der Sendung."),
+    ("it", "Oggi parliamo di come abbiamo costruito la nostra azienda e di quello che abbiamo imparato lungo la strada. \
+        Penso che la cosa più importante sia che devi essere onesto con le persone che lavorano con te. \
+        Quando abbiamo iniziato questa azienda non avevamo soldi, quindi abbiamo dovuto trovare clienti molto \
+        velocemente. Cosa diresti a qualcuno che vuole iniziare il suo progetto adesso? Beh, gli direi che \
+        sarà più difficile di quanto pensa, ma è anche la cosa migliore che possa fare. Grazie mille per essere \
+        stati con noi, e ci vediamo nella prossima puntata del programma."),
+];
+
+fn profiles() -> &'static [LanguageProfile] {
+    static PROFILES: OnceLock<Vec<LanguageProfile>> = OnceLock::new();
+    PROFILES.get_or_init(|| {
+        SAMPLES
+            .iter()
+            .map(|(code, sample)| {
+                let counts = trigram_counts(sample);
+                let total = counts.values().sum();
+                LanguageProfile { code, counts, total }
+            })
+            .collect()
+    })
+}
+
+/// Language codes the detector can return
+pub fn supported_languages() -> Vec<&'static str> {
+    SAMPLES.iter().map(|(code, _)| *code).collect()
+}
+
+// Every supported language is written in Latin script; Cyrillic, CJK and other scripts can't match
+fn mostly_non_latin(text: &str) -> bool {
+    let (latin, other) = text
+        .chars()
+        .filter(|c| c.is_alphabetic())
+        .fold((0usize, 0usize), |(latin, other), c| {
+            // Basic Latin through Latin Extended-B, plus Latin Extended Additional
+            if c <= '\u{024F}' || ('\u{1E00}'..='\u{1EFF}').contains(&c) {
+                (latin + 1, other)
+            } else {
+                (latin, other + 1)
+            }
+        });
+    other > latin
+}
+
+// Lowercased letter trigrams, with word boundaries marked by spaces
+fn trigram_counts(text: &str) -> HashMap<String, u32> {
+    let mut counts = HashMap::new();
+
+    for word in text
+        .split(|c: char| !c.is_alphabetic() && c != '\'')
+        .filter(|w| !w.is_empty())
+    {
+        let padded: Vec<char> = format!(" {} ", word.to_lowercase()).chars().collect();
+        for window in padded.windows(3) {
+            *counts.entry(window.iter().collect()).or_insert(0) += 1;
+        }
+    }
+
+    counts
+}
+
+/// Identify the language of a text. Returns None when the text has no letters, is mostly in a
+/// non-Latin script, or doesn't match any supported language closely enough.
+/// Scores are naive Bayes log-likelihoods over trigrams with add-one smoothing;
+/// confidence is the posterior of the best language.
+pub fn detect_language(text: &str) -> Option<LanguageGuess> {
+    let sample: String = text.chars().take(MAX_SAMPLE_CHARS).collect();
+    if mostly_non_latin(&sample) {
+        return None;
+    }
+    let document = trigram_counts(&sample);
+    let total: u32 = document.values().sum();
+    if total == 0 {
+        return None;
+    }
+
+    let vocabulary = profiles()
+        .iter()
+        .flat_map(|p| p.counts.keys())
+        .collect::<std::collections::HashSet<_>>()
+        .len() as f64;
+
+    // Average log-likelihood per trigram for each language
+    let scores: Vec<(&str, f64)> = profiles()
+        .iter()
+        .map(|profile| {
+            let denominator = profile.total as f64 + vocabulary;
+            let log_likelihood: f64 = document
+                .iter()
+                .map(|(trigram, count)| {
+                    let seen = *profile.counts.get(trigram).unwrap_or(&0) as f64;
+                    *count as f64 * ((seen + 1.0) / denominator).ln()
+                })
+                .sum();
+            (profile.code, log_likelihood / total as f64)
+        })
+        .collect();
+
+    let evidence = (total as f64).min(MAX_EVIDENCE_TRIGRAMS);
+    let best = scores
+        .iter()
+        .cloned()
+        .fold(("", f64::NEG_INFINITY), |best, score| if score.1 > best.1 { score } else { best });
+
+    // The posterior only ranks the supported languages against each other, so an unsupported
+    // language close to one of them (Dutch and German) still scores near 1.0. Require the best
+    // profile to actually know a fair share of the text's trigrams.
+    let best_profile = profiles().iter().find(|profile| profile.code == best.0)?;
+    let covered: u32 = document
+        .iter()
+        .filter(|(trigram, _)| best_profile.counts.contains_key(*trigram))
+        .map(|(_, count)| count)
+        .sum();
+    if (covered as f64 / total as f64) < MIN_TRIGRAM_COVERAGE {
+        return None;
+    }
+
+    let normalizer: f64 = scores.iter().map(|(_, score)| ((score - best.1) * evidence).exp()).sum();
+    let mut confidence = 1.0 / normalizer;
+
+    if (total as usize) < MIN_RELIABLE_TRIGRAMS {
+        confidence *= total as f64 / MIN_RELIABLE_TRIGRAMS as f64;
+    }
+
+    if confidence < MIN_CONFIDENCE {
+        return None;
+    }
+
+    Some(LanguageGuess {
+        language: best.0.to_string(),
+        confidence: (confidence * 1000.0).round() / 1000.0,
+    })
+}
+
+/// Normalize a declared language ("en-US", "English", "pt_BR") to a lowercase primary code
+pub fn normalize_language_code(language: &str) -> Option<String> {
+    let primary = language
+        .trim()
+        .split(['-', '_'])
+        .next()?
+        .to_lowercase();
+
+    let code = match primary.as_str() {
+        "english" => "en",
+        "spanish" | "español" | "espanol" => "es",
+        "portuguese" | "português" | "portugues" => "pt",
+        "french" | "français" => "fr",
+        "german" | "deutsch" => "de",
+        "italian" | "italiano" => "it",
+        // ISO 639-2/3 codes for languages that have a two-letter code
+        "eng" => "en",
+        "spa" => "es",
+        "por" => "pt",
+        "fra" | "fre" => "fr",
+        "deu" | "ger" => "de",
+        "ita" => "it",
+        "nld" | "dut" => "nl",
+        "jpn" => "ja",
+        "zho" | "chi" => "zh",
+        "kor" => "ko",
+        "rus" => "ru",
+        "ara" => "ar",
+        "hin" => "hi",
+        "pol" => "pl",
+        "swe" => "sv",
+        "tur" => "tr",
+        code if (code.len() == 2 || code.len() == 3) && code.chars().all(|c| c.is_ascii_alphabetic()) => code,
+        _ => return None,
+    };
+
+    Some(code.to_string())
+}
+
+/// Normalize an optional language filter the way stored languages are; blank means no filter
+pub fn normalize_language_filter(language: Option<&str>) -> Result<Option<String>, String> {
+    match language.map(str::trim).filter(|l| !l.is_empty()) {
+        Some(language) => normalize_language_code(language)
+            .map(Some)
+            .ok_or_else(|| format!("Invalid language code: {}", language)),
+        None => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detected(text: &str) -> Option<String> {
+        detect_language(text).map(|guess| guess.language)
+    }
+
+    #[test]
+    fn detects_supported_languages() {
+        let cases = [
+            ("en", "The quarterly numbers look solid. Revenue grew twelve percent, churn dropped, and the new onboarding flow converted much better than expected."),
+            ("es", "Entonces, básicamente, lo que hicimos fue cambiar toda la estrategia de ventas. Antes vendíamos a pequeñas empresas y ahora nos enfocamos en clientes grandes."),
+            ("pt", "Então, basicamente, o que a gente fez foi mudar toda a estratégia de vendas. Antes a gente vendia para pequenas empresas e agora focamos em clientes grandes."),
+            ("fr", "Alors en gros, ce qu'on a fait, c'est changer toute la stratégie commerciale. Avant on vendait aux petites entreprises et maintenant on se concentre sur les grands comptes."),
+            ("de", "Also im Grunde haben wir die ganze Vertriebsstrategie geändert. Früher haben wir an kleine Firmen verkauft, jetzt konzentrieren wir uns auf große Kunden."),
+            ("it", "Quindi, in pratica, abbiamo cambiato tutta la strategia di vendita. Prima vendevamo alle piccole imprese e ora ci concentriamo sui grandi clienti."),
+        ];
+        for (language, text) in cases {
+            let guess = detect_language(text).unwrap_or_else(|| panic!("no guess for {}", language));
+            assert_eq!(guess.language, language);
+            assert!(guess.confidence >= 0.9, "{} confidence {}", language, guess.confidence);
+        }
+    }
+
+    #[test]
+    fn rejects_non_latin_scripts() {
+        assert_eq!(detected("今日は私たちがどのようにビジネスを築いてきたか、そしてその過程で何を学んだかについて話します。"), None);
+        assert_eq!(detected("Сегодня мы поговорим о том, как мы построили наш бизнес и чему мы научились на этом пути."), None);
+        assert_eq!(detected("Σήμερα θα μιλήσουμε για το πώς χτίσαμε την επιχείρησή μας και τι μάθαμε στην πορεία."), None);
+        assert!(mostly_non_latin("Привет, OK"));
+        assert!(!mostly_non_latin("Crème brûlée, naïve façade, Łódź"));
+    }
+
+    #[test]
+    fn rejects_unsupported_latin_languages() {
+        assert_eq!(detected("Dus eigenlijk is het heel simpel: als je klanten niet tevreden zijn, dan kom je nergens. We hebben dat in het begin niet goed begrepen en daar hebben we veel van geleerd."), None);
+        assert_eq!(detected("Hej allihopa, idag ska vi prata om hur vi byggde vårt företag och vad vi lärde oss på vägen."), None);
+        assert_eq!(detected("Dzień dobry, dzisiaj porozmawiamy o tym, jak zbudowaliśmy naszą firmę i czego się nauczyliśmy po drodze."), None);
+        assert_eq!(detected("Bugün işimizi nasıl kurduğumuzu ve yol boyunca neler öğrendiğimizi konuşacağız."), None);
+    }
+
+    #[test]
+    fn rejects_text_too_short_to_tell() {
+        assert_eq!(detected(""), None);
+        assert_eq!(detected("12:30 -- 45%"), None);
+        assert_eq!(detected("ok yes"), None);
+    }
+
+    #[test]
+    fn normalizes_declared_languages() {
+        assert_eq!(normalize_language_code("en-US").as_deref(), Some("en"));
+        assert_eq!(normalize_language_code("Português").as_deref(), Some("pt"));
+        assert_eq!(normalize_language_code("pt_BR").as_deref(), Some("pt"));
+        assert_eq!(normalize_language_code("ger").as_deref(), Some("de"));
+        assert_eq!(normalize_language_code("nl").as_deref(), Some("nl"));
+        assert_eq!(normalize_language_code("Klingon"), None);
+        assert_eq!(normalize_language_filter(Some("  ")), Ok(None));
+        assert!(normalize_language_filter(Some("12")).is_err());
+    }
+}
@@ -1,3 +1,21 @@
 pub mod descript_auth;
+pub mod caption_export;
+pub mod chapters;
+pub mod clip_finder;
+pub mod content_metrics;
+pub mod content_render;
 pub mod descript;
+pub mod disfluency;
+pub mod embeddings;
+pub mod json_diff;
+pub mod keywords;
+pub mod language_detect;
+pub mod redaction;
+pub mod related;
+pub mod search_query;
 pub mod settings;
+pub mod stopwords;
+pub mod summarizer;
+pub mod text_encoding;
+pub mod transcript_edit;
+pub mod transcript_parser;
\ No newline at end of file
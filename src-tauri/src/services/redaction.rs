@@ -0,0 +1,421 @@
+// Personal data and brand-safety scrubbing: emails, phone numbers, links, card numbers, profanity
+// and a user blocklist, found in text and replaced with placeholders
+use serde::{Deserialize, Serialize};
+
+// Characters stripped from the edges of a word before it is checked
+const LEADING_PUNCTUATION: &[char] = &['"', '\'', '(', '[', '<', '{', '“', '‘', '«', '¿', '¡'];
+const TRAILING_PUNCTUATION: &[char] = &['"', '\'', ')', ']', '>', '}', '”', '’', '»', '.', ',', ';', ':', '!', '?', '…'];
+
+// Endings that make a bare "name.tld" a web address rather than a sentence with a missing space
+const COMMON_TLDS: &[&str] = &[
+    "com", "org", "net", "io", "co", "ai", "app", "dev", "edu", "gov", "info", "biz", "me", "tv", "fm", "ly", "gg",
+    "us", "uk", "ca", "au", "de", "fr", "es", "it", "nl", "br", "pt", "mx", "in", "jp",
+];
+
+// Swear words and slurs, with their common inflections. Matched as whole words, any case.
+const PROFANITY: &[&str] = &[
+    "fuck", "fucks", "fucked", "fucker", "fuckers", "fucking", "motherfucker", "motherfuckers", "motherfucking",
+    "shit", "shits", "shitty", "shitting", "bullshit", "horseshit", "bitch", "bitches", "bitching",
+    "asshole", "assholes", "bastard", "bastards", "dick", "dicks", "dickhead", "cunt", "cunts",
+    "piss", "pissed", "prick", "pricks", "wanker", "wankers", "twat", "bollocks", "goddamn", "goddamned",
+    "slut", "sluts", "whore", "whores", "retard", "retarded", "fag", "faggot", "nigger", "niggers",
+    // Spanish, Portuguese, French, German, Italian
+    "mierda", "puta", "puto", "joder", "coño", "cabrón", "pendejo", "gilipollas",
+    "merda", "porra", "caralho", "foda", "foder",
+    "merde", "putain", "connard", "connasse", "salope", "enculé",
+    "scheiße", "scheisse", "arschloch", "fotze", "wichser",
+    "cazzo", "stronzo", "vaffanculo", "puttana", "minchia",
+];
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum RedactionCategory {
+    Email,
+    Phone,
+    Url,
+    CardNumber,
+    Profanity,
+    Blocklist,
+}
+
+impl RedactionCategory {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RedactionCategory::Email => "email",
+            RedactionCategory::Phone => "phone",
+            RedactionCategory::Url => "url",
+            RedactionCategory::CardNumber => "card_number",
+            RedactionCategory::Profanity => "profanity",
+            RedactionCategory::Blocklist => "blocklist",
+        }
+    }
+}
+
+/// A name or term that must not be published, with what to show instead ("[REDACTED]" if none)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlocklistTerm {
+    pub term: String,
+    pub replacement: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RedactionHit {
+    pub category: RedactionCategory,
+    pub start: usize, // Byte offsets into the scanned text
+    pub end: usize,
+    pub text: String,
+    pub replacement: String,
+}
+
+// Whitespace-separated words with their byte offsets, edge punctuation trimmed
+fn words(text: &str) -> Vec<(usize, &str)> {
+    let mut words = Vec::new();
+    let mut start = None;
+    for (i, c) in text.char_indices() {
+        match (c.is_whitespace(), start) {
+            (true, Some(s)) => {
+                words.push((s, &text[s..i]));
+                start = None;
+            }
+            (false, None) => start = Some(i),
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        words.push((s, &text[s..]));
+    }
+
+    words
+        .into_iter()
+        .filter_map(|(offset, word)| {
+            let leading = word.len() - word.trim_start_matches(LEADING_PUNCTUATION).len();
+            let trimmed = word[leading..].trim_end_matches(TRAILING_PUNCTUATION);
+            (!trimmed.is_empty()).then_some((offset + leading, trimmed))
+        })
+        .collect()
+}
+
+fn is_domain(host: &str) -> bool {
+    let labels: Vec<&str> = host.split('.').collect();
+    labels.len() >= 2
+        && labels
+            .iter()
+            .all(|l| !l.is_empty() && l.chars().all(|c| c.is_alphanumeric() || c == '-'))
+        && labels.last().is_some_and(|tld| tld.chars().count() >= 2 && tld.chars().all(char::is_alphabetic))
+}
+
+fn is_email(word: &str) -> bool {
+    let Some((local, domain)) = word.split_once('@') else { return false };
+    !local.is_empty()
+        && local.chars().all(|c| c.is_alphanumeric() || "._%+-".contains(c))
+        && is_domain(domain)
+}
+
+fn is_url(word: &str) -> bool {
+    let lower = word.to_lowercase();
+    for prefix in ["https://", "http://", "www."] {
+        if let Some(rest) = lower.strip_prefix(prefix) {
+            return rest.len() > 2 && rest.contains('.');
+        }
+    }
+    // Bare "example.com/page", only for well-known endings
+    let host = lower.split(['/', '?', '#']).next().unwrap_or("");
+    is_domain(host) && host.rsplit('.').next().is_some_and(|tld| COMMON_TLDS.contains(&tld))
+}
+
+fn luhn_valid(digits: &[u32]) -> bool {
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &d)| if i % 2 == 1 { if d * 2 > 9 { d * 2 - 9 } else { d * 2 } } else { d })
+        .sum();
+    sum.is_multiple_of(10)
+}
+
+// Runs of digits broken only by single spaces, dashes, dots or parentheses ("+1 (555) 010-9999")
+fn digit_runs(text: &str) -> Vec<(usize, usize)> {
+    let chars: Vec<(usize, char)> = text.char_indices().collect();
+    let mut runs = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let (offset, c) = chars[i];
+        let starts_run = c.is_ascii_digit() || ((c == '+' || c == '(') && chars.get(i + 1).is_some_and(|(_, n)| n.is_ascii_digit()));
+        let after_word = i > 0 && chars[i - 1].1.is_alphanumeric();
+        if !starts_run || after_word {
+            i += 1;
+            continue;
+        }
+
+        let mut j = i + 1;
+        let mut end = if c.is_ascii_digit() { offset + 1 } else { offset };
+        while j < chars.len() {
+            let (o, ch) = chars[j];
+            if ch.is_ascii_digit() {
+                end = o + 1;
+                j += 1;
+                continue;
+            }
+            // A separator only continues the run if a digit follows within two characters
+            let gap = chars[j..].iter().take_while(|(_, s)| matches!(s, ' ' | '-' | '.' | '(' | ')')).count();
+            let continues = gap <= 2 && chars.get(j + gap).is_some_and(|(_, n)| n.is_ascii_digit());
+            let single_space = chars[j..j + gap].iter().filter(|(_, s)| *s == ' ').count() <= 1;
+            if gap == 0 || !continues || !single_space {
+                break;
+            }
+            j += gap;
+        }
+
+        // "(555) 010-9999" keeps its opening parenthesis; a run followed by letters is part of a word
+        if !chars.get(j).is_some_and(|(_, n)| n.is_alphanumeric()) {
+            runs.push((offset, end));
+        }
+        i = j.max(i + 1);
+    }
+
+    runs
+}
+
+fn classify_number(span: &str) -> Option<RedactionCategory> {
+    let digits: Vec<u32> = span.chars().filter_map(|c| c.to_digit(10)).collect();
+    let groups: Vec<usize> = span
+        .split(|c: char| !c.is_ascii_digit())
+        .filter(|g| !g.is_empty())
+        .map(str::len)
+        .collect();
+
+    // Card numbers: 13 to 19 digits, ungrouped or in blocks of four (Amex 4-6-5), passing the Luhn check
+    let card_grouping = groups.len() == 1
+        || groups == [4, 6, 5]
+        || groups.iter().rev().skip(1).all(|&g| g == 4);
+    if (13..=19).contains(&digits.len()) && card_grouping && luhn_valid(&digits) {
+        return Some(RedactionCategory::CardNumber);
+    }
+
+    // IPv4 addresses look like dotted numbers but aren't phone numbers
+    if groups.len() == 4 && groups.iter().all(|&g| g <= 3) && !span.contains([' ', '-', '(']) {
+        return None;
+    }
+
+    // Phone numbers: 10 to 15 digits written together or in at most five short groups, the last
+    // of three or more, or a local "555-0199"; a leading + allows shorter international numbers.
+    // Grouped digits also need a phone-like shape, so years or round numbers read out in a row
+    // ("2019 2020 2021") aren't taken for one: a leading + or "(", dashes or dots between the
+    // groups, or a 3-3-4 grouping after an optional country code.
+    let international = span.starts_with('+');
+    let last_group = groups.last().copied().unwrap_or(0);
+    let phone_length = (10..=15).contains(&digits.len()) || (international && digits.len() >= 8);
+    let local = groups == [3, 4] && !span.contains(' ');
+    let phone_shape = international
+        || span.starts_with('(')
+        || span.contains(['-', '.'])
+        || (groups.ends_with(&[3, 3, 4]) && groups.len() <= 4);
+    let phone_grouping = match groups.len() {
+        1 => (10..=11).contains(&digits.len()),
+        2..=5 => phone_shape && (3..=8).contains(&last_group) && groups[..groups.len() - 1].iter().all(|&g| g <= 5),
+        _ => false,
+    };
+    if (phone_length && phone_grouping) || local {
+        return Some(RedactionCategory::Phone);
+    }
+
+    None
+}
+
+fn normalize(word: &str) -> String {
+    word.trim_matches(|c: char| !c.is_alphanumeric()).to_lowercase()
+}
+
+fn mask(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => std::iter::once(first).chain(chars.map(|_| '*')).collect(),
+        None => String::new(),
+    }
+}
+
+/// Find personal data, profanity and blocklisted terms in `text`. Hits are in text order and
+/// never overlap; where two would, the one starting first (then the longer) wins.
+pub fn detect(text: &str, blocklist: &[BlocklistTerm]) -> Vec<RedactionHit> {
+    let mut hits: Vec<RedactionHit> = Vec::new();
+    let hit = |category, start: usize, end: usize, replacement: String| RedactionHit {
+        category,
+        start,
+        end,
+        text: text[start..end].to_string(),
+        replacement,
+    };
+
+    let words = words(text);
+    for &(offset, word) in &words {
+        if is_email(word) {
+            hits.push(hit(RedactionCategory::Email, offset, offset + word.len(), "[EMAIL]".to_string()));
+        } else if is_url(word) {
+            hits.push(hit(RedactionCategory::Url, offset, offset + word.len(), "[LINK]".to_string()));
+        } else if PROFANITY.contains(&normalize(word).as_str()) {
+            let core_start = offset + word.len() - word.trim_start_matches(|c: char| !c.is_alphanumeric()).len();
+            let core = word.trim_matches(|c: char| !c.is_alphanumeric());
+            hits.push(hit(RedactionCategory::Profanity, core_start, core_start + core.len(), mask(core)));
+        }
+    }
+
+    for (start, end) in digit_runs(text) {
+        if let Some(category) = classify_number(&text[start..end]) {
+            let replacement = if category == RedactionCategory::CardNumber { "[CARD]" } else { "[PHONE]" };
+            hits.push(hit(category, start, end, replacement.to_string()));
+        }
+    }
+
+    // Blocklist terms match whole words, any case, possessives included ("Acme's")
+    let normalized: Vec<String> = words.iter().map(|(_, w)| normalize(w)).collect();
+    for term in blocklist {
+        let term_words: Vec<String> = term.term.split_whitespace().map(normalize).collect();
+        if term_words.is_empty() || term_words.iter().any(|w| w.is_empty()) {
+            continue;
+        }
+        for i in 0..normalized.len().saturating_sub(term_words.len() - 1) {
+            let matches = term_words.iter().enumerate().all(|(k, term_word)| {
+                let word = &normalized[i + k];
+                word == term_word
+                    || (k == term_words.len() - 1
+                        && (word.strip_suffix("'s").or_else(|| word.strip_suffix("’s")) == Some(term_word.as_str())))
+            });
+            if !matches {
+                continue;
+            }
+            let last_index = i + term_words.len() - 1;
+            let (first_offset, first) = words[i];
+            let (last_offset, last) = words[last_index];
+            let start = first_offset + first.len() - first.trim_start_matches(|c: char| !c.is_alphanumeric()).len();
+            // The possessive ending stays visible: "[REDACTED]'s"
+            let mut last_core = last.trim_end_matches(|c: char| !c.is_alphanumeric());
+            if normalized[last_index] != term_words[term_words.len() - 1] {
+                last_core = last_core.strip_suffix("'s").or_else(|| last_core.strip_suffix("’s")).unwrap_or(last_core);
+            }
+            let end = last_offset + last_core.len();
+            let replacement = term.replacement.clone().unwrap_or_else(|| "[REDACTED]".to_string());
+            hits.push(hit(RedactionCategory::Blocklist, start, end, replacement));
+        }
+    }
+
+    hits.sort_by(|a, b| a.start.cmp(&b.start).then(b.end.cmp(&a.end)));
+    let mut kept: Vec<RedactionHit> = Vec::new();
+    for hit in hits {
+        if kept.last().is_none_or(|last| hit.start >= last.end) {
+            kept.push(hit);
+        }
+    }
+    kept
+}
+
+/// `text` with each hit replaced. Hits must come from `detect` on the same text.
+pub fn apply_redactions<'a>(text: &str, hits: impl IntoIterator<Item = &'a RedactionHit>) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut position = 0;
+    for hit in hits {
+        output.push_str(&text[position..hit.start]);
+        output.push_str(&hit.replacement);
+        position = hit.end;
+    }
+    output.push_str(&text[position..]);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn found(text: &str) -> Vec<(&'static str, String)> {
+        detect(text, &[]).into_iter().map(|h| (h.category.as_str(), h.text)).collect()
+    }
+
+    fn categories(text: &str) -> Vec<&'static str> {
+        detect(text, &[]).iter().map(|h| h.category.as_str()).collect()
+    }
+
+    #[test]
+    fn finds_emails_and_links() {
+        assert_eq!(found("Write to jane.doe+news@example.co.uk."), vec![("email", "jane.doe+news@example.co.uk".to_string())]);
+        assert_eq!(found("See https://example.org/a?b=1, or www.example.com"), vec![
+            ("url", "https://example.org/a?b=1".to_string()),
+            ("url", "www.example.com".to_string()),
+        ]);
+        assert_eq!(found("Visit acme.io/pricing today"), vec![("url", "acme.io/pricing".to_string())]);
+        assert!(found("The end.Next sentence, user@ or @handle, and e.g. this").is_empty());
+        assert!(found("Version 2.0 of node.js").is_empty());
+    }
+
+    #[test]
+    fn finds_phone_numbers_with_a_phone_shape() {
+        for phone in ["+1 (555) 010-9999", "(212) 555 0188", "212-555-0188", "212.555.0188", "555 010 9999", "1 555 010 9999", "+44 20 7946 0958", "5550109999", "555-0199"] {
+            assert_eq!(found(&format!("Call {} now", phone)), vec![("phone", phone.to_string())], "{}", phone);
+        }
+    }
+
+    #[test]
+    fn leaves_numbers_in_speech_alone() {
+        for text in [
+            "We grew in 2019 2020 2021 and 2022",
+            "Count 100 200 300 400 500",
+            "Scores of 1000 2000 3000",
+            "Server 192.168.1.10 went down",
+            "On 2024-05-12 at 10:30",
+            "It had 1,234,567 views",
+            "Room 555 0199",
+        ] {
+            assert!(categories(text).is_empty(), "{}: {:?}", text, found(text));
+        }
+    }
+
+    #[test]
+    fn finds_card_numbers_that_pass_the_luhn_check() {
+        assert_eq!(found("card 4111 1111 1111 1111 ok"), vec![("card_number", "4111 1111 1111 1111".to_string())]);
+        assert_eq!(found("amex 3782 822463 10005"), vec![("card_number", "3782 822463 10005".to_string())]);
+        assert_eq!(categories("card 4111 1111 1111 1112 ok"), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn checks_luhn_digits() {
+        let digits = |s: &str| s.chars().filter_map(|c| c.to_digit(10)).collect::<Vec<_>>();
+        assert!(luhn_valid(&digits("4111111111111111")));
+        assert!(luhn_valid(&digits("79927398713")));
+        assert!(!luhn_valid(&digits("79927398710")));
+    }
+
+    #[test]
+    fn splits_digit_runs_on_wide_gaps_and_words() {
+        let runs = |text: &str| digit_runs(text).into_iter().map(|(s, e)| text[s..e].to_string()).collect::<Vec<_>>();
+        assert_eq!(runs("+1 (555) 010-9999"), vec!["+1 (555) 010-9999"]);
+        assert_eq!(runs("12  34 then 56-78"), vec!["12", "34", "56-78"]);
+        assert_eq!(runs("abc123 456def 789"), vec!["789"]);
+    }
+
+    #[test]
+    fn masks_profanity_as_whole_words() {
+        let hits = detect("This is shit, \"Fucking\" great", &[]);
+        let masked: Vec<(&str, &str)> = hits.iter().map(|h| (h.text.as_str(), h.replacement.as_str())).collect();
+        assert_eq!(masked, vec![("shit", "s***"), ("Fucking", "F******")]);
+        assert!(categories("Scunthorpe and shitake and assessment").is_empty());
+    }
+
+    #[test]
+    fn matches_blocklist_terms_and_possessives() {
+        let blocklist = vec![BlocklistTerm { term: "Acme Corp".to_string(), replacement: Some("[CLIENT]".to_string()) }];
+        let text = "Thanks to ACME corp and Acme Corp's team, not Acme alone";
+        let hits = detect(text, &blocklist);
+        assert_eq!(hits.iter().map(|h| h.text.as_str()).collect::<Vec<_>>(), vec!["ACME corp", "Acme Corp"]);
+        assert_eq!(apply_redactions(text, &hits), "Thanks to [CLIENT] and [CLIENT]'s team, not Acme alone");
+    }
+
+    #[test]
+    fn applies_redactions_in_order_without_overlaps() {
+        let text = "Mail me@example.com or call 212-555-0188, ☕ shit";
+        let hits = detect(text, &[]);
+        assert_eq!(hits.iter().map(|h| h.category.as_str()).collect::<Vec<_>>(), vec!["email", "phone", "profanity"]);
+        assert_eq!(apply_redactions(text, &hits), "Mail [EMAIL] or call [PHONE], ☕ s***");
+        assert_eq!(apply_redactions(text, &[]), text);
+        // The link inside an email isn't found twice
+        assert_eq!(categories("contact me@acme.io"), vec!["email"]);
+    }
+}
@@ -0,0 +1,94 @@
+// Related transcripts: shared weighted tags combined with TF-IDF cosine similarity of the text
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+// Shares of the combined score; transcripts without tags are compared on text alone
+const TAG_WEIGHT: f64 = 0.4;
+const TEXT_WEIGHT: f64 = 0.6;
+// Matches below this are coincidental overlap rather than a related recording
+const MIN_SCORE: f64 = 0.05;
+
+#[derive(Debug, Clone)]
+pub struct RelatedDocument {
+    pub transcript_id: String,
+    pub terms: HashMap<String, f64>, // TF-IDF weights from CorpusStats::tfidf_vector
+    pub tags: Vec<(String, f64)>, // Tag name and weight (relevance)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RelatedMatch {
+    pub transcript_id: String,
+    pub score: f64, // 0.0 to 1.0
+    pub tag_score: f64,
+    pub text_score: f64,
+    pub shared_tags: Vec<String>, // Strongest shared tag first
+}
+
+fn cosine(a: &HashMap<String, f64>, b: &HashMap<String, f64>) -> f64 {
+    let (small, large) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    let dot: f64 = small.iter().filter_map(|(w, x)| large.get(w).map(|y| x * y)).sum();
+    let norm_a = a.values().map(|x| x * x).sum::<f64>().sqrt();
+    let norm_b = b.values().map(|x| x * x).sum::<f64>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a * norm_b)
+    }
+}
+
+// Weighted Jaccard similarity: shared weight (the lower of the two) over combined weight (the higher)
+fn tag_similarity(a: &[(String, f64)], b: &[(String, f64)]) -> (f64, Vec<String>) {
+    let b_weights: HashMap<&str, f64> = b.iter().map(|(name, weight)| (name.as_str(), *weight)).collect();
+
+    let mut shared: Vec<(&str, f64)> = Vec::new();
+    let mut union = b.iter().map(|(_, weight)| weight).sum::<f64>();
+    for (name, weight) in a {
+        match b_weights.get(name.as_str()) {
+            Some(&other) => {
+                shared.push((name, weight.min(other)));
+                union += weight.max(other) - other;
+            }
+            None => union += weight,
+        }
+    }
+
+    if union <= 0.0 {
+        return (0.0, Vec::new());
+    }
+    let intersection: f64 = shared.iter().map(|(_, weight)| weight).sum();
+    shared.sort_by(|x, y| y.1.total_cmp(&x.1));
+
+    (intersection / union, shared.into_iter().map(|(name, _)| name.to_string()).collect())
+}
+
+/// Rank `candidates` by how related they are to `target`: a weighted mix of tag similarity
+/// (weighted Jaccard over tag relevance) and TF-IDF cosine similarity of the text. When either
+/// side has no tags the text similarity is the whole score. Weak matches are dropped.
+pub fn rank_related(target: &RelatedDocument, candidates: &[RelatedDocument], limit: usize) -> Vec<RelatedMatch> {
+    let mut matches: Vec<RelatedMatch> = candidates
+        .iter()
+        .filter(|c| c.transcript_id != target.transcript_id)
+        .map(|candidate| {
+            let text_score = cosine(&target.terms, &candidate.terms);
+            let (tag_score, shared_tags) = tag_similarity(&target.tags, &candidate.tags);
+            let score = if target.tags.is_empty() || candidate.tags.is_empty() {
+                text_score
+            } else {
+                TAG_WEIGHT * tag_score + TEXT_WEIGHT * text_score
+            };
+
+            RelatedMatch {
+                transcript_id: candidate.transcript_id.clone(),
+                score,
+                tag_score,
+                text_score,
+                shared_tags,
+            }
+        })
+        .filter(|m| m.score >= MIN_SCORE)
+        .collect();
+
+    matches.sort_by(|a, b| b.score.total_cmp(&a.score));
+    matches.truncate(limit);
+    matches
+}
@@ -0,0 +1,477 @@
+// Search query parsing: turns user search syntax into a safe FTS5 MATCH expression
+//
+// Supported syntax:
+//   word            matches the word
+//   "exact phrase"  matches the words in order
+//   word*           prefix match
+//   a AND b, a b    both terms (AND is implied between terms)
+//   a OR b          either term
+//   NOT a, -a       excludes a term; needs at least one positive term alongside it
+//   ( ... )         grouping
+//   field:term      restricts a term or phrase to one column (content:, summary:)
+//
+// Precedence follows FTS5: NOT binds tightest, then AND, then OR.
+
+// Markers wrapped around matches by snippet()/highlight(); control characters are stripped from
+// transcript text on import, so they never collide with real content
+pub const MATCH_START: &str = "\u{2}";
+pub const MATCH_END: &str = "\u{3}";
+
+// Deepest grouping or NOT chain accepted; keeps pasted junk from exhausting the stack
+const MAX_NESTING: usize = 32;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    Phrase(String),
+    Field(String),
+    And,
+    Or,
+    Not,
+    LParen,
+    RParen,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum QueryNode {
+    Term { text: String, prefix: bool, field: Option<&'static str> },
+    Phrase { text: String, field: Option<&'static str> },
+    And(Vec<QueryNode>),
+    Or(Vec<QueryNode>),
+    Not(Box<QueryNode>),
+}
+
+// Map a user-facing field name to its TranscriptFTS column
+fn fts_column(field: &str) -> Option<&'static str> {
+    match field.to_lowercase().as_str() {
+        "content" | "text" | "transcript" => Some("Content"),
+        "summary" => Some("Summary"),
+        _ => None,
+    }
+}
+
+fn tokenize(query: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut chars = query.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' => {
+                chars.next();
+                tokens.push(Token::LParen);
+            }
+            ')' => {
+                chars.next();
+                tokens.push(Token::RParen);
+            }
+            '"' => {
+                chars.next();
+                // An unterminated quote runs to the end of the query
+                let phrase: String = chars.by_ref().take_while(|c| *c != '"').collect();
+                tokens.push(Token::Phrase(phrase));
+            }
+            // A leading dash excludes the term it is attached to ("-draft"); a lone dash is ignored
+            '-' => {
+                chars.next();
+                if chars.peek().map_or(false, |c| !c.is_whitespace()) {
+                    tokens.push(Token::Not);
+                }
+            }
+            _ => {
+                let mut word = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c.is_whitespace() || c == '(' || c == ')' || c == '"' {
+                        break;
+                    }
+                    word.push(c);
+                    chars.next();
+                }
+
+                match word.as_str() {
+                    "AND" | "&&" => tokens.push(Token::And),
+                    "OR" | "||" => tokens.push(Token::Or),
+                    "NOT" => tokens.push(Token::Not),
+                    _ => match word.split_once(':') {
+                        Some((field, rest)) if fts_column(field).is_some() => {
+                            tokens.push(Token::Field(field.to_string()));
+                            if !rest.is_empty() {
+                                tokens.push(Token::Word(rest.to_string()));
+                            }
+                        }
+                        _ => tokens.push(Token::Word(word)),
+                    },
+                }
+            }
+        }
+    }
+
+    tokens
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+    depth: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn parse_or(&mut self) -> Result<Option<QueryNode>, String> {
+        let mut branches = Vec::new();
+        branches.extend(self.parse_and()?);
+
+        while self.peek() == Some(&Token::Or) {
+            self.next();
+            branches.extend(self.parse_and()?);
+        }
+
+        Ok(match branches.len() {
+            0 => None,
+            1 => branches.pop(),
+            _ => Some(QueryNode::Or(branches)),
+        })
+    }
+
+    fn parse_and(&mut self) -> Result<Option<QueryNode>, String> {
+        let mut terms = Vec::new();
+
+        loop {
+            match self.peek() {
+                None | Some(Token::Or) | Some(Token::RParen) => break,
+                Some(Token::And) => {
+                    self.next();
+                }
+                _ => terms.extend(self.parse_unary()?),
+            }
+        }
+
+        Ok(match terms.len() {
+            0 => None,
+            1 => terms.pop(),
+            _ => Some(QueryNode::And(terms)),
+        })
+    }
+
+    fn parse_unary(&mut self) -> Result<Option<QueryNode>, String> {
+        if self.depth >= MAX_NESTING {
+            return Err("Search query is nested too deeply".to_string());
+        }
+        self.depth += 1;
+        let node = self.parse_unary_inner();
+        self.depth -= 1;
+        node
+    }
+
+    fn parse_unary_inner(&mut self) -> Result<Option<QueryNode>, String> {
+        match self.next() {
+            Some(Token::Not) => Ok(self.parse_unary()?.map(|node| QueryNode::Not(Box::new(node)))),
+            Some(Token::LParen) => {
+                let inner = self.parse_or()?;
+                if self.next() != Some(Token::RParen) {
+                    return Err("Missing closing parenthesis".to_string());
+                }
+                Ok(inner)
+            }
+            Some(Token::Field(field)) => {
+                let column = fts_column(&field);
+                Ok(self.parse_unary()?.map(|node| with_field(node, column)))
+            }
+            Some(Token::Word(word)) => Ok(term(&word, None)),
+            Some(Token::Phrase(phrase)) => Ok(phrase_node(&phrase, None)),
+            Some(Token::RParen) => Err("Unexpected closing parenthesis".to_string()),
+            Some(Token::And) | Some(Token::Or) | None => Ok(None),
+        }
+    }
+}
+
+fn term(word: &str, field: Option<&'static str>) -> Option<QueryNode> {
+    let prefix = word.ends_with('*');
+    let text = word.trim_end_matches('*');
+
+    // Terms made only of punctuation produce no tokens in FTS5
+    if !text.chars().any(|c| c.is_alphanumeric()) {
+        return None;
+    }
+
+    Some(QueryNode::Term { text: text.to_string(), prefix, field })
+}
+
+fn phrase_node(phrase: &str, field: Option<&'static str>) -> Option<QueryNode> {
+    let text = phrase.split_whitespace().collect::<Vec<_>>().join(" ");
+    if !text.chars().any(|c| c.is_alphanumeric()) {
+        return None;
+    }
+
+    Some(QueryNode::Phrase { text, field })
+}
+
+fn with_field(node: QueryNode, column: Option<&'static str>) -> QueryNode {
+    match node {
+        QueryNode::Term { text, prefix, .. } => QueryNode::Term { text, prefix, field: column },
+        QueryNode::Phrase { text, .. } => QueryNode::Phrase { text, field: column },
+        QueryNode::And(nodes) => QueryNode::And(nodes.into_iter().map(|n| with_field(n, column)).collect()),
+        QueryNode::Or(nodes) => QueryNode::Or(nodes.into_iter().map(|n| with_field(n, column)).collect()),
+        QueryNode::Not(node) => QueryNode::Not(Box::new(with_field(*node, column))),
+    }
+}
+
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('"', "\"\""))
+}
+
+fn compile(node: &QueryNode) -> Result<String, String> {
+    match node {
+        QueryNode::Term { text, prefix, field } => {
+            let mut output = quote(text);
+            if *prefix {
+                output.push_str(" *");
+            }
+            Ok(match field {
+                Some(column) => format!("{} : {}", column, output),
+                None => output,
+            })
+        }
+        QueryNode::Phrase { text, field } => Ok(match field {
+            Some(column) => format!("{} : {}", column, quote(text)),
+            None => quote(text),
+        }),
+        // FTS5's NOT is binary, so exclusions are attached to the positive terms of their group
+        QueryNode::And(nodes) => {
+            let (negative, positive): (Vec<&QueryNode>, Vec<&QueryNode>) =
+                nodes.iter().partition(|n| matches!(n, QueryNode::Not(_)));
+
+            if positive.is_empty() {
+                return Err("A search needs at least one term that isn't excluded with NOT".to_string());
+            }
+
+            let positive = positive.into_iter().map(compile).collect::<Result<Vec<_>, _>>()?;
+            let mut output = format!("({})", positive.join(" AND "));
+
+            if !negative.is_empty() {
+                let negative = negative
+                    .into_iter()
+                    .map(|n| match n {
+                        QueryNode::Not(inner) => compile(inner),
+                        other => compile(other),
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                output = format!("{} NOT ({})", output, negative.join(" OR "));
+            }
+
+            Ok(output)
+        }
+        QueryNode::Or(nodes) => {
+            let branches = nodes.iter().map(compile).collect::<Result<Vec<_>, _>>()?;
+            Ok(format!("({})", branches.join(" OR ")))
+        }
+        QueryNode::Not(_) => Err("A search needs at least one term that isn't excluded with NOT".to_string()),
+    }
+}
+
+fn parse_query(query: &str) -> Result<QueryNode, String> {
+    let mut parser = Parser {
+        tokens: tokenize(query),
+        position: 0,
+        depth: 0,
+    };
+
+    let node = parser.parse_or()?;
+    if let Some(token) = parser.peek() {
+        return Err(format!("Unexpected {:?} in search query", token));
+    }
+
+    node.ok_or_else(|| "Search query has no searchable terms".to_string())
+}
+
+/// Parse user search syntax into an FTS5 MATCH expression. Every term is quoted, so punctuation
+/// in the input can never produce an FTS5 syntax error.
+pub fn build_fts_query(query: &str) -> Result<String, String> {
+    compile(&parse_query(query)?)
+}
+
+// Rewrite a query for TranscriptSegmentFTS, which only indexes segment text: content: scoping is
+// dropped and summary: terms are removed. None means nothing is left to constrain segments.
+fn segment_node(node: &QueryNode) -> Option<QueryNode> {
+    match node {
+        QueryNode::Term { field: Some("Summary"), .. } | QueryNode::Phrase { field: Some("Summary"), .. } => None,
+        QueryNode::Term { text, prefix, .. } => Some(QueryNode::Term { text: text.clone(), prefix: *prefix, field: None }),
+        QueryNode::Phrase { text, .. } => Some(QueryNode::Phrase { text: text.clone(), field: None }),
+        QueryNode::And(nodes) => {
+            let mut kept: Vec<QueryNode> = nodes.iter().filter_map(segment_node).collect();
+            match kept.len() {
+                0 => None,
+                1 => kept.pop(),
+                _ => Some(QueryNode::And(kept)),
+            }
+        }
+        // A branch that can't be checked per segment could match anything
+        QueryNode::Or(nodes) => nodes
+            .iter()
+            .map(segment_node)
+            .collect::<Option<Vec<_>>>()
+            .map(QueryNode::Or),
+        QueryNode::Not(inner) => segment_node(inner).map(|n| QueryNode::Not(Box::new(n))),
+    }
+}
+
+// Every positive term and phrase in the query
+fn positive_leaves(node: &QueryNode, leaves: &mut Vec<QueryNode>) {
+    match node {
+        QueryNode::Term { .. } | QueryNode::Phrase { .. } => leaves.push(node.clone()),
+        QueryNode::And(nodes) | QueryNode::Or(nodes) => nodes.iter().for_each(|n| positive_leaves(n, leaves)),
+        QueryNode::Not(_) => {}
+    }
+}
+
+/// MATCH expressions for finding the segments that satisfy a query: an exact one requiring the
+/// whole query within a single segment, and a looser one matching any of its terms, for hits
+/// whose words are spread over neighbouring cues. None when the query only scopes the summary.
+pub fn build_segment_fts_queries(query: &str) -> Result<Option<(String, String)>, String> {
+    let Some(node) = segment_node(&parse_query(query)?) else {
+        return Ok(None);
+    };
+
+    let mut leaves = Vec::new();
+    positive_leaves(&node, &mut leaves);
+    if leaves.is_empty() {
+        return Ok(None);
+    }
+
+    let exact = compile(&node)?;
+    let any = compile(&QueryNode::Or(leaves))?;
+    Ok(Some((exact, any)))
+}
+
+/// Strip match markers from snippet()/highlight() output and return the match ranges.
+/// Offsets are UTF-16 code unit indices into the cleaned text, matching JavaScript string indexing.
+pub fn extract_highlights(marked: &str) -> (String, Vec<(usize, usize)>) {
+    let mut text = String::with_capacity(marked.len());
+    let mut highlights = Vec::new();
+    let mut position = 0;
+    let mut start = None;
+
+    for c in marked.chars() {
+        match c {
+            '\u{2}' => start = Some(position),
+            '\u{3}' => {
+                if let Some(start) = start.take() {
+                    if position > start {
+                        highlights.push((start, position));
+                    }
+                }
+            }
+            c => {
+                text.push(c);
+                position += c.len_utf16();
+            }
+        }
+    }
+
+    (text, highlights)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quotes_every_term() {
+        assert_eq!(build_fts_query("coffee").unwrap(), "\"coffee\"");
+        assert_eq!(build_fts_query("brew*").unwrap(), "\"brew\" *");
+        assert_eq!(build_fts_query("\"cold  brew\"").unwrap(), "\"cold brew\"");
+        assert_eq!(build_fts_query("café 東京").unwrap(), "(\"café\" AND \"東京\")");
+    }
+
+    #[test]
+    fn escapes_fts_operators_and_punctuation() {
+        // FTS5 keywords and syntax characters in the input are only ever searched for as text
+        assert_eq!(build_fts_query("near").unwrap(), "\"near\"");
+        assert_eq!(build_fts_query("and or").unwrap(), "(\"and\" AND \"or\")");
+        assert_eq!(build_fts_query("NEAR(a b)").unwrap(), "(\"NEAR\" AND (\"a\" AND \"b\"))");
+        assert_eq!(build_fts_query("a^b c:d {x} +y").unwrap(), "(\"a^b\" AND \"c:d\" AND \"{x}\" AND \"+y\")");
+        assert_eq!(build_fts_query("\"say \"\"hi\"\"\"").unwrap(), "(\"say\" AND \"hi\")");
+        assert_eq!(build_fts_query("it's").unwrap(), "\"it's\"");
+        assert_eq!(build_fts_query("\"unterminated phrase").unwrap(), "\"unterminated phrase\"");
+    }
+
+    #[test]
+    fn follows_fts_precedence() {
+        assert_eq!(build_fts_query("a OR b c").unwrap(), "(\"a\" OR (\"b\" AND \"c\"))");
+        assert_eq!(build_fts_query("(a OR b) c").unwrap(), "((\"a\" OR \"b\") AND \"c\")");
+        assert_eq!(build_fts_query("a AND b || c && d").unwrap(), "((\"a\" AND \"b\") OR (\"c\" AND \"d\"))");
+        assert_eq!(build_fts_query("NOT draft notes").unwrap(), "(\"notes\") NOT (\"draft\")");
+        assert_eq!(build_fts_query("coffee -decaf -\"instant coffee\"").unwrap(), "(\"coffee\") NOT (\"decaf\" OR \"instant coffee\")");
+        assert_eq!(build_fts_query("a - b").unwrap(), "(\"a\" AND \"b\")");
+    }
+
+    #[test]
+    fn scopes_terms_to_fields() {
+        assert_eq!(build_fts_query("summary:launch").unwrap(), "Summary : \"launch\"");
+        assert_eq!(build_fts_query("content:(a OR \"b c\")").unwrap(), "(Content : \"a\" OR Content : \"b c\")");
+        assert_eq!(build_fts_query("unknown:field").unwrap(), "\"unknown:field\"");
+        assert_eq!(
+            build_segment_fts_queries("summary:launch coffee").unwrap(),
+            Some(("\"coffee\"".to_string(), "(\"coffee\")".to_string()))
+        );
+        assert_eq!(build_segment_fts_queries("summary:launch").unwrap(), None);
+    }
+
+    #[test]
+    fn rejects_malformed_queries() {
+        for query in ["", "   ", "-", "()", "AND OR", "!!!", "\"\"", "content:"] {
+            assert!(build_fts_query(query).is_err(), "{:?}", query);
+        }
+        assert!(build_fts_query("NOT draft").is_err());
+        assert!(build_fts_query("a OR -b").is_err());
+        assert_eq!(build_fts_query("(a OR b").unwrap_err(), "Missing closing parenthesis");
+        assert!(build_fts_query("a) OR (b").is_err());
+        assert_eq!(build_fts_query(&"(".repeat(10_000)).unwrap_err(), "Search query is nested too deeply");
+        assert!(build_fts_query(&"NOT ".repeat(10_000)).is_err());
+        assert!(build_fts_query(&format!("{}a{}", "(".repeat(20), ")".repeat(20))).is_ok());
+    }
+
+    #[tokio::test]
+    async fn compiled_queries_are_valid_fts5() {
+        let pool = sqlx::SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::query("CREATE VIRTUAL TABLE Doc USING fts5(Content, Summary)").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO Doc VALUES ('NEAR the café, it''s cold brew', 'launch notes')").execute(&pool).await.unwrap();
+
+        for (query, matches) in [
+            ("cold brew", true),
+            ("NEAR(cold brew)", true),
+            ("caf*", true),
+            ("it's", true),
+            ("a^b OR c:d OR {x} OR +y", false),
+            ("summary:launch -decaf", true),
+            ("content:launch", false),
+            ("brew -\"cold brew\"", false),
+        ] {
+            let fts = build_fts_query(query).unwrap();
+            let (count,) = sqlx::query_as::<_, (i64,)>("SELECT COUNT(*) FROM Doc WHERE Doc MATCH ?1")
+                .bind(&fts)
+                .fetch_one(&pool)
+                .await
+                .unwrap_or_else(|e| panic!("{:?} compiled to invalid FTS5 {:?}: {}", query, fts, e));
+            assert_eq!(count > 0, matches, "{:?} -> {}", query, fts);
+        }
+    }
+
+    #[test]
+    fn extracts_highlights_in_utf16_offsets() {
+        let (text, highlights) = extract_highlights("a \u{2}😀b\u{3} é\u{2}\u{3} \u{2}c");
+        assert_eq!(text, "a 😀b é c");
+        assert_eq!(highlights, vec![(2, 5)]);
+    }
+}
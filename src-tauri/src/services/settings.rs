@@ -51,6 +51,26 @@ pub struct UsageStats {
     pub last_updated: String,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct EmbeddingSettings {
+    pub provider: String, // "hashing" (built in, offline) or "http" (OpenAI-compatible endpoint)
+    pub endpoint: String, // Base URL; POST {endpoint}/embeddings
+    pub model: String,
+    pub api_key_service: String, // Entry in api_keys holding the endpoint's key, if it needs one
+}
+
+impl Default for EmbeddingSettings {
+    fn default() -> Self {
+        EmbeddingSettings {
+            provider: "hashing".to_string(),
+            endpoint: "https://api.openai.com/v1".to_string(),
+            model: "text-embedding-3-small".to_string(),
+            api_key_service: "openai".to_string(),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Settings {
     pub api_keys: HashMap<String, ApiKey>,
@@ -58,6 +78,8 @@ pub struct Settings {
     pub file_organization: FileOrganization,
     pub brand_settings: BrandSettings,
     pub usage_stats: UsageStats,
+    #[serde(default)]
+    pub embeddings: EmbeddingSettings,
 }
 
 impl Default for Settings {
@@ -89,6 +111,7 @@ impl Default for Settings {
                 storage_used_mb: 0.0,
                 last_updated: chrono::Utc::now().to_rfc3339(),
             },
+            embeddings: EmbeddingSettings::default(),
         }
     }
 }
@@ -221,6 +244,13 @@ impl SettingsService {
         Ok(())
     }
     
+    pub fn update_embedding_settings(&self, embeddings: EmbeddingSettings) -> Result<(), Box<dyn std::error::Error>> {
+        let mut settings = self.load_settings()?;
+        settings.embeddings = embeddings;
+        self.save_settings(&settings)?;
+        Ok(())
+    }
+    
     pub fn update_usage_stats(&self, stats: UsageStats) -> Result<(), Box<dyn std::error::Error>> {
         let mut settings = self.load_settings()?;
         settings.usage_stats = stats;
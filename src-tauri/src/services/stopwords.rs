@@ -0,0 +1,79 @@
+// Per-language stopwords for offline text analysis (summaries, keyword extraction)
+use std::collections::{HashMap, HashSet};
+use std::sync::OnceLock;
+
+// Function words plus the fillers and discourse markers common in spoken transcripts.
+// Covers the languages language_detect can identify.
+const STOPWORDS: &[(&str, &str)] = &[
+    ("en", "a about above after again against all also am an and any are aren't as at be because been before \
+        being below between both but by can can't cannot could couldn't did didn't do does doesn't doing don't \
+        down during each either else even ever every few for from further get gets getting go goes going gonna \
+        got gotta had hadn't has hasn't have haven't having he he'd he'll he's her here here's hers herself him \
+        himself his how how's i i'd i'll i'm i've if in into is isn't it it's its itself just kind kinda know \
+        let's like lot lots may maybe me might more most much must mustn't my myself need no nor not now of off \
+        oh okay ok on once one only or other ought our ours ourselves out over own pretty probably quite rather \
+        really right said say says see so some something sort still such than that that's the their theirs them \
+        themselves then there there's these they they'd they'll they're they've thing things think this those \
+        though through to too uh um under until up upon us very wanna want was wasn't way we we'd we'll we're \
+        we've well were weren't what what's when when's where where's whether which while who who's whom why \
+        why's will with won't would wouldn't yeah yes yet you you'd you'll you're you've your yours yourself \
+        yourselves"),
+    ("es", "a al algo algunas algunos ante antes bien bueno como con contra cual cuando de del desde donde dos \
+        durante e el ella ellas ello ellos en entonces entre era eran es esa esas ese eso esos esta estaba \
+        estaban estado estamos estar estas este esto estos estoy fue fueron ha había han hasta hay la las le les \
+        lo los me mi mis mismo mucho muy más nada ni no nos nosotros o os otra otro otros para pero poco por \
+        porque pues que quien qué se sea ser si sido sin sobre somos son su sus sí también tan te tener tengo \
+        tiene tienen todo todos tu tus tú un una unas uno unos vale vamos y ya yo él"),
+    ("pt", "a ai algo ao aos aquela aquele aqui as até bem boa bom com como da das de dela dele depois do dos e \
+        ela elas ele eles em entre então era eram essa esse esta estava este estou está estão eu foi for foram \
+        gente há isso isto já la lhe mais mas me mesmo meu minha muito na nas nem no nos nossa nosso não num \
+        numa né o os ou para pela pelo por porque pra quando que quem se sem ser seu sua são só também te tem \
+        tinha to tudo um uma umas uns vai você vocês à é"),
+    ("fr", "a ai alors au aussi aux avec avait avoir bah ben bien c ce cela ces cet cette comme d dans de des \
+        donc du elle elles en est et eu euh fait faire il ils j je l la le les leur lui m ma mais me même mes \
+        moi mon n ne nos notre nous on ont ou où par pas peu plus pour qu quand que quel qui quoi s sa sans se \
+        ses si son sont sur t ta te tes toi ton tous tout très tu un une vos votre vous y à ça été être"),
+    ("de", "aber alle allem allen aller alles als also am an auch auf aus bei bin bis bist da damit dann das dass \
+        dem den denn der des dich die dir doch dort du durch ein eine einem einen einer eines er es etwas euch \
+        für gibt hab habe haben hat hatte hier ich ihm ihn ihr im in ist ja jetzt kann kein keine man mein \
+        meine mich mir mit muss nach nicht noch nun nur ob oder ohne schon sehr sein seine sich sie sind so \
+        über um und uns unser vom von vor war waren was weil wenn wer wie wir wird wo zu zum zur äh ähm"),
+    ("it", "a ad agli ai al alla alle allora anche avere c che chi ci come con cosa da dal dalla dei del della \
+        delle di dove e ed era essere gli ha hanno ho i il in io la le lei li lo loro lui ma mi mia mio molto \
+        ne nei nel nella no noi non o per perché più poi quando quella quello questa questo qui se si sia sono \
+        su sua suo ti tu tutto un una uno vi voi è"),
+];
+
+fn stopword_sets() -> &'static HashMap<&'static str, HashSet<&'static str>> {
+    static SETS: OnceLock<HashMap<&'static str, HashSet<&'static str>>> = OnceLock::new();
+    SETS.get_or_init(|| {
+        STOPWORDS
+            .iter()
+            .map(|(code, words)| (*code, words.split_whitespace().collect()))
+            .collect()
+    })
+}
+
+/// Whether a lowercased word is a stopword in the given language. Unsupported languages fall
+/// back to English, since English terms are common in transcripts of any language.
+pub fn is_stopword(language: &str, word: &str) -> bool {
+    let sets = stopword_sets();
+    let set = sets.get(language).unwrap_or(&sets["en"]);
+    set.contains(word) || (language != "en" && sets["en"].contains(word))
+}
+
+/// Lowercased word tokens; apostrophes inside words are kept ("don't")
+pub fn tokenize_words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric() && c != '\'' && c != '’')
+        .map(|w| w.trim_matches(|c| c == '\'' || c == '’').replace('’', "'").to_lowercase())
+        .filter(|w| !w.is_empty())
+        .collect()
+}
+
+/// Lowercased tokens that carry meaning: no stopwords, numbers or single letters
+pub fn content_words(language: &str, text: &str) -> Vec<String> {
+    tokenize_words(text)
+        .into_iter()
+        .filter(|w| w.chars().count() > 1 && w.chars().any(|c| c.is_alphabetic()) && !is_stopword(language, w))
+        .collect()
+}
@@ -0,0 +1,240 @@
+// Offline extractive summarization: TextRank over transcript sentences
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+use super::stopwords::content_words;
+use super::transcript_parser::ParsedSegment;
+
+// PageRank damping factor, as in the TextRank paper
+const DAMPING: f64 = 0.85;
+const MAX_ITERATIONS: usize = 100;
+const CONVERGENCE: f64 = 1e-6;
+// Sentences outside this range make poor summary material (fragments, run-on captions)
+const MIN_SENTENCE_WORDS: usize = 6;
+const MAX_SENTENCE_WORDS: usize = 60;
+// Unpunctuated captions are cut into sentences at the first segment boundary past this length
+const UNPUNCTUATED_SENTENCE_WORDS: usize = 30;
+// Key points sharing more than this fraction of their content words with an earlier one are skipped
+const MAX_KEY_POINT_OVERLAP: f64 = 0.5;
+
+// Abbreviations whose trailing period doesn't end a sentence
+const ABBREVIATIONS: &[&str] = &[
+    "mr.", "mrs.", "ms.", "dr.", "prof.", "sr.", "jr.", "st.", "vs.", "etc.", "e.g.", "i.e.", "approx.", "no.",
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeyPoint {
+    pub text: String,
+    pub start_time: Option<f64>,
+    pub end_time: Option<f64>,
+    pub speaker: Option<String>,
+    pub score: f64, // Relative to the top-ranked sentence (1.0)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExtractiveSummary {
+    pub summary: String,
+    pub key_points: Vec<KeyPoint>,
+    pub sentence_count: usize,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Sentence {
+    pub(crate) text: String,
+    pub(crate) start_time: Option<f64>,
+    pub(crate) end_time: Option<f64>,
+    pub(crate) speaker: Option<String>,
+    pub(crate) word_count: usize,
+}
+
+fn ends_sentence(word: &str) -> bool {
+    let trimmed = word.trim_end_matches(['"', '\'', '”', '’', ')', ']']);
+    if !trimmed.ends_with(['.', '!', '?', '…', '。', '！', '？']) {
+        return false;
+    }
+
+    let lower = trimmed.to_lowercase();
+    // Initials like "J." are not sentence ends either
+    let initial = trimmed.chars().count() == 2 && trimmed.starts_with(|c: char| c.is_uppercase());
+    !ABBREVIATIONS.contains(&lower.as_str()) && !initial
+}
+
+// Split segments into sentences. Sentences can span cues; a speaker change always ends one.
+pub(crate) fn split_sentences(segments: &[ParsedSegment]) -> Vec<Sentence> {
+    let mut sentences = Vec::new();
+    let mut words: Vec<&str> = Vec::new();
+    let mut start_time = None;
+    let mut end_time = None;
+    let mut speaker: Option<String> = None;
+
+    let mut close = |words: &mut Vec<&str>, start_time: Option<f64>, end_time: Option<f64>, speaker: &Option<String>| {
+        if !words.is_empty() {
+            sentences.push(Sentence {
+                text: words.join(" "),
+                start_time,
+                end_time,
+                speaker: speaker.clone(),
+                word_count: words.len(),
+            });
+            words.clear();
+        }
+    };
+
+    for segment in segments {
+        if segment.speaker != speaker {
+            close(&mut words, start_time, end_time, &speaker);
+            speaker = segment.speaker.clone();
+        }
+
+        for word in segment.text.split_whitespace() {
+            if words.is_empty() {
+                start_time = segment.start_time;
+            }
+            words.push(word);
+            end_time = segment.end_time;
+
+            if ends_sentence(word) {
+                close(&mut words, start_time, end_time, &speaker);
+            }
+        }
+
+        if words.len() >= UNPUNCTUATED_SENTENCE_WORDS {
+            close(&mut words, start_time, end_time, &speaker);
+        }
+    }
+
+    close(&mut words, start_time, end_time, &speaker);
+    sentences
+}
+
+// TextRank edge weight: shared content words, normalized by sentence lengths
+fn similarity(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+    let shared = a.intersection(b).count();
+    if shared == 0 {
+        return 0.0;
+    }
+
+    shared as f64 / ((a.len() as f64 + 1.0).ln() + (b.len() as f64 + 1.0).ln())
+}
+
+fn overlap(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+    let smaller = a.len().min(b.len());
+    if smaller == 0 {
+        return 0.0;
+    }
+
+    a.intersection(b).count() as f64 / smaller as f64
+}
+
+// Weighted PageRank over the sentence similarity graph. The graph is kept as adjacency lists
+// since most sentence pairs in a long transcript share no content words.
+fn rank(words: &[HashSet<String>]) -> Vec<f64> {
+    let n = words.len();
+    let mut edges: Vec<Vec<(usize, f64)>> = vec![Vec::new(); n];
+    for i in 0..n {
+        for j in (i + 1)..n {
+            let weight = similarity(&words[i], &words[j]);
+            if weight > 0.0 {
+                edges[i].push((j, weight));
+                edges[j].push((i, weight));
+            }
+        }
+    }
+
+    let out_weight: Vec<f64> = edges.iter().map(|e| e.iter().map(|(_, w)| w).sum()).collect();
+    let mut scores = vec![1.0 / n as f64; n];
+
+    for _ in 0..MAX_ITERATIONS {
+        let next: Vec<f64> = (0..n)
+            .map(|i| {
+                let incoming: f64 = edges[i]
+                    .iter()
+                    .map(|&(j, weight)| weight / out_weight[j] * scores[j])
+                    .sum();
+                (1.0 - DAMPING) / n as f64 + DAMPING * incoming
+            })
+            .collect();
+
+        let change: f64 = next.iter().zip(&scores).map(|(a, b)| (a - b).abs()).sum();
+        scores = next;
+        if change < CONVERGENCE {
+            break;
+        }
+    }
+
+    scores
+}
+
+/// Pick the most central sentences of a transcript. The summary is the top `summary_sentences`
+/// sentences in their original order; key points are the top `key_point_count` sentences with
+/// near-duplicates removed, also in transcript order and with their timestamps.
+/// Returns None when the transcript has no sentences long enough to rank.
+pub fn summarize(
+    segments: &[ParsedSegment],
+    language: &str,
+    summary_sentences: usize,
+    key_point_count: usize,
+) -> Option<ExtractiveSummary> {
+    let sentences = split_sentences(segments);
+    let sentence_count = sentences.len();
+
+    let candidates: Vec<(Sentence, HashSet<String>)> = sentences
+        .into_iter()
+        .filter(|s| (MIN_SENTENCE_WORDS..=MAX_SENTENCE_WORDS).contains(&s.word_count))
+        .map(|s| {
+            let words = content_words(language, &s.text).into_iter().collect();
+            (s, words)
+        })
+        .filter(|(_, words): &(Sentence, HashSet<String>)| words.len() >= 2)
+        .collect();
+
+    if candidates.is_empty() {
+        return None;
+    }
+
+    let words: Vec<HashSet<String>> = candidates.iter().map(|(_, w)| w.clone()).collect();
+    let scores = rank(&words);
+    let top_score = scores.iter().cloned().fold(f64::MIN, f64::max);
+
+    let mut by_score: Vec<usize> = (0..candidates.len()).collect();
+    by_score.sort_by(|&a, &b| scores[b].total_cmp(&scores[a]).then(a.cmp(&b)));
+
+    let mut summary_indices: Vec<usize> = by_score.iter().take(summary_sentences.max(1)).cloned().collect();
+    summary_indices.sort_unstable();
+    let summary = summary_indices
+        .iter()
+        .map(|&i| candidates[i].0.text.as_str())
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let mut key_indices: Vec<usize> = Vec::new();
+    for &i in &by_score {
+        if key_indices.len() >= key_point_count {
+            break;
+        }
+        if key_indices.iter().all(|&k| overlap(&words[i], &words[k]) <= MAX_KEY_POINT_OVERLAP) {
+            key_indices.push(i);
+        }
+    }
+    key_indices.sort_unstable();
+
+    let key_points = key_indices
+        .into_iter()
+        .map(|i| {
+            let sentence = &candidates[i].0;
+            KeyPoint {
+                text: sentence.text.clone(),
+                start_time: sentence.start_time,
+                end_time: sentence.end_time,
+                speaker: sentence.speaker.clone(),
+                score: if top_score > 0.0 { scores[i] / top_score } else { 0.0 },
+            }
+        })
+        .collect();
+
+    Some(ExtractiveSummary {
+        summary,
+        key_points,
+        sentence_count,
+    })
+}
@@ -0,0 +1,117 @@
+// Text encoding detection and cleanup for imported transcript files
+use encoding_rs::{UTF_16BE, UTF_16LE, WINDOWS_1252};
+
+#[derive(Debug, Clone)]
+pub struct DecodedText {
+    pub text: String,
+    pub encoding: &'static str,
+}
+
+/// Decode raw file bytes to UTF-8.
+/// BOMs decide UTF-8/16/32 outright; otherwise valid UTF-8 wins, then BOM-less UTF-16
+/// (spotted by its zero bytes), then a legacy single-byte encoding.
+pub fn decode_text(bytes: &[u8]) -> Result<DecodedText, String> {
+    if let Some(rest) = bytes.strip_prefix(&[0xEF, 0xBB, 0xBF]) {
+        return utf8(rest, "UTF-8 (BOM)");
+    }
+    // UTF-32 BOMs must be checked before UTF-16, since FF FE also starts the UTF-32LE BOM
+    if let Some(rest) = bytes.strip_prefix(&[0xFF, 0xFE, 0x00, 0x00]) {
+        return utf32(rest, u32::from_le_bytes, "UTF-32LE");
+    }
+    if let Some(rest) = bytes.strip_prefix(&[0x00, 0x00, 0xFE, 0xFF]) {
+        return utf32(rest, u32::from_be_bytes, "UTF-32BE");
+    }
+    if let Some(rest) = bytes.strip_prefix(&[0xFF, 0xFE]) {
+        return Ok(DecodedText { text: UTF_16LE.decode_without_bom_handling(rest).0.into_owned(), encoding: "UTF-16LE" });
+    }
+    if let Some(rest) = bytes.strip_prefix(&[0xFE, 0xFF]) {
+        return Ok(DecodedText { text: UTF_16BE.decode_without_bom_handling(rest).0.into_owned(), encoding: "UTF-16BE" });
+    }
+
+    if let Ok(text) = std::str::from_utf8(bytes) {
+        return Ok(DecodedText { text: text.to_string(), encoding: "UTF-8" });
+    }
+
+    if let Some(encoding) = sniff_utf16(bytes) {
+        return Ok(DecodedText { text: encoding.decode_without_bom_handling(bytes).0.into_owned(), encoding: encoding.name() });
+    }
+
+    Ok(decode_single_byte(bytes))
+}
+
+fn utf8(bytes: &[u8], label: &'static str) -> Result<DecodedText, String> {
+    let text = std::str::from_utf8(bytes).map_err(|e| format!("Invalid UTF-8 after BOM: {}", e))?;
+    Ok(DecodedText { text: text.to_string(), encoding: label })
+}
+
+fn utf32(bytes: &[u8], to_u32: fn([u8; 4]) -> u32, label: &'static str) -> Result<DecodedText, String> {
+    if bytes.len() % 4 != 0 {
+        return Err(format!("Truncated {} file", label));
+    }
+
+    let text = bytes
+        .chunks_exact(4)
+        .map(|chunk| {
+            let code = to_u32([chunk[0], chunk[1], chunk[2], chunk[3]]);
+            char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER)
+        })
+        .collect();
+
+    Ok(DecodedText { text, encoding: label })
+}
+
+// Mostly-ASCII UTF-16 text has a zero in every other byte
+fn sniff_utf16(bytes: &[u8]) -> Option<&'static encoding_rs::Encoding> {
+    let sample = &bytes[..bytes.len().min(4096) & !1];
+    if sample.len() < 4 {
+        return None;
+    }
+
+    let pairs = sample.len() / 2;
+    let zero_even = sample.iter().step_by(2).filter(|b| **b == 0).count();
+    let zero_odd = sample.iter().skip(1).step_by(2).filter(|b| **b == 0).count();
+
+    if zero_odd * 10 > pairs * 3 && zero_even * 10 < pairs {
+        Some(UTF_16LE)
+    } else if zero_even * 10 > pairs * 3 && zero_odd * 10 < pairs {
+        Some(UTF_16BE)
+    } else {
+        None
+    }
+}
+
+// Windows-1252 covers Latin-1's printable range plus curly quotes and dashes that
+// caption tools emit. Bytes Windows-1252 leaves undefined mean the file is really ISO-8859-1.
+fn decode_single_byte(bytes: &[u8]) -> DecodedText {
+    let undefined_in_1252 = bytes.iter().any(|b| matches!(b, 0x81 | 0x8D | 0x8F | 0x90 | 0x9D));
+
+    if undefined_in_1252 {
+        DecodedText {
+            text: bytes.iter().map(|&b| b as char).collect(),
+            encoding: "ISO-8859-1",
+        }
+    } else {
+        DecodedText {
+            text: WINDOWS_1252.decode_without_bom_handling(bytes).0.into_owned(),
+            encoding: WINDOWS_1252.name(),
+        }
+    }
+}
+
+/// Normalize line endings to "\n" and remove invisible characters that break parsing and search:
+/// control characters (except tab and newline), zero-width characters, stray BOMs and bidi marks.
+pub fn clean_text(text: &str) -> String {
+    let text = text.replace("\r\n", "\n").replace('\r', "\n");
+
+    text.chars()
+        .filter_map(|c| match c {
+            '\n' | '\t' => Some(c),
+            '\u{2028}' | '\u{2029}' => Some('\n'),
+            '\u{00A0}' | '\u{202F}' => Some(' '),
+            '\u{00AD}' | '\u{200B}'..='\u{200F}' | '\u{202A}'..='\u{202E}' | '\u{2060}'..='\u{2064}'
+            | '\u{2066}'..='\u{2069}' | '\u{FEFF}' => None,
+            c if c.is_control() => None,
+            c => Some(c),
+        })
+        .collect()
+}
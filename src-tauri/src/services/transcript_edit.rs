@@ -0,0 +1,143 @@
+// Applying text edits to timed transcript segments and diffing transcript revisions
+use serde::{Deserialize, Serialize};
+use similar::{capture_diff_slices, Algorithm, ChangeTag, DiffOp, TextDiff};
+
+use super::transcript_parser::{ParsedSegment, ParsedWord};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TextChange {
+    pub kind: String, // "equal", "insert" or "delete"
+    pub text: String,
+}
+
+/// Carry an edit of the full transcript text over to its segments, keeping segment timings.
+/// Each word of `old_content` is matched to the segment it came from; words the edit keeps stay in
+/// their segment, inserted words join the segment of the word before them, and words that belong to
+/// no segment (speaker labels, timestamps) are dropped. Segments left without words are removed.
+pub fn apply_text_edit(segments: &[ParsedSegment], old_content: &str, new_content: &str) -> Vec<ParsedSegment> {
+    let old_tokens: Vec<&str> = old_content.split_whitespace().collect();
+    let new_tokens: Vec<&str> = new_content.split_whitespace().collect();
+    let owners = token_owners(segments, &old_tokens);
+
+    // Nearest segment around an old position, looking backwards first
+    let owner_near = |index: usize| -> usize {
+        owners[..index.min(owners.len())]
+            .iter()
+            .rev()
+            .chain(owners[index.min(owners.len())..].iter())
+            .find_map(|owner| *owner)
+            .unwrap_or(0)
+    };
+
+    let mut texts: Vec<Vec<&str>> = vec![Vec::new(); segments.len().max(1)];
+
+    for op in capture_diff_slices(Algorithm::Myers, &old_tokens, &new_tokens) {
+        match op {
+            DiffOp::Equal { old_index, new_index, len } => {
+                for offset in 0..len {
+                    if let Some(segment) = owners[old_index + offset] {
+                        texts[segment].push(new_tokens[new_index + offset]);
+                    }
+                }
+            }
+            DiffOp::Delete { .. } => {}
+            DiffOp::Insert { old_index, new_index, new_len } => {
+                let segment = owner_near(old_index);
+                texts[segment].extend(&new_tokens[new_index..new_index + new_len]);
+            }
+            DiffOp::Replace { old_index, new_index, new_len, .. } => {
+                let segment = owners[old_index].unwrap_or_else(|| owner_near(old_index));
+                texts[segment].extend(&new_tokens[new_index..new_index + new_len]);
+            }
+        }
+    }
+
+    if segments.is_empty() {
+        return vec![ParsedSegment {
+            start_time: None,
+            end_time: None,
+            speaker: None,
+            text: texts[0].join(" "),
+            words: Vec::new(),
+        }];
+    }
+
+    segments
+        .iter()
+        .zip(texts)
+        .filter(|(_, words)| !words.is_empty())
+        .map(|(segment, words)| with_text(segment, &words.join(" ")))
+        .collect()
+}
+
+// Which segment each word of the content came from; None for words no segment contains
+fn token_owners(segments: &[ParsedSegment], tokens: &[&str]) -> Vec<Option<usize>> {
+    let mut owners = vec![None; tokens.len()];
+    let mut cursor = 0;
+
+    for (index, segment) in segments.iter().enumerate() {
+        for word in segment.text.split_whitespace() {
+            if let Some(offset) = tokens[cursor..].iter().position(|token| *token == word) {
+                owners[cursor + offset] = Some(index);
+                cursor += offset + 1;
+            }
+        }
+    }
+
+    owners
+}
+
+/// Replace a segment's text, keeping word timings when the word count is unchanged
+pub fn with_text(segment: &ParsedSegment, text: &str) -> ParsedSegment {
+    let tokens: Vec<&str> = text.split_whitespace().collect();
+
+    let words = if segment.text == text {
+        segment.words.clone()
+    } else if segment.words.len() == tokens.len() {
+        segment
+            .words
+            .iter()
+            .zip(&tokens)
+            .map(|(word, token)| ParsedWord {
+                text: token.to_string(),
+                start_time: word.start_time,
+                end_time: word.end_time,
+                confidence: if word.text == *token { word.confidence } else { None },
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    ParsedSegment {
+        start_time: segment.start_time,
+        end_time: segment.end_time,
+        speaker: segment.speaker.clone(),
+        text: tokens.join(" "),
+        words,
+    }
+}
+
+/// Word-level diff, with consecutive words of the same kind merged into one change
+pub fn diff_words(old: &str, new: &str) -> Vec<TextChange> {
+    let diff = TextDiff::from_words(old, new);
+    let mut changes: Vec<TextChange> = Vec::new();
+
+    for change in diff.iter_all_changes() {
+        let kind = match change.tag() {
+            ChangeTag::Equal => "equal",
+            ChangeTag::Insert => "insert",
+            ChangeTag::Delete => "delete",
+        };
+
+        match changes.last_mut() {
+            Some(last) if last.kind == kind => last.text.push_str(change.value()),
+            _ => changes.push(TextChange {
+                kind: kind.to_string(),
+                text: change.value().to_string(),
+            }),
+        }
+    }
+
+    changes
+}
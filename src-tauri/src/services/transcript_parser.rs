@@ -0,0 +1,1164 @@
+// Transcript parsing: turns raw transcript files into timed, speaker-attributed segments
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::io::{Cursor, Read};
+use zip::ZipArchive;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParsedWord {
+    pub text: String,
+    pub start_time: Option<f64>,
+    pub end_time: Option<f64>,
+    pub confidence: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParsedSegment {
+    pub start_time: Option<f64>,
+    pub end_time: Option<f64>,
+    pub speaker: Option<String>,
+    pub text: String,
+    pub words: Vec<ParsedWord>, // Word-level timings, when the format has them
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ParsedTranscript {
+    pub segments: Vec<ParsedSegment>,
+    pub language: Option<String>, // Language declared by the file itself
+}
+
+impl ParsedTranscript {
+    /// Distinct speaker labels in order of first appearance
+    pub fn speakers(&self) -> Vec<String> {
+        let mut speakers: Vec<String> = Vec::new();
+        for segment in &self.segments {
+            if let Some(speaker) = &segment.speaker {
+                if !speakers.contains(speaker) {
+                    speakers.push(speaker.clone());
+                }
+            }
+        }
+        speakers
+    }
+
+    /// Flattened text, prefixing "Label:" whenever the speaker changes
+    pub fn plain_text(&self) -> String {
+        let mut parts = Vec::new();
+        let mut current_speaker: Option<&String> = None;
+
+        for segment in &self.segments {
+            match &segment.speaker {
+                Some(speaker) if Some(speaker) != current_speaker => {
+                    current_speaker = Some(speaker);
+                    parts.push(format!("{}: {}", speaker, segment.text));
+                }
+                _ => parts.push(segment.text.clone()),
+            }
+        }
+
+        parts.join(" ")
+    }
+
+    pub fn word_count(&self) -> usize {
+        self.segments.iter().map(|s| s.text.split_whitespace().count()).sum()
+    }
+}
+
+/// Parse transcript content based on its file extension (DOCX content must already be extracted text)
+pub fn parse_transcript(content: &str, extension: &str) -> Result<ParsedTranscript, String> {
+    let parsed = match extension {
+        "srt" => parse_srt(content),
+        "vtt" => parse_vtt(content),
+        "sbv" => parse_sbv(content),
+        "txt" | "docx" => parse_txt(content),
+        "ttml" | "dfxp" => parse_ttml(content)?,
+        "json" => parse_json(content)?,
+        other => return Err(format!("Unsupported transcript format: {}", other)),
+    };
+
+    if parsed.segments.is_empty() {
+        return Err(format!("No transcript text found in {} file", extension.to_uppercase()));
+    }
+
+    Ok(parsed)
+}
+
+// SubRip: numbered cues separated by blank lines
+fn parse_srt(content: &str) -> ParsedTranscript {
+    let mut segments = Vec::new();
+    let mut last_speaker: Option<String> = None;
+
+    for block in split_blocks(content) {
+        let mut lines = block.iter().map(|l| l.trim()).peekable();
+
+        // Skip sequence number
+        if lines.peek().map_or(false, |l| l.parse::<u32>().is_ok()) {
+            lines.next();
+        }
+
+        let (start_time, end_time) = match lines.peek().and_then(|l| parse_timing_line(l)) {
+            Some(timing) => {
+                lines.next();
+                (Some(timing.0), Some(timing.1))
+            }
+            None => (None, None),
+        };
+
+        let text_lines: Vec<&str> = lines.collect();
+        push_labelled_lines(&mut segments, &mut last_speaker, &text_lines, start_time, end_time);
+    }
+
+    ParsedTranscript { segments, ..Default::default() }
+}
+
+// WebVTT: header, optional NOTE/STYLE/REGION blocks, cues with optional identifiers
+fn parse_vtt(content: &str) -> ParsedTranscript {
+    let mut segments = Vec::new();
+    let mut last_speaker: Option<String> = None;
+
+    for block in split_blocks(content) {
+        let first = block[0].trim();
+        if first.starts_with("WEBVTT")
+            || first.starts_with("NOTE")
+            || first.starts_with("STYLE")
+            || first.starts_with("REGION")
+        {
+            continue;
+        }
+
+        let timing_index = block.iter().position(|l| l.contains("-->"));
+        let (start_time, end_time, text_start) = match timing_index {
+            Some(index) => match parse_timing_line(block[index].trim()) {
+                Some((start, end)) => (Some(start), Some(end), index + 1),
+                None => continue,
+            },
+            None => (None, None, 0),
+        };
+
+        let cue_text = block[text_start..]
+            .iter()
+            .map(|l| l.trim())
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        if cue_text.contains("<v") {
+            // One segment per voice span
+            for (speaker, text) in split_voice_spans(&cue_text) {
+                let text = collapse_whitespace(&strip_tags(&text));
+                if text.is_empty() {
+                    continue;
+                }
+                let speaker = speaker.or_else(|| last_speaker.clone());
+                last_speaker = speaker.clone();
+                segments.push(ParsedSegment { start_time, end_time, speaker, text, words: Vec::new() });
+            }
+        } else {
+            let stripped = strip_tags(&cue_text);
+            let text_lines: Vec<&str> = stripped.lines().map(|l| l.trim()).collect();
+            push_labelled_lines(&mut segments, &mut last_speaker, &text_lines, start_time, end_time);
+        }
+    }
+
+    ParsedTranscript { segments, ..Default::default() }
+}
+
+// Plain text: one segment per paragraph or speaker turn, no timing
+fn parse_txt(content: &str) -> ParsedTranscript {
+    let mut segments: Vec<ParsedSegment> = Vec::new();
+    let mut last_speaker: Option<String> = None;
+
+    for block in split_blocks(content) {
+        let mut current: Option<ParsedSegment> = None;
+
+        for line in block.iter().map(|l| l.trim()) {
+            let (start_time, rest) = strip_leading_timestamp(line);
+            match split_speaker_label(rest) {
+                Some((speaker, label_time, text)) => {
+                    segments.extend(current.take());
+                    last_speaker = Some(speaker.clone());
+                    current = Some(ParsedSegment {
+                        start_time: start_time.or(label_time),
+                        end_time: None,
+                        speaker: Some(speaker),
+                        text: text.to_string(),
+                        words: Vec::new(),
+                    });
+                }
+                None => match current.as_mut() {
+                    Some(segment) => {
+                        segment.text.push(' ');
+                        segment.text.push_str(rest);
+                    }
+                    None => {
+                        current = Some(ParsedSegment {
+                            start_time,
+                            end_time: None,
+                            speaker: last_speaker.clone(),
+                            text: rest.to_string(),
+                            words: Vec::new(),
+                        });
+                    }
+                },
+            }
+        }
+
+        segments.extend(current);
+    }
+
+    segments.retain(|s| !s.text.trim().is_empty());
+    fill_end_times(&mut segments);
+
+    ParsedTranscript { segments, ..Default::default() }
+}
+
+// YouTube SBV: "0:00:01.000,0:00:04.000" timing line followed by text lines
+fn parse_sbv(content: &str) -> ParsedTranscript {
+    let mut segments = Vec::new();
+    let mut last_speaker: Option<String> = None;
+
+    for block in split_blocks(content) {
+        let timing = block[0]
+            .trim()
+            .split_once(',')
+            .and_then(|(start, end)| Some((parse_timestamp(start)?, parse_timestamp(end)?)));
+
+        let (start_time, end_time, text_lines) = match timing {
+            Some((start, end)) => (Some(start), Some(end), &block[1..]),
+            None => (None, None, &block[..]),
+        };
+
+        let text_lines: Vec<&str> = text_lines.iter().map(|l| l.trim()).collect();
+        push_labelled_lines(&mut segments, &mut last_speaker, &text_lines, start_time, end_time);
+    }
+
+    ParsedTranscript { segments, ..Default::default() }
+}
+
+// TTML / DFXP: <p begin end> cues, speakers via ttm:agent references
+fn parse_ttml(content: &str) -> Result<ParsedTranscript, String> {
+    let mut reader = Reader::from_str(content);
+    let mut segments = Vec::new();
+    let mut agents: HashMap<String, String> = HashMap::new();
+    let mut language = None;
+    let mut frame_rate = 30.0;
+    let mut tick_rate = 1.0;
+
+    // State for the cue currently being read
+    let mut cue: Option<(Option<f64>, Option<f64>, Option<String>, String)> = None;
+    let mut agent_id: Option<String> = None;
+    let mut in_agent_name = false;
+
+    loop {
+        let event = reader
+            .read_event()
+            .map_err(|e| format!("Invalid TTML at position {}: {}", reader.buffer_position(), e))?;
+
+        match event {
+            Event::Start(ref e) | Event::Empty(ref e) => {
+                let is_empty = matches!(event, Event::Empty(_));
+                let attrs = xml_attributes(e);
+
+                match e.local_name().as_ref() {
+                    b"tt" => {
+                        language = attrs.get("lang").cloned();
+                        if let Some(rate) = attrs.get("frameRate").and_then(|r| r.parse().ok()) {
+                            frame_rate = rate;
+                        }
+                        if let Some(rate) = attrs.get("tickRate").and_then(|r| r.parse().ok()) {
+                            tick_rate = rate;
+                        }
+                    }
+                    b"agent" => agent_id = attrs.get("id").cloned(),
+                    b"name" if agent_id.is_some() => in_agent_name = !is_empty,
+                    b"p" => {
+                        let begin = attrs.get("begin").and_then(|t| parse_ttml_time(t, frame_rate, tick_rate));
+                        let end = attrs.get("end").and_then(|t| parse_ttml_time(t, frame_rate, tick_rate)).or_else(|| {
+                            let duration = attrs.get("dur").and_then(|t| parse_ttml_time(t, frame_rate, tick_rate))?;
+                            Some(begin? + duration)
+                        });
+                        if !is_empty {
+                            cue = Some((begin, end, attrs.get("agent").cloned(), String::new()));
+                        }
+                    }
+                    b"span" => {
+                        if let Some((_, _, agent, _)) = cue.as_mut() {
+                            if agent.is_none() {
+                                *agent = attrs.get("agent").cloned();
+                            }
+                        }
+                    }
+                    b"br" => {
+                        if let Some((_, _, _, text)) = cue.as_mut() {
+                            text.push(' ');
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            Event::Text(e) => {
+                let text = e.unescape().map_err(|e| format!("Invalid TTML text: {}", e))?;
+                if in_agent_name {
+                    if let Some(id) = &agent_id {
+                        agents.insert(id.clone(), text.trim().to_string());
+                    }
+                } else if let Some((_, _, _, cue_text)) = cue.as_mut() {
+                    cue_text.push_str(&text);
+                }
+            }
+            Event::End(ref e) => match e.local_name().as_ref() {
+                b"p" => {
+                    if let Some((start_time, end_time, agent, text)) = cue.take() {
+                        let text = collapse_whitespace(&text);
+                        if !text.is_empty() {
+                            let speaker = agent.map(|id| agents.get(&id).cloned().unwrap_or(id));
+                            segments.push(ParsedSegment { start_time, end_time, speaker, text, words: Vec::new() });
+                        }
+                    }
+                }
+                b"name" => in_agent_name = false,
+                b"agent" => agent_id = None,
+                _ => {}
+            },
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok(ParsedTranscript { segments, language })
+}
+
+// Attributes keyed by local name, so "ttm:agent" and "xml:lang" become "agent" and "lang"
+fn xml_attributes(element: &BytesStart) -> HashMap<String, String> {
+    element
+        .attributes()
+        .flatten()
+        .filter_map(|attr| {
+            let key = String::from_utf8_lossy(attr.key.local_name().as_ref()).to_string();
+            let value = attr.unescape_value().ok()?.to_string();
+            Some((key, value))
+        })
+        .collect()
+}
+
+/// Parse TTML clock times ("00:00:01.500", "00:00:01:12" with frames) and offsets ("1.5s", "1500ms", "90f", "15000000t")
+fn parse_ttml_time(value: &str, frame_rate: f64, tick_rate: f64) -> Option<f64> {
+    let value = value.trim();
+
+    if value.contains(':') {
+        let parts: Vec<&str> = value.split(':').collect();
+        if parts.len() == 4 {
+            let clock = parse_timestamp(&parts[..3].join(":"))?;
+            let frames: f64 = parts[3].parse().ok()?;
+            return Some(clock + frames / frame_rate);
+        }
+        return parse_timestamp(value);
+    }
+
+    let split = value.find(|c: char| c.is_alphabetic())?;
+    let (number, unit) = value.split_at(split);
+    let number: f64 = number.parse().ok()?;
+
+    match unit {
+        "h" => Some(number * 3600.0),
+        "m" => Some(number * 60.0),
+        "s" => Some(number),
+        "ms" => Some(number / 1000.0),
+        "f" => Some(number / frame_rate),
+        "t" => Some(number / tick_rate),
+        _ => None,
+    }
+}
+
+/// Extract paragraph text from a DOCX document, one paragraph per line
+pub fn extract_docx_text(bytes: &[u8]) -> Result<String, String> {
+    let mut archive = ZipArchive::new(Cursor::new(bytes))
+        .map_err(|e| format!("Invalid DOCX file: {}", e))?;
+
+    let mut document = String::new();
+    archive
+        .by_name("word/document.xml")
+        .map_err(|_| "Invalid DOCX file: missing word/document.xml".to_string())?
+        .read_to_string(&mut document)
+        .map_err(|e| format!("Failed to read DOCX document: {}", e))?;
+
+    let mut reader = Reader::from_str(&document);
+    let mut paragraphs = Vec::new();
+    let mut current = String::new();
+    let mut in_text = false;
+
+    loop {
+        match reader.read_event().map_err(|e| format!("Invalid DOCX document XML: {}", e))? {
+            Event::Start(ref e) if e.local_name().as_ref() == b"t" => in_text = true,
+            Event::End(ref e) => match e.local_name().as_ref() {
+                b"t" => in_text = false,
+                b"p" => paragraphs.push(std::mem::take(&mut current)),
+                _ => {}
+            },
+            Event::Empty(ref e) => match e.local_name().as_ref() {
+                b"tab" => current.push('\t'),
+                b"br" | b"cr" => current.push('\n'),
+                _ => {}
+            },
+            Event::Text(e) if in_text => {
+                current.push_str(&e.unescape().map_err(|e| format!("Invalid DOCX text: {}", e))?);
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    // Blank lines between paragraphs so each becomes its own block
+    Ok(paragraphs.join("\n\n"))
+}
+
+// JSON exports: detect the producing tool from the document shape
+fn parse_json(content: &str) -> Result<ParsedTranscript, String> {
+    let json: Value = serde_json::from_str(content).map_err(|e| format!("Invalid JSON: {}", e))?;
+
+    if json.get("transcription").is_some() {
+        parse_whisper_cpp_json(&json)
+    } else if json.get("speakers").is_some() || json.get("paragraphs").is_some() || json.get("utterances").is_some() {
+        parse_descript_json(&json)
+    } else if json.get("segments").is_some() {
+        parse_whisper_json(&json)
+    } else {
+        Err("Unrecognized JSON transcript: expected Whisper or Descript export".to_string())
+    }
+}
+
+// openai-whisper / faster-whisper / WhisperX: {"language", "segments": [{"start", "end", "text", "speaker"?, "words"?}]}
+fn parse_whisper_json(json: &Value) -> Result<ParsedTranscript, String> {
+    let items = json["segments"]
+        .as_array()
+        .ok_or("Invalid Whisper JSON: 'segments' must be an array")?;
+
+    let segments = items
+        .iter()
+        .filter_map(|item| {
+            let text = collapse_whitespace(item["text"].as_str()?);
+            if text.is_empty() {
+                return None;
+            }
+
+            let words = item["words"]
+                .as_array()
+                .map(|words| {
+                    words
+                        .iter()
+                        .filter_map(|word| {
+                            Some(ParsedWord {
+                                text: word["word"].as_str().or(word["text"].as_str())?.trim().to_string(),
+                                start_time: word["start"].as_f64(),
+                                end_time: word["end"].as_f64(),
+                                confidence: word["probability"].as_f64().or(word["score"].as_f64()),
+                            })
+                        })
+                        .filter(|word| !word.text.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default();
+
+            Some(ParsedSegment {
+                start_time: item["start"].as_f64(),
+                end_time: item["end"].as_f64(),
+                speaker: item["speaker"].as_str().map(str::to_string),
+                text,
+                words,
+            })
+        })
+        .collect();
+
+    Ok(ParsedTranscript {
+        segments,
+        language: json["language"].as_str().map(str::to_string),
+    })
+}
+
+// whisper.cpp --output-json(-full): {"result": {"language"}, "transcription": [{"offsets": {"from", "to"}, "text", "tokens"?}]}
+fn parse_whisper_cpp_json(json: &Value) -> Result<ParsedTranscript, String> {
+    let items = json["transcription"]
+        .as_array()
+        .ok_or("Invalid whisper.cpp JSON: 'transcription' must be an array")?;
+
+    let segments = items
+        .iter()
+        .filter_map(|item| {
+            let text = collapse_whitespace(item["text"].as_str()?);
+            if text.is_empty() {
+                return None;
+            }
+
+            let (start_time, end_time) = whisper_cpp_range(item);
+
+            // Tokens starting with a space begin a new word; "[_BEG_]"-style tokens are control tokens
+            let mut words: Vec<ParsedWord> = Vec::new();
+            for token in item["tokens"].as_array().into_iter().flatten() {
+                let token_text = token["text"].as_str().unwrap_or_default();
+                if token_text.is_empty() || token_text.starts_with("[_") {
+                    continue;
+                }
+                let (token_start, token_end) = whisper_cpp_range(token);
+
+                match words.last_mut() {
+                    Some(word) if !token_text.starts_with(' ') => {
+                        word.text.push_str(token_text);
+                        word.end_time = token_end.or(word.end_time);
+                    }
+                    _ => words.push(ParsedWord {
+                        text: token_text.trim().to_string(),
+                        start_time: token_start,
+                        end_time: token_end,
+                        confidence: token["p"].as_f64(),
+                    }),
+                }
+            }
+
+            Some(ParsedSegment { start_time, end_time, speaker: None, text, words })
+        })
+        .collect();
+
+    Ok(ParsedTranscript {
+        segments,
+        language: json["result"]["language"].as_str().map(str::to_string),
+    })
+}
+
+// whisper.cpp gives millisecond "offsets" and "HH:MM:SS,mmm" "timestamps"
+fn whisper_cpp_range(item: &Value) -> (Option<f64>, Option<f64>) {
+    let offset = |key: &str| {
+        item["offsets"][key]
+            .as_f64()
+            .map(|ms| ms / 1000.0)
+            .or_else(|| item["timestamps"][key].as_str().and_then(parse_timestamp))
+    };
+    (offset("from"), offset("to"))
+}
+
+// Descript transcript JSON: a "speakers" list plus "paragraphs"/"utterances" (or a flat "words" list), times in seconds
+fn parse_descript_json(json: &Value) -> Result<ParsedTranscript, String> {
+    let speaker_names = descript_speaker_names(&json["speakers"]);
+    let speaker_of = |item: &Value| -> Option<String> {
+        let id = ["speaker", "speaker_id", "speakerId", "speaker_name", "speakerName"]
+            .iter()
+            .find_map(|key| match &item[*key] {
+                Value::String(s) => Some(s.clone()),
+                Value::Number(n) => Some(n.to_string()),
+                _ => None,
+            })?;
+        Some(speaker_names.get(&id).cloned().unwrap_or(id))
+    };
+
+    let word_of = |item: &Value| -> Option<ParsedWord> {
+        Some(ParsedWord {
+            text: item["text"].as_str().or(item["word"].as_str())?.trim().to_string(),
+            start_time: item["start"].as_f64(),
+            end_time: item["end"].as_f64(),
+            confidence: item["confidence"].as_f64(),
+        })
+    };
+
+    let mut segments = Vec::new();
+
+    if let Some(items) = ["paragraphs", "utterances", "segments"].iter().find_map(|key| json[*key].as_array()) {
+        for item in items {
+            let words: Vec<ParsedWord> = item["words"]
+                .as_array()
+                .map(|words| words.iter().filter_map(word_of).collect())
+                .unwrap_or_default();
+
+            let text = match item["text"].as_str() {
+                Some(text) => collapse_whitespace(text),
+                None => words.iter().map(|w| w.text.as_str()).collect::<Vec<_>>().join(" "),
+            };
+            if text.is_empty() {
+                continue;
+            }
+
+            segments.push(ParsedSegment {
+                start_time: item["start"].as_f64().or_else(|| words.first().and_then(|w| w.start_time)),
+                end_time: item["end"].as_f64().or_else(|| words.last().and_then(|w| w.end_time)),
+                speaker: speaker_of(item),
+                text,
+                words,
+            });
+        }
+    } else if let Some(items) = json["words"].as_array() {
+        // Flat word list: start a new segment whenever the speaker changes
+        for item in items {
+            let Some(word) = word_of(item).filter(|w| !w.text.is_empty()) else {
+                continue;
+            };
+            let speaker = speaker_of(item);
+
+            match segments.last_mut() {
+                Some(segment) if segment.speaker == speaker => {
+                    segment.text.push(' ');
+                    segment.text.push_str(&word.text);
+                    segment.end_time = word.end_time.or(segment.end_time);
+                    segment.words.push(word);
+                }
+                _ => segments.push(ParsedSegment {
+                    start_time: word.start_time,
+                    end_time: word.end_time,
+                    speaker,
+                    text: word.text.clone(),
+                    words: vec![word],
+                }),
+            }
+        }
+    } else {
+        return Err("Invalid Descript JSON: expected 'paragraphs', 'utterances' or 'words'".to_string());
+    }
+
+    Ok(ParsedTranscript {
+        segments,
+        language: json["language"].as_str().map(str::to_string),
+    })
+}
+
+// Speakers come as [{"id", "name"}] or {"id": "name"}
+fn descript_speaker_names(speakers: &Value) -> HashMap<String, String> {
+    match speakers {
+        Value::Array(items) => items
+            .iter()
+            .filter_map(|item| {
+                let id = match &item["id"] {
+                    Value::String(s) => s.clone(),
+                    Value::Number(n) => n.to_string(),
+                    _ => return None,
+                };
+                Some((id, item["name"].as_str()?.to_string()))
+            })
+            .collect(),
+        Value::Object(map) => map
+            .iter()
+            .filter_map(|(id, name)| Some((id.clone(), name.as_str()?.to_string())))
+            .collect(),
+        _ => HashMap::new(),
+    }
+}
+
+// Turn cue text lines into segments, starting a new segment at each speaker label
+fn push_labelled_lines(
+    segments: &mut Vec<ParsedSegment>,
+    last_speaker: &mut Option<String>,
+    lines: &[&str],
+    start_time: Option<f64>,
+    end_time: Option<f64>,
+) {
+    let mut current: Option<ParsedSegment> = None;
+
+    for line in lines.iter().filter(|l| !l.is_empty()) {
+        // Captions often mark speaker changes with a leading dash
+        let line = line.trim_start_matches("- ").trim_start_matches(">> ");
+
+        match split_speaker_label(line) {
+            Some((speaker, _, text)) => {
+                segments.extend(current.take());
+                *last_speaker = Some(speaker.clone());
+                current = Some(ParsedSegment {
+                    start_time,
+                    end_time,
+                    speaker: Some(speaker),
+                    text: text.to_string(),
+                    words: Vec::new(),
+                });
+            }
+            None => match current.as_mut() {
+                Some(segment) => {
+                    segment.text.push(' ');
+                    segment.text.push_str(line);
+                }
+                None => {
+                    current = Some(ParsedSegment {
+                        start_time,
+                        end_time,
+                        speaker: last_speaker.clone(),
+                        text: line.to_string(),
+                        words: Vec::new(),
+                    });
+                }
+            },
+        }
+    }
+
+    segments.extend(current.filter(|s| !s.text.trim().is_empty()));
+}
+
+// Group lines into blank-line separated blocks
+fn split_blocks(content: &str) -> Vec<Vec<&str>> {
+    let mut blocks = Vec::new();
+    let mut current = Vec::new();
+
+    for line in content.lines() {
+        if line.trim().is_empty() {
+            if !current.is_empty() {
+                blocks.push(std::mem::take(&mut current));
+            }
+        } else {
+            current.push(line);
+        }
+    }
+
+    if !current.is_empty() {
+        blocks.push(current);
+    }
+
+    blocks
+}
+
+/// Parse "00:01:02,500 --> 00:01:05,000" (SRT) or "01:02.500 --> 01:05.000 align:start" (VTT)
+pub fn parse_timing_line(line: &str) -> Option<(f64, f64)> {
+    let (start, rest) = line.split_once("-->")?;
+    let end = rest.split_whitespace().next()?;
+    Some((parse_timestamp(start.trim())?, parse_timestamp(end)?))
+}
+
+/// Parse "HH:MM:SS,mmm", "HH:MM:SS.mmm" or "MM:SS.mmm" into seconds
+pub fn parse_timestamp(value: &str) -> Option<f64> {
+    let parts: Vec<&str> = value.trim().split(':').collect();
+    if parts.len() < 2 || parts.len() > 3 {
+        return None;
+    }
+    // Plain digits only; f64 parsing would also accept "NaN", "inf" and exponents
+    let numeric = |part: &str| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit() || c == '.' || c == ',');
+    if !parts.iter().all(|part| numeric(part)) {
+        return None;
+    }
+
+    let seconds: f64 = parts[parts.len() - 1].replace(',', ".").parse().ok()?;
+    let minutes: f64 = parts[parts.len() - 2].parse().ok()?;
+    let hours: f64 = if parts.len() == 3 { parts[0].parse().ok()? } else { 0.0 };
+
+    if seconds < 0.0 || minutes < 0.0 || hours < 0.0 {
+        return None;
+    }
+
+    Some(hours * 3600.0 + minutes * 60.0 + seconds)
+}
+
+// Strip "[00:01:02]" or "(01:02)" prefixes used by Descript and other text exports
+fn strip_leading_timestamp(line: &str) -> (Option<f64>, &str) {
+    for (open, close) in [('[', ']'), ('(', ')')] {
+        if let Some(rest) = line.strip_prefix(open) {
+            if let Some((inner, after)) = rest.split_once(close) {
+                if let Some(seconds) = parse_timestamp(inner) {
+                    return (Some(seconds), after.trim_start());
+                }
+            }
+        }
+    }
+    (None, line)
+}
+
+/// Split a "Speaker 1: text" or "Jane Doe (00:01:02): text" line into label, optional time and text
+pub fn split_speaker_label(line: &str) -> Option<(String, Option<f64>, &str)> {
+    // First colon outside parentheses that ends the line or is followed by whitespace
+    let mut depth = 0;
+    let colon = line.char_indices().find(|&(i, c)| {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            _ => {}
+        }
+        c == ':' && depth == 0 && line[i + 1..].chars().next().map_or(true, char::is_whitespace)
+    })?;
+    let (label, text) = (&line[..colon.0], &line[colon.0 + 1..]);
+
+    // Split off a trailing "(00:01:02)" timestamp from the label
+    let (label, time) = match label.rfind('(') {
+        Some(index) if label.ends_with(')') => match parse_timestamp(&label[index + 1..label.len() - 1]) {
+            Some(seconds) => (label[..index].trim(), Some(seconds)),
+            None => (label.trim(), None),
+        },
+        _ => (label.trim(), None),
+    };
+
+    if !is_speaker_label(label) {
+        return None;
+    }
+
+    Some((label.to_string(), time, text.trim()))
+}
+
+// Words that open ordinary sentences or notes with a colon ("Note:", "Update:") rather than name a speaker
+const NON_SPEAKER_WORDS: &[&str] = &[
+    "note", "notes", "nb", "ps", "update", "edit", "step", "tip", "warning", "caution", "important",
+    "example", "summary", "reminder", "disclaimer", "source", "sources", "fyi", "tldr", "todo",
+    "agenda", "topic", "chapter", "part", "section", "result", "results", "conclusion", "takeaway",
+    "key", "action", "title", "subject", "date", "time", "location", "transcript",
+];
+
+// Generic words that label anonymous speakers by number, as in "Speaker 2" or "Guest 1"
+const NUMBERED_SPEAKER_WORDS: &[&str] = &["speaker", "spk", "guest", "participant", "person", "voice", "caller", "interviewee"];
+
+// Labels are short, capitalised names like "Speaker 1", "SPEAKER_00", "Host" or "Jane Doe"
+fn is_speaker_label(label: &str) -> bool {
+    if label.is_empty() || label.len() > 40 {
+        return false;
+    }
+
+    let words: Vec<&str> = label.split_whitespace().collect();
+    if words.is_empty() || words.len() > 3 {
+        return false;
+    }
+    if NON_SPEAKER_WORDS.contains(&words[0].to_lowercase().trim_end_matches('.')) {
+        return false;
+    }
+
+    let shaped = words.iter().all(|word| {
+        word.starts_with(|c: char| c.is_uppercase() || c.is_ascii_digit())
+            && word.chars().all(|c| c.is_alphanumeric() || c == '.' || c == '\'' || c == '-' || c == '_')
+    });
+    if !shaped {
+        return false;
+    }
+
+    // Digits only in numbered speaker IDs, so "Step 1:" or "Q3 2024:" stay part of the text
+    if !label.chars().any(|c| c.is_ascii_digit()) {
+        return true;
+    }
+    let is_number = |word: &str| word.chars().all(|c| c.is_ascii_digit());
+    match words.as_slice() {
+        [id] => id
+            .split_once('_')
+            .is_some_and(|(name, number)| name.chars().all(char::is_alphabetic) && !name.is_empty() && !number.is_empty() && is_number(number)),
+        [name, number] => NUMBERED_SPEAKER_WORDS.contains(&name.to_lowercase().as_str()) && is_number(number),
+        _ => false,
+    }
+}
+
+/// Whether a speaker label is an anonymous ID ("SPEAKER_00", "Speaker 2", "1", "A") rather than a
+/// name, so the same label in two transcripts needn't be the same person
+pub fn is_generic_speaker_label(label: &str) -> bool {
+    let label = label.trim();
+    label.chars().any(|c| c.is_ascii_digit()) || (label.chars().count() == 1 && label.chars().all(char::is_alphabetic))
+}
+
+// Split WebVTT cue text on <v Name> voice spans
+fn split_voice_spans(text: &str) -> Vec<(Option<String>, String)> {
+    let mut spans = Vec::new();
+    let mut rest = text;
+
+    // Text before the first voice tag belongs to no explicit speaker
+    if let Some(index) = rest.find("<v") {
+        if !rest[..index].trim().is_empty() {
+            spans.push((None, rest[..index].to_string()));
+        }
+        rest = &rest[index..];
+    }
+
+    while let Some(tag_end) = rest.find('>') {
+        // "<v Name>" or "<v.loud Name>"
+        let tag = &rest[2..tag_end];
+        let name = tag
+            .split_once(char::is_whitespace)
+            .map(|(_, name)| name.trim().to_string())
+            .filter(|name| !name.is_empty());
+
+        let body_start = tag_end + 1;
+        let body_end = rest[body_start..]
+            .find("<v")
+            .map(|i| body_start + i)
+            .unwrap_or(rest.len());
+
+        spans.push((name, rest[body_start..body_end].replace("</v>", "")));
+        rest = &rest[body_end..];
+
+        if rest.is_empty() {
+            break;
+        }
+    }
+
+    spans
+}
+
+// Remove markup such as <i>, <c.yellow> and inline <00:00:01.000> timestamps
+fn strip_tags(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut in_tag = false;
+
+    for c in text.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => result.push(c),
+            _ => {}
+        }
+    }
+
+    result
+        .replace("&amp;", "&")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&nbsp;", " ")
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+// Untimed formats only know start times; close each segment at the next one's start
+fn fill_end_times(segments: &mut [ParsedSegment]) {
+    for i in 0..segments.len() {
+        if segments[i].start_time.is_none() || segments[i].end_time.is_some() {
+            continue;
+        }
+        segments[i].end_time = segments[i + 1..].iter().find_map(|s| s.start_time);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Cue<'a> = (Option<f64>, Option<f64>, Option<&'a str>, &'a str);
+
+    fn cues(parsed: &ParsedTranscript) -> Vec<Cue<'_>> {
+        parsed.segments.iter().map(|s| (s.start_time, s.end_time, s.speaker.as_deref(), s.text.as_str())).collect()
+    }
+
+    #[test]
+    fn splits_speaker_labels() {
+        assert_eq!(split_speaker_label("Jane Doe: Hello there"), Some(("Jane Doe".to_string(), None, "Hello there")));
+        assert_eq!(split_speaker_label("Speaker 2: Yes"), Some(("Speaker 2".to_string(), None, "Yes")));
+        assert_eq!(split_speaker_label("SPEAKER_00: Right"), Some(("SPEAKER_00".to_string(), None, "Right")));
+        assert_eq!(split_speaker_label("Host (00:01:02): Welcome"), Some(("Host".to_string(), Some(62.0), "Welcome")));
+        assert_eq!(split_speaker_label("José: ¿Qué tal?"), Some(("José".to_string(), None, "¿Qué tal?")));
+    }
+
+    #[test]
+    fn rejects_prose_before_a_colon() {
+        for line in [
+            "Note: this is important",
+            "Update: we moved the launch",
+            "Step 1: open the app",
+            "Q3 2024: revenue grew",
+            "Key takeaway: ship it",
+            "The Plan For Next Year: more video",
+            "lowercase: not a name",
+            "See https://example.com for details",
+            "Time 10:30 works",
+        ] {
+            assert_eq!(split_speaker_label(line), None, "{}", line);
+        }
+    }
+
+    #[test]
+    fn keeps_prose_lines_with_the_current_speaker() {
+        let parsed = parse_transcript("Host: Welcome back.\nNote: this part was recorded later.\n\nGuest 1: Thanks!", "txt").unwrap();
+        assert_eq!(parsed.speakers(), vec!["Host", "Guest 1"]);
+        assert_eq!(parsed.segments[0].text, "Welcome back. Note: this part was recorded later.");
+    }
+
+    #[test]
+    fn parses_timestamps() {
+        assert_eq!(parse_timestamp("00:01:02,500"), Some(62.5));
+        assert_eq!(parse_timestamp("01:02.250"), Some(62.25));
+        assert_eq!(parse_timestamp(" 1:00:00.000 "), Some(3600.0));
+        assert_eq!(parse_timing_line("00:00:01.000 --> 00:00:02.500 align:start position:10%"), Some((1.0, 2.5)));
+        for value in ["", "12", "1:2:3:4", "aa:bb", "00:-01:00", "00:00:NaN", "00:00:inf", "１:00"] {
+            assert_eq!(parse_timestamp(value), None, "{:?}", value);
+        }
+        assert_eq!(parse_timing_line("00:00:01,000 -> 00:00:02,000"), None);
+    }
+
+    #[test]
+    fn parses_srt_cues() {
+        let srt = "1\r\n00:00:01,000 --> 00:00:03,000\r\nAna: Olá, tudo bem?\r\n\r\n2\r\n00:00:03,500 --> 00:00:05,000\r\n- 你好，世界\r\n";
+        let parsed = parse_transcript(srt, "srt").unwrap();
+        assert_eq!(parsed.segments.len(), 2);
+        assert_eq!(parsed.segments[0].speaker.as_deref(), Some("Ana"));
+        assert_eq!(parsed.segments[0].text, "Olá, tudo bem?");
+        assert_eq!(parsed.segments[1].start_time, Some(3.5));
+        assert_eq!(parsed.segments[1].speaker.as_deref(), Some("Ana"));
+        assert_eq!(parsed.segments[1].text, "你好，世界");
+    }
+
+    #[test]
+    fn keeps_text_of_srt_cues_with_broken_timing() {
+        let parsed = parse_transcript("1\n00:00:01,000 --> later\nStill here\n\n\n\n2\n", "srt").unwrap();
+        assert_eq!(parsed.segments.len(), 1);
+        assert_eq!(parsed.segments[0].start_time, None);
+        assert_eq!(parsed.segments[0].text, "00:00:01,000 --> later Still here");
+        assert!(parse_transcript("1\n00:00:01,000 --> 00:00:02,000\n\n", "srt").is_err());
+    }
+
+    #[test]
+    fn parses_vtt_voices_and_markup() {
+        let vtt = "WEBVTT - Interview\n\nNOTE speakers are tagged\n\nintro\n00:01.000 --> 00:04.000 line:0\n<v Émilie>Bonjour <i>à tous</i></v> <v.loud Bob>Hi &amp; welcome\n\n00:04.000 --> 00:05.000\n<c.yellow>Bob continues</c> &lt;3\n";
+        let parsed = parse_transcript(vtt, "vtt").unwrap();
+        let turns: Vec<(Option<&str>, &str)> = parsed.segments.iter().map(|s| (s.speaker.as_deref(), s.text.as_str())).collect();
+        assert_eq!(turns, vec![(Some("Émilie"), "Bonjour à tous"), (Some("Bob"), "Hi & welcome"), (Some("Bob"), "Bob continues <3")]);
+        assert_eq!(parsed.segments[0].start_time, Some(1.0));
+    }
+
+    #[test]
+    fn skips_vtt_cues_with_malformed_timing() {
+        let parsed = parse_transcript("WEBVTT\n\n00:01.000 --> nope\nLost\n\n00:02.000 --> 00:03.000\nKept\n", "vtt").unwrap();
+        assert_eq!(parsed.segments.len(), 1);
+        assert_eq!(parsed.segments[0].text, "Kept");
+        assert!(parse_transcript("WEBVTT\n\nSTYLE\n::cue { color: red }\n", "vtt").is_err());
+    }
+
+    #[test]
+    fn parses_descript_paragraphs_and_word_lists() {
+        let paragraphs = r#"{"speakers": [{"id": "s1", "name": "Ōtani"}], "paragraphs": [
+            {"speaker": "s1", "words": [{"text": "こんにちは", "start": 0.5, "end": 1.0}, {"text": "皆さん", "start": 1.0, "end": 1.6}]},
+            {"speaker": "s2", "text": "  Unknown   speaker  ", "start": 2.0, "end": 3.0},
+            {"speaker": "s1", "text": ""}
+        ]}"#;
+        let parsed = parse_transcript(paragraphs, "json").unwrap();
+        assert_eq!(parsed.segments.len(), 2);
+        assert_eq!(parsed.segments[0].speaker.as_deref(), Some("Ōtani"));
+        assert_eq!(parsed.segments[0].text, "こんにちは 皆さん");
+        assert_eq!((parsed.segments[0].start_time, parsed.segments[0].end_time), (Some(0.5), Some(1.6)));
+        assert_eq!(parsed.segments[1].speaker.as_deref(), Some("s2"));
+        assert_eq!(parsed.segments[1].text, "Unknown speaker");
+
+        let words = r#"{"speakers": {"1": "Ann"}, "words": [
+            {"text": "Hi", "speaker": 1, "start": 0.0, "end": 0.2},
+            {"text": "there", "speaker": 1, "start": 0.2, "end": 0.5},
+            {"text": "Hey", "speaker": 2, "start": 0.6, "end": 0.8},
+            {"word": "", "speaker": 2}
+        ]}"#;
+        let parsed = parse_transcript(words, "json").unwrap();
+        let turns: Vec<(Option<&str>, &str)> = parsed.segments.iter().map(|s| (s.speaker.as_deref(), s.text.as_str())).collect();
+        assert_eq!(turns, vec![(Some("Ann"), "Hi there"), (Some("2"), "Hey")]);
+        assert_eq!(parsed.segments[0].end_time, Some(0.5));
+    }
+
+    #[test]
+    fn rejects_malformed_json_transcripts() {
+        assert!(parse_transcript("{\"paragraphs\": [", "json").is_err());
+        assert!(parse_transcript("{\"speakers\": []}", "json").is_err());
+        assert!(parse_transcript("{\"segments\": \"text\"}", "json").is_err());
+        assert!(parse_transcript("[1, 2, 3]", "json").is_err());
+        assert!(parse_transcript("{\"paragraphs\": []}", "json").is_err());
+    }
+
+    #[test]
+    fn parses_whisper_json_segments_and_words() {
+        let json = r#"{"language": "pt", "text": "Olá mundo. Tudo bem?", "segments": [
+            {"id": 0, "start": 0.0, "end": 1.52, "text": " Olá mundo.", "speaker": "SPEAKER_00",
+             "words": [{"word": " Olá", "start": 0.0, "end": 0.6, "probability": 0.91}, {"word": " mundo.", "start": 0.6, "end": 1.52, "probability": 0.88}]},
+            {"id": 1, "start": 1.52, "end": 3.0, "text": "   "},
+            {"id": 2, "start": 3.0, "end": 4.25, "text": " Tudo bem?"}
+        ]}"#;
+        let parsed = parse_transcript(json, "json").unwrap();
+        assert_eq!(parsed.language.as_deref(), Some("pt"));
+        let segments = cues(&parsed);
+        assert_eq!(segments, vec![(Some(0.0), Some(1.52), Some("SPEAKER_00"), "Olá mundo."), (Some(3.0), Some(4.25), None, "Tudo bem?")]);
+        let words: Vec<(&str, Option<f64>, Option<f64>)> = parsed.segments[0].words.iter().map(|w| (w.text.as_str(), w.start_time, w.confidence)).collect();
+        assert_eq!(words, vec![("Olá", Some(0.0), Some(0.91)), ("mundo.", Some(0.6), Some(0.88))]);
+        assert!(parsed.segments[1].words.is_empty());
+    }
+
+    #[test]
+    fn parses_whisper_cpp_json_offsets_and_tokens() {
+        let json = r#"{"result": {"language": "de"}, "transcription": [
+            {"timestamps": {"from": "00:00:00,000", "to": "00:00:02,400"}, "offsets": {"from": 0, "to": 2400}, "text": " Guten Morgen",
+             "tokens": [{"text": "[_BEG_]", "offsets": {"from": 0, "to": 0}}, {"text": " Gut", "offsets": {"from": 0, "to": 500}, "p": 0.8},
+                        {"text": "en", "offsets": {"from": 500, "to": 900}, "p": 0.9}, {"text": " Morgen", "offsets": {"from": 900, "to": 2400}, "p": 0.95}]},
+            {"timestamps": {"from": "00:00:02,400", "to": "00:00:05,000"}, "text": " zusammen."}
+        ]}"#;
+        let parsed = parse_transcript(json, "json").unwrap();
+        assert_eq!(parsed.language.as_deref(), Some("de"));
+        assert_eq!(parsed.segments.len(), 2);
+        assert_eq!((parsed.segments[0].start_time, parsed.segments[0].end_time), (Some(0.0), Some(2.4)));
+        assert_eq!(parsed.segments[0].text, "Guten Morgen");
+        let words: Vec<(&str, Option<f64>, Option<f64>)> = parsed.segments[0].words.iter().map(|w| (w.text.as_str(), w.start_time, w.end_time)).collect();
+        assert_eq!(words, vec![("Guten", Some(0.0), Some(0.9)), ("Morgen", Some(0.9), Some(2.4))]);
+        // Without "offsets" the "timestamps" strings are used
+        assert_eq!((parsed.segments[1].start_time, parsed.segments[1].end_time), (Some(2.4), Some(5.0)));
+        assert_eq!(parsed.segments[1].text, "zusammen.");
+        assert!(parse_transcript("{\"transcription\": {}}", "json").is_err());
+    }
+
+    #[test]
+    fn parses_sbv_captions() {
+        let sbv = "0:00:01.000,0:00:03.500\nHost: Welcome to the show\nwith two lines\n\n0:00:03.500,0:00:05.000\nGlad to be here\n\n0:01:00.000,broken\nLoose text\n";
+        let parsed = parse_transcript(sbv, "sbv").unwrap();
+        assert_eq!(parsed.language, None);
+        let segments = cues(&parsed);
+        assert_eq!(segments[0], (Some(1.0), Some(3.5), Some("Host"), "Welcome to the show with two lines"));
+        assert_eq!(segments[1], (Some(3.5), Some(5.0), Some("Host"), "Glad to be here"));
+        assert_eq!(segments[2].0, None);
+        assert!(segments[2].3.ends_with("Loose text"));
+    }
+
+    #[test]
+    fn parses_ttml_cues_agents_and_language() {
+        let ttml = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <tt xmlns="http://www.w3.org/ns/ttml" xmlns:ttm="http://www.w3.org/ns/ttml#metadata" xmlns:ttp="http://www.w3.org/ns/ttml#parameter"
+                xml:lang="fr-CA" ttp:frameRate="25" ttp:tickRate="10000000">
+              <head><metadata><ttm:agent xml:id="a1" type="person"><ttm:name type="full">Émilie Roy</ttm:name></ttm:agent></metadata></head>
+              <body><div>
+                <p begin="00:00:01.500" end="00:00:03.000" ttm:agent="a1">Bonjour<br/>à tous &amp; bienvenue</p>
+                <p begin="75f" dur="1.5s"><span ttm:agent="a2">Merci</span></p>
+                <p begin="60000000t" end="7s">   </p>
+                <p begin="00:00:08:05" end="9500ms">Au revoir</p>
+              </div></body>
+            </tt>"#;
+        let parsed = parse_transcript(ttml, "ttml").unwrap();
+        assert_eq!(parsed.language.as_deref(), Some("fr-CA"));
+        assert_eq!(parsed.language.as_deref().and_then(crate::services::language_detect::normalize_language_code).as_deref(), Some("fr"));
+        let segments = cues(&parsed);
+        assert_eq!(segments, vec![
+            (Some(1.5), Some(3.0), Some("Émilie Roy"), "Bonjour à tous & bienvenue"),
+            (Some(3.0), Some(4.5), Some("a2"), "Merci"),
+            (Some(8.2), Some(9.5), None, "Au revoir"),
+        ]);
+
+        let dfxp = r#"<tt xmlns="http://www.w3.org/2006/10/ttaf1"><body><div><p begin="1s" end="2s">No language</p></div></body></tt>"#;
+        let parsed = parse_transcript(dfxp, "dfxp").unwrap();
+        assert_eq!(parsed.language, None);
+        assert_eq!(parsed.segments[0].text, "No language");
+        assert!(parse_transcript("<tt><body><p begin=\"1s\">Open", "ttml").is_err());
+    }
+
+    #[test]
+    fn extracts_docx_paragraphs() {
+        use std::io::Write;
+        use zip::write::{FileOptions, ZipWriter};
+
+        let document = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+            <w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body>
+              <w:p><w:r><w:t>Host: Welcome</w:t></w:r><w:r><w:t xml:space="preserve"> back &amp; hello</w:t></w:r></w:p>
+              <w:p><w:r><w:t>[00:01:05] Guest: Thanks</w:t><w:tab/><w:t>for having me</w:t></w:r></w:p>
+              <w:p/>
+            </w:body></w:document>"#;
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        writer.start_file("[Content_Types].xml", FileOptions::default()).unwrap();
+        writer.write_all(b"<Types/>").unwrap();
+        writer.start_file("word/document.xml", FileOptions::default()).unwrap();
+        writer.write_all(document.as_bytes()).unwrap();
+        let bytes = writer.finish().unwrap().into_inner();
+
+        let text = extract_docx_text(&bytes).unwrap();
+        assert_eq!(text, "Host: Welcome back & hello\n\n[00:01:05] Guest: Thanks\tfor having me");
+        let parsed = parse_transcript(&text, "docx").unwrap();
+        let turns: Vec<(Option<&str>, &str)> = parsed.segments.iter().map(|s| (s.speaker.as_deref(), s.text.as_str())).collect();
+        assert_eq!(turns, vec![(Some("Host"), "Welcome back & hello"), (Some("Guest"), "Thanks\tfor having me")]);
+        assert_eq!(parsed.segments[1].start_time, Some(65.0));
+
+        assert!(extract_docx_text(b"not a zip").is_err());
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        writer.start_file("word/other.xml", FileOptions::default()).unwrap();
+        let bytes = writer.finish().unwrap().into_inner();
+        assert!(extract_docx_text(&bytes).is_err());
+    }
+
+    #[test]
+    fn tells_anonymous_speaker_ids_from_names() {
+        for label in ["SPEAKER_00", "Speaker 2", "spk_1", "3", "A"] {
+            assert!(is_generic_speaker_label(label), "{}", label);
+        }
+        for label in ["Jane Doe", "Host", "Ōtani", "Dr. Lee"] {
+            assert!(!is_generic_speaker_label(label), "{}", label);
+        }
+    }
+}
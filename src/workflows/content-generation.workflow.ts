@@ -23,6 +23,7 @@ export interface ContentGenerationState extends BaseWorkflowState {
     constraints?: Record<string, any>;
     templateId?: string; // Add template ID for saving
   };
+  targetLanguage?: string; // Generate from each recording's track in this language
   generatedContent?: {
     title: string;
     content: string | string[];
@@ -48,6 +49,9 @@ export class ContentGenerationWorkflow extends BaseWorkflow<ContentGenerationSta
       template: {
         value: (x?: any, y?: any) => y ?? x ?? {},
       },
+      targetLanguage: {
+        value: (x?: string, y?: string) => y ?? x,
+      },
       generatedContent: {
         value: (x?: any, y?: any) => y ?? x,
       },
@@ -134,6 +138,7 @@ export class ContentGenerationWorkflow extends BaseWorkflow<ContentGenerationSta
   
   protected buildGraph(): void {
     this.graph.addNode('validateInput', this.validateInput.bind(this));
+    this.graph.addNode('resolveTracks', this.resolveTracks.bind(this));
     this.graph.addNode('prepareContext', this.prepareContext.bind(this));
     this.graph.addNode('generateContent', this.generateContent.bind(this));
     this.graph.addNode('formatContent', this.formatContent.bind(this));
@@ -142,7 +147,8 @@ export class ContentGenerationWorkflow extends BaseWorkflow<ContentGenerationSta
     
     this.graph.setEntryPoint('validateInput');
     
-    this.graph.addEdge('validateInput', 'prepareContext');
+    this.graph.addEdge('validateInput', 'resolveTracks');
+    this.graph.addEdge('resolveTracks', 'prepareContext');
     this.graph.addEdge('prepareContext', 'generateContent');
     this.graph.addEdge('generateContent', 'formatContent');
     this.graph.addEdge('formatContent', 'saveContent');
@@ -191,6 +197,46 @@ export class ContentGenerationWorkflow extends BaseWorkflow<ContentGenerationSta
     return state;
   }
   
+  // Swap each transcript for its recording's track in the target language, so the content is
+  // generated from, and saved with, the tracks actually used
+  private async resolveTracks(state: ContentGenerationState): Promise<ContentGenerationState> {
+    this.startTimer('resolveTracks');
+    this.logStep('resolveTracks', { targetLanguage: state.targetLanguage });
+    
+    if (!state.targetLanguage) {
+      this.endTimer('resolveTracks');
+      return state;
+    }
+    
+    try {
+      const transcripts = await Promise.all(
+        state.transcripts.map(async transcript => {
+          const track = await invoke<{ transcript_id: string }>('resolve_transcript_track', {
+            transcriptId: transcript.id,
+            language: state.targetLanguage,
+          });
+          if (track.transcript_id === transcript.id) {
+            return transcript;
+          }
+          
+          const resolved = await invoke<{ content: string }>('get_transcript_by_id', { transcriptId: track.transcript_id });
+          // Analysis was of the other track's text, so it no longer applies
+          return { id: track.transcript_id, content: resolved.content };
+        })
+      );
+      
+      this.endTimer('resolveTracks');
+      return { ...state, transcripts };
+    } catch (error) {
+      this.endTimer('resolveTracks');
+      return this.handleStepError(
+        error instanceof Error ? error : new Error(String(error)),
+        'resolveTracks',
+        state
+      );
+    }
+  }
+  
   private async prepareContext(state: ContentGenerationState): Promise<ContentGenerationState> {
     this.startTimer('prepareContext');
     this.logStep('prepareContext');
@@ -44,13 +44,15 @@ export class WorkflowOrchestrator {
     transcripts: Array<{ id: string; content: string; analysis?: any }>,
     templateType: 'thread' | 'carousel' | 'newsletter' | 'blog' | 'video-script',
     templateConstraints?: Record<string, any>,
-    templateId?: string
+    templateId?: string,
+    targetLanguage?: string
   ) {
     console.log('[WorkflowOrchestrator] generateContent called with:', {
       transcriptCount: transcripts.length,
       templateType,
       templateConstraints,
       templateId,
+      targetLanguage,
       firstTranscriptSample: transcripts[0]?.content?.substring(0, 100)
     });
     
@@ -62,6 +64,7 @@ export class WorkflowOrchestrator {
           constraints: templateConstraints,
           templateId,
         },
+        targetLanguage,
         messages: [],
       });
       